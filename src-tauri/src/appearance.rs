@@ -0,0 +1,91 @@
+//! Native appearance of the calendar panel (vibrancy material, opacity, corner radius).
+
+use serde::{Deserialize, Serialize};
+use tauri::window::{Effect, EffectState, EffectsBuilder};
+use tauri::Manager;
+use tauri_nspanel::ManagerExt;
+
+const MIN_OPACITY: f64 = 0.3;
+const MAX_CORNER_RADIUS: f64 = 32.0;
+
+/// Background material of the calendar panel.
+///
+/// `Solid` leaves the webview background as is; the others map to macOS vibrancy materials.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PanelMaterial {
+    Solid,
+    Popover,
+    Menu,
+    HudWindow,
+    Sidebar,
+    UnderWindowBackground,
+}
+
+impl PanelMaterial {
+    fn effect(&self) -> Option<Effect> {
+        match self {
+            PanelMaterial::Solid => None,
+            PanelMaterial::Popover => Some(Effect::Popover),
+            PanelMaterial::Menu => Some(Effect::Menu),
+            PanelMaterial::HudWindow => Some(Effect::HudWindow),
+            PanelMaterial::Sidebar => Some(Effect::Sidebar),
+            PanelMaterial::UnderWindowBackground => Some(Effect::UnderWindowBackground),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PanelAppearance {
+    pub material: PanelMaterial,
+    /// Window alpha between 0.3 and 1.0.
+    pub opacity: f64,
+    /// Corner radius in logical points, matching the popup's CSS radius by default.
+    pub corner_radius: f64,
+}
+
+impl Default for PanelAppearance {
+    fn default() -> Self {
+        Self {
+            material: PanelMaterial::Solid,
+            opacity: 1.0,
+            corner_radius: 16.0,
+        }
+    }
+}
+
+impl PanelAppearance {
+    /// Returns a copy with opacity and radius clamped to values the panel can render.
+    pub fn clamped(&self) -> Self {
+        Self {
+            material: self.material,
+            opacity: self.opacity.clamp(MIN_OPACITY, 1.0),
+            corner_radius: self.corner_radius.clamp(0.0, MAX_CORNER_RADIUS),
+        }
+    }
+}
+
+/// Applies the appearance to the calendar window and its panel.
+pub fn apply_panel_appearance(app: &tauri::AppHandle, appearance: &PanelAppearance) -> Result<(), String> {
+    let appearance = appearance.clamped();
+
+    if let Some(window) = app.get_webview_window("settings") {
+        let effects = appearance.material.effect().map(|effect| {
+            EffectsBuilder::new()
+                .effect(effect)
+                .state(EffectState::Active)
+                .radius(appearance.corner_radius)
+                .build()
+        });
+        window
+            .set_effects(effects)
+            .map_err(|e| format!("Failed to apply window effects: {}", e))?;
+    }
+
+    if let Ok(panel) = app.get_webview_panel("settings") {
+        panel.set_alpha_value(appearance.opacity);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+//! Backups of everything the user has entered, for moving to another machine.
+//!
+//! A backup is a single JSON file holding the settings, events, birthdays, custom
+//! holidays and paydays, tagged with `format` and a `version` of its layout. The
+//! settings are kept as plain JSON so that an older backup goes through
+//! `settings_migration` like an old settings file would. A backup is checked in full
+//! before anything is restored, so a bad file leaves the current data untouched.
+
+use crate::birthdays::Birthday;
+use crate::custom_holidays::CustomHoliday;
+use crate::events::Event;
+use crate::paydays::Payday;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// Marks a file as a ZemenBar backup.
+pub const BACKUP_FORMAT: &str = "zemenbar-backup";
+
+/// Layout version written by this build.
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Name of the backup of the current data written in the app data directory before a restore.
+pub const PRE_RESTORE_FILE: &str = "backup-before-restore.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Backup {
+    pub format: String,
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    /// `AppSettings` as stored in `settings.json`, in the layout of the build that wrote it.
+    pub settings: Value,
+    #[serde(default)]
+    pub events: Vec<Event>,
+    #[serde(default)]
+    pub birthdays: Vec<Birthday>,
+    #[serde(default)]
+    pub custom_holidays: Vec<CustomHoliday>,
+    #[serde(default)]
+    pub paydays: Vec<Payday>,
+}
+
+/// How many entries of each kind a backup holds.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BackupSummary {
+    pub events: usize,
+    pub birthdays: usize,
+    pub custom_holidays: usize,
+    pub paydays: usize,
+}
+
+/// Where each store is restored to.
+pub struct StorePaths {
+    pub events: PathBuf,
+    pub birthdays: PathBuf,
+    pub custom_holidays: PathBuf,
+    pub paydays: PathBuf,
+}
+
+impl Backup {
+    pub fn new(
+        settings: Value,
+        events: Vec<Event>,
+        birthdays: Vec<Birthday>,
+        custom_holidays: Vec<CustomHoliday>,
+        paydays: Vec<Payday>,
+    ) -> Self {
+        Self {
+            format: BACKUP_FORMAT.to_string(),
+            version: BACKUP_VERSION,
+            created_at: Utc::now(),
+            settings,
+            events,
+            birthdays,
+            custom_holidays,
+            paydays,
+        }
+    }
+
+    pub fn summary(&self) -> BackupSummary {
+        BackupSummary {
+            events: self.events.len(),
+            birthdays: self.birthdays.len(),
+            custom_holidays: self.custom_holidays.len(),
+            paydays: self.paydays.len(),
+        }
+    }
+
+    /// Rejects entries the stores would not have written: empty names, bad dates and
+    /// ids used twice.
+    fn validate(&self) -> Result<(), String> {
+        if !self.settings.is_object() {
+            return Err("Invalid backup: settings are not a JSON object".to_string());
+        }
+        unique_ids("event", self.events.iter().map(|event| event.id.as_str()))?;
+        unique_ids("birthday", self.birthdays.iter().map(|birthday| birthday.id.as_str()))?;
+        unique_ids("payday", self.paydays.iter().map(|payday| payday.id.as_str()))?;
+        unique_ids("custom holiday", self.custom_holidays.iter().filter_map(|holiday| holiday.id.as_deref()))?;
+
+        if let Some(event) = self.events.iter().find(|event| event.title.trim().is_empty() || !event.date.is_valid()) {
+            return Err(format!("Invalid backup: event {} has no title or an invalid date", event.id));
+        }
+        if let Some(birthday) =
+            self.birthdays.iter().find(|birthday| birthday.name.trim().is_empty() || !birthday.date.is_valid())
+        {
+            return Err(format!("Invalid backup: birthday {} has no name or an invalid date", birthday.id));
+        }
+        if let Some(payday) =
+            self.paydays.iter().find(|payday| payday.title.trim().is_empty() || !payday.date.is_valid())
+        {
+            return Err(format!("Invalid backup: payday {} has no title or an invalid date", payday.id));
+        }
+        for holiday in &self.custom_holidays {
+            holiday.validate().map_err(|e| format!("Invalid backup: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+fn unique_ids<'a>(kind: &str, ids: impl Iterator<Item = &'a str>) -> Result<(), String> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if id.trim().is_empty() {
+            return Err(format!("Invalid backup: a {} has no id", kind));
+        }
+        if !seen.insert(id) {
+            return Err(format!("Invalid backup: {} id {} is used twice", kind, id));
+        }
+    }
+    Ok(())
+}
+
+fn write_json(path: &Path, value: &impl Serialize, what: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {} directory: {}", what, e))?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", what, e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {} file: {}", what, e))
+}
+
+pub fn write_backup(path: &Path, backup: &Backup) -> Result<(), String> {
+    write_json(path, backup, "backup")
+}
+
+/// Reads and checks a backup. Files from a newer build are refused rather than half read.
+pub fn read_backup(path: &Path) -> Result<Backup, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read backup file: {}", e))?;
+    let value: Value = serde_json::from_str(&content).map_err(|e| format!("Failed to parse backup: {}", e))?;
+
+    if value.get("format").and_then(Value::as_str) != Some(BACKUP_FORMAT) {
+        return Err("Failed to read backup: not a ZemenBar backup file".to_string());
+    }
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version == 0 || version > BACKUP_VERSION as u64 {
+        return Err(format!("Failed to read backup: version {} is not supported by this version of ZemenBar", version));
+    }
+
+    let backup: Backup = serde_json::from_value(value).map_err(|e| format!("Failed to parse backup: {}", e))?;
+    backup.validate()?;
+    Ok(backup)
+}
+
+/// Writes the backup's events, birthdays, custom holidays and paydays over the store
+/// files. The stores still have to be reloaded, and the settings saved, by the caller.
+pub fn restore_stores(backup: &Backup, paths: &StorePaths) -> Result<(), String> {
+    write_json(&paths.events, &backup.events, "events")?;
+    write_json(&paths.birthdays, &backup.birthdays, "birthdays")?;
+    write_json(&paths.custom_holidays, &backup.custom_holidays, "custom holidays")?;
+    write_json(&paths.paydays, &backup.paydays, "paydays")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reminders::HolidayCountdown;
+    use crate::{settings_migration, AppSettings};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("zemenbar-backup-{}-{}", std::process::id(), name))
+    }
+
+    fn custom_holiday(id: &str) -> CustomHoliday {
+        CustomHoliday {
+            id: Some(id.to_string()),
+            name: "Family day".to_string(),
+            name_amharic: None,
+            month: Some(3),
+            day: Some(10),
+            rule: None,
+            is_public: false,
+            category: Default::default(),
+        }
+    }
+
+    #[test]
+    fn countdown_on_a_custom_holiday_round_trips() {
+        let dir = temp_dir("countdown");
+        let settings = AppSettings {
+            holiday_countdowns: vec![HolidayCountdown { holiday: "custom_1".to_string(), days_before: vec![7, 1] }],
+            ..AppSettings::default()
+        };
+        let backup = Backup::new(
+            serde_json::to_value(&settings).unwrap(),
+            Vec::new(),
+            Vec::new(),
+            vec![custom_holiday("custom_1")],
+            Vec::new(),
+        );
+        let path = dir.join("backup.json");
+        write_backup(&path, &backup).unwrap();
+
+        // As import_backup does on a machine that has never seen the custom holiday
+        let mut read = read_backup(&path).unwrap();
+        settings_migration::migrate(&mut read.settings).unwrap();
+        let restored: AppSettings = serde_json::from_value(read.settings.clone()).unwrap();
+        assert_eq!(restored.validate(), Ok(()));
+        assert_eq!(restored.holiday_countdowns, settings.holiday_countdowns);
+
+        let paths = StorePaths {
+            events: dir.join("events.json"),
+            birthdays: dir.join("birthdays.json"),
+            custom_holidays: dir.join("custom_holidays.json"),
+            paydays: dir.join("paydays.json"),
+        };
+        restore_stores(&read, &paths).unwrap();
+        let holidays: Vec<CustomHoliday> =
+            serde_json::from_str(&std::fs::read_to_string(&paths.custom_holidays).unwrap()).unwrap();
+        assert_eq!(holidays.iter().filter_map(|holiday| holiday.id.as_deref()).collect::<Vec<_>>(), ["custom_1"]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,636 @@
+//! Two-way sync of the events store with a CalDAV calendar, such as one on Nextcloud or Fastmail.
+//!
+//! The calendar's URL and the username are kept in the settings and the password in
+//! the macOS keychain. A background thread syncs every `sync_interval_minutes`, and
+//! `sync_caldav` syncs on demand. Each stored event is one calendar object on the
+//! server, written by `ics::event_calendar`; `caldav_sync.json` in the app data
+//! directory records which object each event was last synced with, and its ETag.
+//!
+//! An event changed on both sides since the last sync keeps the version with the later
+//! last-modified time. An event deleted on one side is deleted on the other too, unless
+//! it was changed there since the last sync, in which case the change wins and the event
+//! comes back.
+
+use crate::events::{self, Event};
+use crate::ics::{self, RemoteEvent};
+use crate::logging::LogError;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+const KEYCHAIN_SERVICE: &str = "ZemenBar CalDAV";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the background thread checks whether a sync is due.
+const TICK: Duration = Duration::from_secs(60);
+
+const MIN_SYNC_INTERVAL_MINUTES: u32 = 5;
+const MAX_SYNC_INTERVAL_MINUTES: u32 = 24 * 60;
+
+const CALENDAR_QUERY: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop><d:getetag/><c:calendar-data/></d:prop>
+  <c:filter><c:comp-filter name="VCALENDAR"><c:comp-filter name="VEVENT"/></c:comp-filter></c:filter>
+</c:calendar-query>"#;
+
+fn default_sync_interval() -> u32 {
+    15
+}
+
+/// The CalDAV calendar events are synced with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalDavAccount {
+    /// URL of the calendar collection, e.g.
+    /// `https://cloud.example.com/remote.php/dav/calendars/abebe/personal/`.
+    pub url: String,
+    pub username: String,
+    #[serde(default = "default_sync_interval")]
+    pub sync_interval_minutes: u32,
+}
+
+impl CalDavAccount {
+    pub fn validate(&self) -> Result<(), String> {
+        let url = self.url.trim();
+        let local = ["http://localhost", "http://127.0.0.1"].iter().any(|prefix| url.starts_with(prefix));
+        if !url.starts_with("https://") && !local {
+            return Err(format!("CalDAV URL must start with https://: {}", url));
+        }
+        if self.username.trim().is_empty() {
+            return Err("CalDAV username cannot be empty".to_string());
+        }
+        if !(MIN_SYNC_INTERVAL_MINUTES..=MAX_SYNC_INTERVAL_MINUTES).contains(&self.sync_interval_minutes) {
+            return Err(format!(
+                "Sync interval must be between {} and {} minutes, got {}",
+                MIN_SYNC_INTERVAL_MINUTES, MAX_SYNC_INTERVAL_MINUTES, self.sync_interval_minutes
+            ));
+        }
+        Ok(())
+    }
+
+    /// Collection URL with a trailing slash, so object names can be appended.
+    fn collection_url(&self) -> String {
+        format!("{}/", self.url.trim().trim_end_matches('/'))
+    }
+
+    fn keychain_entry(&self) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(KEYCHAIN_SERVICE, &format!("{}@{}", self.username.trim(), self.collection_url()))
+            .map_err(|e| format!("Failed to open keychain entry: {}", e))
+    }
+
+    pub fn store_password(&self, password: &str) -> Result<(), String> {
+        self.keychain_entry()?
+            .set_password(password)
+            .map_err(|e| format!("Failed to save CalDAV password to the keychain: {}", e))
+    }
+
+    pub fn delete_password(&self) -> Result<(), String> {
+        match self.keychain_entry()?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(format!("Failed to remove CalDAV password from the keychain: {}", e)),
+        }
+    }
+
+    fn password(&self) -> Result<String, String> {
+        self.keychain_entry()?
+            .get_password()
+            .map_err(|e| format!("Failed to read CalDAV password from the keychain: {}", e))
+    }
+}
+
+/// What one sync changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CalDavSyncResult {
+    /// Events added or updated from the server.
+    pub pulled: usize,
+    /// Events written to the server.
+    pub pushed: usize,
+    pub deleted_locally: usize,
+    pub deleted_remotely: usize,
+}
+
+/// What the last sync knew about one event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncedEvent {
+    event_id: String,
+    uid: String,
+    /// Absolute URL of the calendar object.
+    href: String,
+    etag: Option<String>,
+    synced_at: DateTime<Utc>,
+    /// See `RemoteEvent::pull_only`.
+    #[serde(default)]
+    pull_only: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    /// Collection the entries belong to; entries for another calendar are dropped.
+    url: String,
+    events: Vec<SyncedEvent>,
+}
+
+/// Held while a sync runs, so the background thread and `sync_caldav` never overlap.
+static SYNC_LOCK: Mutex<()> = Mutex::new(());
+
+fn read_state(path: &Path) -> Result<SyncState, String> {
+    if !path.exists() {
+        return Ok(SyncState::default());
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read CalDAV sync state: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse CalDAV sync state: {}", e))
+}
+
+fn write_state(path: &Path, state: &SyncState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create CalDAV sync directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(state).map_err(|e| format!("Failed to serialize CalDAV sync state: {}", e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write CalDAV sync state: {}", e))
+}
+
+/// Forgets which server objects events were synced with, e.g. after the account is removed.
+pub fn clear_state(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| format!("Failed to remove CalDAV sync state: {}", e))?;
+    }
+    Ok(())
+}
+
+/// A calendar object as listed by the server.
+struct RemoteObject {
+    href: String,
+    etag: Option<String>,
+    data: String,
+}
+
+/// Outcome of a conditional write.
+enum WriteOutcome {
+    /// Written; carries the new ETag when the server sent one.
+    Written(Option<String>),
+    /// The object changed on the server in the meantime; left for the next sync.
+    Conflict,
+}
+
+struct Client {
+    agent: ureq::Agent,
+    url: String,
+    authorization: String,
+}
+
+impl Client {
+    fn new(account: &CalDavAccount, password: &str) -> Self {
+        let credentials = format!("{}:{}", account.username.trim(), password);
+        Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: account.collection_url(),
+            authorization: format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(credentials)),
+        }
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        self.agent.request(method, url).set("Authorization", &self.authorization)
+    }
+
+    /// Turns an `href` from a multistatus response into an absolute URL.
+    fn resolve(&self, href: &str) -> String {
+        if href.starts_with("http://") || href.starts_with("https://") {
+            return href.to_string();
+        }
+        if href.starts_with('/') {
+            let host_start = self.url.find("://").map_or(0, |index| index + 3);
+            let origin_end = self.url[host_start..].find('/').map_or(self.url.len(), |index| host_start + index);
+            return format!("{}{}", &self.url[..origin_end], href);
+        }
+        format!("{}{}", self.url, href)
+    }
+
+    fn list(&self) -> Result<Vec<RemoteObject>, String> {
+        let response = self
+            .request("REPORT", &self.url)
+            .set("Depth", "1")
+            .set("Content-Type", "application/xml; charset=utf-8")
+            .send_string(CALENDAR_QUERY)
+            .map_err(|e| format!("Failed to list CalDAV events: {}", e))?;
+        let body = response.into_string().map_err(|e| format!("Failed to read CalDAV response: {}", e))?;
+        Ok(parse_multistatus(&body)?
+            .into_iter()
+            .map(|object| RemoteObject {
+                href: self.resolve(&object.href),
+                ..object
+            })
+            .collect())
+    }
+
+    /// Writes a calendar object; `etag` is the version being replaced, `None` for a new object.
+    fn put(&self, href: &str, calendar: &str, etag: Option<&str>) -> Result<WriteOutcome, String> {
+        let request = self.request("PUT", href).set("Content-Type", "text/calendar; charset=utf-8");
+        let request = match etag {
+            Some(etag) => request.set("If-Match", etag),
+            None => request.set("If-None-Match", "*"),
+        };
+        match request.send_string(calendar) {
+            Ok(response) => Ok(WriteOutcome::Written(response.header("ETag").map(str::to_string))),
+            Err(ureq::Error::Status(412, _)) => Ok(WriteOutcome::Conflict),
+            Err(e) => Err(format!("Failed to write CalDAV event: {}", e)),
+        }
+    }
+
+    fn delete(&self, href: &str, etag: Option<&str>) -> Result<WriteOutcome, String> {
+        let request = self.request("DELETE", href);
+        let request = match etag {
+            Some(etag) => request.set("If-Match", etag),
+            None => request,
+        };
+        match request.call() {
+            Ok(_) | Err(ureq::Error::Status(404 | 410, _)) => Ok(WriteOutcome::Written(None)),
+            Err(ureq::Error::Status(412, _)) => Ok(WriteOutcome::Conflict),
+            Err(e) => Err(format!("Failed to delete CalDAV event: {}", e)),
+        }
+    }
+}
+
+/// Calendar objects in a `multistatus` response, skipping entries without calendar data.
+fn parse_multistatus(xml: &str) -> Result<Vec<RemoteObject>, String> {
+    use quick_xml::events::Event as XmlEvent;
+
+    #[derive(Clone, Copy)]
+    enum Field {
+        Href,
+        Etag,
+        Data,
+    }
+
+    let mut reader = quick_xml::Reader::from_str(xml);
+    let mut objects = Vec::new();
+    let mut current: Option<RemoteObject> = None;
+    let mut field: Option<Field> = None;
+
+    loop {
+        let event = reader.read_event().map_err(|e| format!("Failed to parse CalDAV response: {}", e))?;
+        let text = match &event {
+            XmlEvent::Start(tag) => {
+                match tag.local_name().as_ref() {
+                    b"response" => {
+                        current = Some(RemoteObject {
+                            href: String::new(),
+                            etag: None,
+                            data: String::new(),
+                        })
+                    }
+                    b"href" => field = Some(Field::Href),
+                    b"getetag" => field = Some(Field::Etag),
+                    b"calendar-data" => field = Some(Field::Data),
+                    _ => {}
+                }
+                continue;
+            }
+            XmlEvent::End(tag) => {
+                match tag.local_name().as_ref() {
+                    b"response" => objects.extend(current.take().filter(|object| !object.data.trim().is_empty())),
+                    b"href" | b"getetag" | b"calendar-data" => field = None,
+                    _ => {}
+                }
+                continue;
+            }
+            XmlEvent::Text(text) => text.unescape().map_err(|e| format!("Failed to parse CalDAV response: {}", e))?.into_owned(),
+            XmlEvent::CData(data) => String::from_utf8_lossy(data).into_owned(),
+            XmlEvent::Eof => break,
+            _ => continue,
+        };
+        match (field, current.as_mut()) {
+            (Some(Field::Href), Some(object)) => object.href.push_str(text.trim()),
+            (Some(Field::Etag), Some(object)) => object.etag = Some(text.trim().to_string()),
+            (Some(Field::Data), Some(object)) => object.data.push_str(&text),
+            _ => {}
+        }
+    }
+    Ok(objects)
+}
+
+fn event_uid(event: &Event) -> String {
+    format!("{}@zemenbar", event.id)
+}
+
+/// Local changes a sync decided on, applied together once the server is up to date.
+#[derive(Default)]
+struct LocalChanges {
+    upserts: Vec<Event>,
+    removals: Vec<String>,
+}
+
+impl LocalChanges {
+    /// Queues the server copy of an event to replace the local one, and returns whether it is pull-only.
+    fn pull(&mut self, event_id: &str, remote: RemoteEvent) -> Option<bool> {
+        let event = remote.input.into_event(event_id.to_string()).ok()?;
+        self.upserts.push(event);
+        Some(remote.pull_only)
+    }
+}
+
+/// Whether `event` was edited here since `item` was last synced.
+fn changed_since_sync(event: &Event, item: &SyncedEvent) -> bool {
+    event.updated_at.is_some_and(|updated| updated > item.synced_at)
+}
+
+/// Whether the server copy of an event on both sides replaces the one here: it changed on
+/// the server and not here, or on both sides and the server's is at least as recent, or it
+/// changed here but the server copy is pull-only and cannot be written back.
+fn remote_wins(item: &SyncedEvent, event: &Event, remote: &RemoteEvent, remote_changed: bool) -> bool {
+    let local_changed = changed_since_sync(event, item);
+    let remote_newer = remote.last_modified.unwrap_or(item.synced_at) >= event.updated_at.unwrap_or(item.synced_at);
+    (remote_changed && (!local_changed || remote_newer)) || (local_changed && remote.pull_only)
+}
+
+/// Brings the events store and the server in line, following the rules in the module docs.
+fn sync_with(client: &Client, events_path: &Path, state_path: &Path) -> Result<CalDavSyncResult, String> {
+    let mut state = read_state(state_path)?;
+    if state.url != client.url {
+        state = SyncState {
+            url: client.url.clone(),
+            events: Vec::new(),
+        };
+    }
+
+    let timezone = crate::today_timezone();
+    let remote = client.list()?;
+    let remote_by_href: HashMap<&str, &RemoteObject> = remote.iter().map(|object| (object.href.as_str(), object)).collect();
+    let local: HashMap<String, Event> = events::all_events().into_iter().map(|event| (event.id.clone(), event)).collect();
+
+    let mut result = CalDavSyncResult::default();
+    let mut changes = LocalChanges::default();
+    // Entries to keep, with whether their server copy changed in this sync.
+    let mut synced: Vec<(SyncedEvent, bool)> = Vec::new();
+    let mut linked_events: HashSet<String> = HashSet::new();
+    let mut known_hrefs: HashSet<String> = HashSet::new();
+
+    for item in state.events {
+        known_hrefs.insert(item.href.clone());
+        let local_event = local.get(&item.event_id);
+        let local_changed = local_event.is_some_and(|event| changed_since_sync(event, &item));
+        let object = remote_by_href.get(item.href.as_str());
+
+        match (local_event, object) {
+            (Some(event), Some(object)) => {
+                linked_events.insert(event.id.clone());
+                let remote_changed = object.etag != item.etag;
+                let Some(remote) = ics::remote_event(&object.data, timezone) else {
+                    synced.push((item, false));
+                    continue;
+                };
+                if remote_wins(&item, event, &remote, remote_changed) {
+                    match changes.pull(&item.event_id, remote) {
+                        Some(pull_only) => {
+                            result.pulled += 1;
+                            synced.push((SyncedEvent { etag: object.etag.clone(), pull_only, ..item }, true));
+                        }
+                        None => synced.push((item, false)),
+                    }
+                } else if local_changed {
+                    let Some(calendar) = ics::event_calendar(event, &item.uid, Utc::now()) else {
+                        synced.push((item, false));
+                        continue;
+                    };
+                    match client.put(&item.href, &calendar, object.etag.as_deref())? {
+                        WriteOutcome::Written(etag) => {
+                            result.pushed += 1;
+                            synced.push((SyncedEvent { etag, ..item }, true));
+                        }
+                        WriteOutcome::Conflict => synced.push((item, false)),
+                    }
+                } else {
+                    synced.push((item, false));
+                }
+            }
+            (None, Some(object)) => {
+                if object.etag != item.etag {
+                    // Changed on the server after it was deleted here: the change wins.
+                    let pulled = ics::remote_event(&object.data, timezone).and_then(|remote| changes.pull(&item.event_id, remote));
+                    if let Some(pull_only) = pulled {
+                        result.pulled += 1;
+                        synced.push((SyncedEvent { etag: object.etag.clone(), pull_only, ..item }, true));
+                    }
+                } else {
+                    match client.delete(&item.href, item.etag.as_deref())? {
+                        WriteOutcome::Written(_) => result.deleted_remotely += 1,
+                        WriteOutcome::Conflict => synced.push((item, false)),
+                    }
+                }
+            }
+            (Some(event), None) => {
+                linked_events.insert(event.id.clone());
+                if local_changed && !item.pull_only {
+                    // Changed here after it was deleted on the server: written back as a new object.
+                    let Some(calendar) = ics::event_calendar(event, &item.uid, Utc::now()) else {
+                        synced.push((item, false));
+                        continue;
+                    };
+                    match client.put(&item.href, &calendar, None)? {
+                        WriteOutcome::Written(etag) => {
+                            result.pushed += 1;
+                            synced.push((SyncedEvent { etag, ..item }, true));
+                        }
+                        WriteOutcome::Conflict => synced.push((item, false)),
+                    }
+                } else {
+                    changes.removals.push(item.event_id);
+                    result.deleted_locally += 1;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    // Objects the last sync did not know about: new on the server, or event copies whose sync state was lost.
+    for object in remote.iter().filter(|object| !known_hrefs.contains(&object.href)) {
+        let Some(remote) = ics::remote_event(&object.data, timezone) else {
+            continue;
+        };
+        let existing = local
+            .values()
+            .find(|event| event_uid(event) == remote.uid && !linked_events.contains(&event.id))
+            .map(|event| event.id.clone());
+        let event_id = existing.unwrap_or_else(|| format!("{}-{}", events::new_event_id(), result.pulled));
+        let uid = remote.uid.clone();
+        if let Some(pull_only) = changes.pull(&event_id, remote) {
+            result.pulled += 1;
+            linked_events.insert(event_id.clone());
+            let item = SyncedEvent {
+                event_id,
+                uid,
+                href: object.href.clone(),
+                etag: object.etag.clone(),
+                synced_at: Utc::now(),
+                pull_only,
+            };
+            synced.push((item, true));
+        }
+    }
+
+    for event in local.values().filter(|event| !linked_events.contains(&event.id)) {
+        let uid = event_uid(event);
+        let href = format!("{}{}.ics", client.url, event.id);
+        let Some(calendar) = ics::event_calendar(event, &uid, Utc::now()) else {
+            continue;
+        };
+        if let WriteOutcome::Written(etag) = client.put(&href, &calendar, None)? {
+            result.pushed += 1;
+            let item = SyncedEvent {
+                event_id: event.id.clone(),
+                uid,
+                href,
+                etag,
+                synced_at: Utc::now(),
+                pull_only: false,
+            };
+            synced.push((item, true));
+        }
+    }
+
+    if !changes.upserts.is_empty() || !changes.removals.is_empty() {
+        events::modify_events(events_path, |events| {
+            events.retain(|event| !changes.removals.contains(&event.id));
+            for event in changes.upserts {
+                match events.iter_mut().find(|existing| existing.id == event.id) {
+                    Some(existing) => *existing = event,
+                    None => events.push(event),
+                }
+            }
+            Ok(())
+        })?;
+    }
+
+    // Stamped after the local changes, so pulled events do not look edited since this sync.
+    let synced_at = Utc::now();
+    state.events = synced
+        .into_iter()
+        .map(|(item, touched)| if touched { SyncedEvent { synced_at, ..item } } else { item })
+        .collect();
+    write_state(state_path, &state)?;
+    Ok(result)
+}
+
+/// Syncs the events store with `account` and tells the windows when events changed.
+pub fn sync(app: &tauri::AppHandle, account: &CalDavAccount) -> Result<CalDavSyncResult, String> {
+    let _guard = SYNC_LOCK.lock().map_err(|e| format!("Failed to lock CalDAV sync: {}", e))?;
+    let client = Client::new(account, &account.password()?);
+    let result = sync_with(&client, &crate::get_events_path(app)?, &crate::get_caldav_state_path(app)?)?;
+    if result.pulled > 0 || result.deleted_locally > 0 {
+        app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    }
+    Ok(result)
+}
+
+/// Starts the background thread that syncs whenever the account's interval has passed.
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut last_attempt: Option<Instant> = None;
+        loop {
+            std::thread::sleep(TICK);
+
+            let Some(account) = crate::load_settings(app.clone()).ok().and_then(|settings| settings.caldav) else {
+                continue;
+            };
+            let interval = Duration::from_secs(account.sync_interval_minutes as u64 * 60);
+            if last_attempt.is_some_and(|attempt| attempt.elapsed() < interval) {
+                continue;
+            }
+            last_attempt = Some(Instant::now());
+            if let Err(e) = sync(&app, &account) {
+                tracing::warn!("CalDAV sync failed: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventInput;
+    use crate::{EthiopianDate, EthiopianDateInput};
+
+    fn synced_at() -> DateTime<Utc> {
+        "2025-01-01T12:00:00Z".parse().unwrap()
+    }
+
+    fn item() -> SyncedEvent {
+        SyncedEvent {
+            event_id: "evt-1".to_string(),
+            uid: "evt-1@zemenbar".to_string(),
+            href: "https://dav.example.com/calendars/a/evt-1.ics".to_string(),
+            etag: Some("\"1\"".to_string()),
+            synced_at: synced_at(),
+            pull_only: false,
+        }
+    }
+
+    /// The event here, edited `minutes` after the last sync, or before it when negative.
+    fn local(minutes: i64) -> Event {
+        Event {
+            id: "evt-1".to_string(),
+            date: EthiopianDate::new(2017, 4, 14),
+            title: "Here".to_string(),
+            notes: String::new(),
+            color: None,
+            recurrence: None,
+            time: None,
+            reminder_minutes: Vec::new(),
+            updated_at: Some(synced_at() + chrono::Duration::minutes(minutes)),
+        }
+    }
+
+    /// The server copy, last modified `minutes` after the last sync.
+    fn remote(minutes: i64, pull_only: bool) -> RemoteEvent {
+        RemoteEvent {
+            uid: "evt-1@zemenbar".to_string(),
+            last_modified: Some(synced_at() + chrono::Duration::minutes(minutes)),
+            input: EventInput {
+                date: EthiopianDateInput { year: 2017, month: 4, day: 14 },
+                title: "There".to_string(),
+                notes: String::new(),
+                color: None,
+                recurrence: None,
+                time: None,
+                reminder_minutes: Vec::new(),
+            },
+            pull_only,
+        }
+    }
+
+    #[test]
+    fn change_on_one_side_wins() {
+        assert!(remote_wins(&item(), &local(-10), &remote(5, false), true));
+        assert!(!remote_wins(&item(), &local(5), &remote(-10, false), false));
+        assert!(!remote_wins(&item(), &local(-10), &remote(-10, false), false));
+    }
+
+    #[test]
+    fn change_on_both_sides_keeps_the_later_one() {
+        assert!(remote_wins(&item(), &local(5), &remote(10, false), true));
+        assert!(!remote_wins(&item(), &local(10), &remote(5, false), true));
+    }
+
+    #[test]
+    fn tie_on_updated_at_keeps_the_server_copy() {
+        assert!(remote_wins(&item(), &local(5), &remote(5, false), true));
+    }
+
+    #[test]
+    fn server_copy_without_last_modified_counts_as_synced() {
+        let mut remote = remote(0, false);
+        remote.last_modified = None;
+        assert!(!remote_wins(&item(), &local(5), &remote, true));
+        assert!(remote_wins(&item(), &local(-10), &remote, true));
+    }
+
+    #[test]
+    fn pull_only_server_copy_replaces_a_local_edit() {
+        assert!(remote_wins(&item(), &local(10), &remote(5, true), false));
+        assert!(!remote_wins(&item(), &local(-10), &remote(5, true), false));
+    }
+}
@@ -0,0 +1,36 @@
+//! The optional second tray item, whose title shows the Ethiopian time.
+//!
+//! It sits next to the date, so the two need not share one title. It is added and
+//! removed as `show_clock_in_tray` is turned on and off, and the scheduler updates it at
+//! the start of every minute. Clicking it opens or closes the calendar like the date does.
+
+use crate::{clock, display, today_timezone, AppSettings};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::Manager;
+
+const TRAY_ID: &str = "clock";
+
+/// Adds, updates or removes the clock tray item to match `settings`.
+pub fn refresh(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
+    if !settings.show_clock_in_tray {
+        app.remove_tray_by_id(TRAY_ID);
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().with_timezone(&today_timezone());
+    let text = display::clock_text(settings, &clock::ethiopian_time(now, &settings.clock));
+    match app.tray_by_id(TRAY_ID) {
+        Some(tray) => tray.set_title(Some(&text)).map_err(|e| format!("Failed to set clock text: {}", e)),
+        None => TrayIconBuilder::with_id(TRAY_ID)
+            .title(&text)
+            .tooltip("ZemenBar - Ethiopian Time")
+            .on_tray_icon_event(|tray, event| {
+                if let TrayIconEvent::Click { button: MouseButton::Left, button_state: MouseButtonState::Up, .. } = event {
+                    crate::shortcuts::toggle_panel(tray.app_handle());
+                }
+            })
+            .build(app)
+            .map(|_| ())
+            .map_err(|e| format!("Failed to add clock to the tray: {}", e)),
+    }
+}
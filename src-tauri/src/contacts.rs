@@ -0,0 +1,88 @@
+//! Birthdays read from macOS Contacts, for importing into the birthdays store.
+//!
+//! Reading needs the user's permission, asked for the first time
+//! `sync_contacts_birthdays` runs. Only contacts with a full birth date are returned,
+//! since an Ethiopian birthday cannot be placed without the Gregorian year.
+
+use crate::birthdays::{ContactBirthday, ContactBirthdays};
+use block2::RcBlock;
+use chrono::NaiveDate;
+use objc2::rc::Retained;
+use objc2::runtime::{Bool, ProtocolObject};
+use objc2::AllocAnyThread;
+use objc2_contacts::{
+    CNAuthorizationStatus, CNContact, CNContactBirthdayKey, CNContactFamilyNameKey, CNContactFetchRequest,
+    CNContactGivenNameKey, CNContactIdentifierKey, CNContactStore, CNEntityType, CNKeyDescriptor,
+};
+use objc2_foundation::{NSArray, NSDateComponentUndefined, NSError};
+use std::ptr::NonNull;
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long to wait for the user to answer the permission prompt.
+const ACCESS_PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Asks for access to the user's contacts, prompting the first time.
+pub fn request_access() -> Result<bool, String> {
+    let status = unsafe { CNContactStore::authorizationStatusForEntityType(CNEntityType::Contacts) };
+    if status == CNAuthorizationStatus::Authorized {
+        return Ok(true);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let completion = RcBlock::new(move |granted: Bool, _error: *mut NSError| {
+        let _ = sender.send(granted.as_bool());
+    });
+    let store = unsafe { CNContactStore::new() };
+    unsafe { store.requestAccessForEntityType_completionHandler(CNEntityType::Contacts, &completion) };
+
+    receiver
+        .recv_timeout(ACCESS_PROMPT_TIMEOUT)
+        .map_err(|e| format!("Failed to get contacts access: {}", e))
+}
+
+/// Every contact's birthday.
+pub fn read_birthdays() -> Result<ContactBirthdays, String> {
+    let keys: Retained<NSArray<ProtocolObject<dyn CNKeyDescriptor>>> = unsafe {
+        NSArray::from_slice(&[
+            ProtocolObject::from_ref(CNContactIdentifierKey),
+            ProtocolObject::from_ref(CNContactGivenNameKey),
+            ProtocolObject::from_ref(CNContactFamilyNameKey),
+            ProtocolObject::from_ref(CNContactBirthdayKey),
+        ])
+    };
+    let request = unsafe { CNContactFetchRequest::initWithKeysToFetch(CNContactFetchRequest::alloc(), &keys) };
+    let store = unsafe { CNContactStore::new() };
+
+    let found = std::cell::RefCell::new(ContactBirthdays::default());
+    let visit = RcBlock::new(|contact: NonNull<CNContact>, _stop: NonNull<Bool>| {
+        let contact = unsafe { contact.as_ref() };
+        let Some(components) = (unsafe { contact.birthday() }) else {
+            return;
+        };
+        let (year, month, day) = (components.year(), components.month(), components.day());
+        if year == NSDateComponentUndefined {
+            found.borrow_mut().without_year += 1;
+            return;
+        }
+        let Some(birthday) = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32) else {
+            return;
+        };
+        let name = format!("{} {}", unsafe { contact.givenName() }, unsafe { contact.familyName() })
+            .trim()
+            .to_string();
+        if name.is_empty() {
+            return;
+        }
+        found.borrow_mut().birthdays.push(ContactBirthday {
+            contact_id: unsafe { contact.identifier() }.to_string(),
+            name,
+            birthday,
+        });
+    });
+    unsafe { store.enumerateContactsWithFetchRequest_error_usingBlock(&request, &visit) }
+        .map_err(|e| format!("Failed to read contacts: {}", e))?;
+    drop(visit);
+
+    Ok(found.into_inner())
+}
@@ -0,0 +1,127 @@
+//! `zemenbar://` links, so other apps and scripts can drive ZemenBar.
+//!
+//! - `zemenbar://show` opens the calendar panel;
+//! - `zemenbar://date/2017-04-14` opens it on the month of an Ethiopian date;
+//! - `zemenbar://convert?g=2025-12-23` converts a Gregorian date to Ethiopian, and
+//!   `zemenbar://convert?ec=2017-04-14` the other way.
+//!
+//! A link cannot answer its caller directly, so a conversion is copied to the
+//! clipboard and shown in a notification. Callers that pass an `x-success` URL, in
+//! the x-callback-url style of Shortcuts and similar tools, have that URL opened with
+//! the converted `YYYY-MM-DD` date added as `result` instead. Only web and known
+//! automation app URLs are opened, so a link cannot make ZemenBar open local files
+//! or launch arbitrary apps.
+
+use crate::logging::LogError;
+use crate::EthiopianDate;
+use chrono::NaiveDate;
+use tauri::{Emitter, Manager, Url};
+
+pub const SCHEME: &str = "zemenbar";
+
+/// Schemes an `x-success` URL may use: the web, and automation apps that take x-callback-url replies.
+const CALLBACK_SCHEMES: [&str; 6] = ["http", "https", "shortcuts", "raycast", "alfred", "drafts"];
+
+fn parse_callback(callback: &str) -> Result<Url, String> {
+    let url = Url::parse(callback).map_err(|e| format!("Invalid x-success URL {}: {}", callback, e))?;
+    if !CALLBACK_SCHEMES.contains(&url.scheme()) {
+        return Err(format!("x-success URLs with the {}: scheme are not allowed", url.scheme()));
+    }
+    Ok(url)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeepLink {
+    Show,
+    Date(EthiopianDate),
+    FromGregorian { date: NaiveDate, callback: Option<Url> },
+    ToGregorian { date: EthiopianDate, callback: Option<Url> },
+}
+
+impl DeepLink {
+    pub fn parse(url: &Url) -> Result<Self, String> {
+        if url.scheme() != SCHEME {
+            return Err(format!("Not a {} link: {}", SCHEME, url));
+        }
+        let argument = url.path().trim_matches('/');
+        let query = |key: &str| url.query_pairs().find(|(name, _)| name == key).map(|(_, value)| value.into_owned());
+        let callback = query("x-success").map(|callback| parse_callback(&callback)).transpose()?;
+
+        match url.host_str().unwrap_or_default() {
+            "show" | "" => Ok(Self::Show),
+            "date" => Ok(Self::Date(argument.parse()?)),
+            "convert" => match (query("g"), query("ec")) {
+                (Some(gregorian), None) => Ok(Self::FromGregorian {
+                    date: NaiveDate::parse_from_str(&gregorian, "%Y-%m-%d")
+                        .map_err(|e| format!("Invalid date {}: {}", gregorian, e))?,
+                    callback,
+                }),
+                (None, Some(ethiopian)) => Ok(Self::ToGregorian {
+                    date: ethiopian.parse()?,
+                    callback,
+                }),
+                _ => Err("A convert link needs either g=YYYY-MM-DD or ec=YYYY-MM-DD".to_string()),
+            },
+            other => Err(format!("Unknown link: {}://{}", SCHEME, other)),
+        }
+    }
+}
+
+/// Positions, shows and focuses the calendar panel.
+pub(crate) fn show_calendar(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        crate::position_calendar_window(app.clone(), None).log_error("Failed to position calendar window");
+        window.show().log_error("Failed to show calendar window");
+        window.set_focus().log_error("Failed to focus calendar window");
+    }
+}
+
+/// Hands a conversion result back to the caller: through `callback` when given, otherwise
+/// on the clipboard with a notification.
+fn deliver(app: &tauri::AppHandle, result: String, text: String, callback: Option<Url>) -> Result<(), String> {
+    if let Some(mut callback) = callback {
+        use tauri_plugin_opener::OpenerExt;
+
+        callback.query_pairs_mut().append_pair("result", &result);
+        return app
+            .opener()
+            .open_url(callback.as_str(), None::<&str>)
+            .map_err(|e| format!("Failed to open x-success URL: {}", e));
+    }
+
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+    use tauri_plugin_notification::NotificationExt;
+
+    app.clipboard()
+        .write_text(result.clone())
+        .map_err(|e| format!("Failed to copy to clipboard: {}", e))?;
+    app.notification()
+        .builder()
+        .title(&result)
+        .body(&text)
+        .show()
+        .map_err(|e| format!("Failed to show notification: {}", e))
+}
+
+pub fn handle(app: &tauri::AppHandle, url: &Url) -> Result<(), String> {
+    match DeepLink::parse(url)? {
+        DeepLink::Show => show_calendar(app),
+        DeepLink::Date(date) => {
+            show_calendar(app);
+            app.emit("show-date", date).log_error("Failed to emit show-date");
+        }
+        DeepLink::FromGregorian { date, callback } => {
+            let converted = EthiopianDate::from_naive_date(date).ok_or_else(|| format!("Failed to convert {}", date))?;
+            let text = converted.format("%A, %B %e, %Y %C / %EA፣ %EB %e %Y %EC");
+            deliver(app, converted.format("%Y-%m-%d"), text, callback)?;
+        }
+        DeepLink::ToGregorian { date, callback } => {
+            let converted = date
+                .to_gregorian()
+                .ok_or_else(|| format!("Failed to convert {} to Gregorian", date))?;
+            let text = format!("{} = {}", date.format("%B %e, %Y %C"), converted.format("%A, %B %-d, %Y"));
+            deliver(app, converted.format("%Y-%m-%d").to_string(), text, callback)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,82 @@
+//! Dates as the tray and the copy menu show them, following the display settings.
+
+use crate::clock::{ClockPeriod, EthiopianTime};
+use crate::format::FormatOptions;
+use crate::locale::Language;
+use crate::{paydays, strings, AppSettings, EthiopianDate, TrayMode};
+
+/// Shown after the tray date on paydays.
+const PAYDAY_BADGE: &str = "💰";
+
+/// Era, language and numeric date format for the tokens that depend on them, from the settings.
+pub fn format_options(settings: &AppSettings) -> FormatOptions {
+    FormatOptions {
+        era: settings.era,
+        language: settings.language,
+        numeric: settings.numeric_format,
+    }
+}
+
+/// Template for the date as shown in the tray and copied from the calendar, built from the display settings.
+pub fn display_template(settings: &AppSettings) -> String {
+    if settings.use_numeric_format {
+        return settings.numeric_format.template(settings.use_geez_numbers);
+    }
+
+    let mut parts = vec!["%LB", if settings.use_geez_numbers { "%Od" } else { "%e" }];
+    if settings.language == Language::Amharic && settings.show_qen {
+        parts.push("%q");
+    }
+    parts.push(if settings.use_geez_numbers { "%OY" } else { "%Y" });
+    if settings.language.uses_ethiopic_script() && settings.show_amete_mihret {
+        parts.push("%EC");
+    }
+    parts.join(" ")
+}
+
+/// Tray title for `today`: the date, or the days left in countdown mode, with the
+/// payday badge when one falls today.
+pub fn tray_text(settings: &AppSettings, today: &EthiopianDate) -> String {
+    let days_left = match (settings.tray_mode, &settings.countdown_target) {
+        (TrayMode::Countdown, Some(target)) => today.days_between(target).filter(|days| *days >= 0),
+        _ => None,
+    };
+
+    let text = match days_left {
+        Some(days) => days_left_text(days as usize, settings),
+        None => today.format_with(&display_template(settings), format_options(settings)),
+    };
+    if settings.show_payday_badge && !paydays::paydays_on(today).is_empty() {
+        format!("{} {}", text, PAYDAY_BADGE)
+    } else {
+        text
+    }
+}
+
+/// "23 ቀን ቀርቷል", "1 day left" or, for 0, "Today", in the display language and numerals.
+pub fn days_left_text(days: usize, settings: &AppSettings) -> String {
+    if days == 0 {
+        return strings::label(settings.language, "countdown_today");
+    }
+    let key = if days == 1 { "countdown_day_left" } else { "countdown_days_left" };
+    let days = if settings.use_geez_numbers { EthiopianDate::to_geez_number(days) } else { days.to_string() };
+    strings::label(settings.language, key).replace("{days}", &days)
+}
+
+/// Title of the clock tray item, e.g. "3:25 ቀን", or "፫:፳፭ ቀን" with Ge'ez numerals.
+pub fn clock_text(settings: &AppSettings, time: &EthiopianTime) -> String {
+    let key = match time.period {
+        ClockPeriod::Day => "clock_day",
+        ClockPeriod::Night => "clock_night",
+    };
+    let period = strings::label(settings.language, key);
+    if !settings.use_geez_numbers {
+        return format!("{} {}", time, period);
+    }
+    // Ge'ez numerals have no zero, so on the hour only the hour is shown
+    let hour = EthiopianDate::to_geez_number(time.hour as usize);
+    match time.minute {
+        0 => format!("{} {}", hour, period),
+        minute => format!("{}:{} {}", hour, EthiopianDate::to_geez_number(minute as usize), period),
+    }
+}
@@ -0,0 +1,46 @@
+//! Errors returned by the Tauri commands.
+//!
+//! The frontend receives them as `{ "code": "settings_io", "message": "Failed to write
+//! settings file: ..." }`, so it can pick a message or recovery by `code` and still show
+//! or log the text. The modules behind the commands keep returning `String` errors; a
+//! command wraps them in the variant that says what failed.
+
+use serde::Serialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, thiserror::Error)]
+#[serde(tag = "code", content = "message", rename_all = "snake_case")]
+pub enum ZemenbarError {
+    /// `settings.json` could not be read, parsed or written.
+    #[error("{0}")]
+    SettingsIo(String),
+    /// A date that does not exist, or could not be converted to the other calendar.
+    #[error("{0}")]
+    Conversion(String),
+    /// Input the command cannot act on, such as a start date after the end date.
+    #[error("{0}")]
+    InvalidInput(String),
+    /// macOS Contacts or Calendar access was not granted.
+    #[error("{0}")]
+    PermissionDenied(String),
+    /// The calendar panel, a window or the tray could not be updated.
+    #[error("{0}")]
+    Panel(String),
+    /// Anything else, e.g. a store file, an export or a sync that failed.
+    #[error("{0}")]
+    Other(String),
+}
+
+impl From<String> for ZemenbarError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+/// Lets code that returns `String` errors call a command with `?`.
+impl From<ZemenbarError> for String {
+    fn from(error: ZemenbarError) -> Self {
+        error.to_string()
+    }
+}
+
+pub type CommandResult<T> = Result<T, ZemenbarError>;
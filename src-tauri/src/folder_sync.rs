@@ -0,0 +1,534 @@
+//! Sync of the settings and events through a shared folder, such as one in iCloud Drive.
+//!
+//! With `sync_directory` set, the app keeps `settings.json` and `events.json` in a
+//! `ZemenBar` folder inside it, and every Mac pointed at the same folder reads and
+//! writes them. Changes there are picked up by a file watcher; changes here are written
+//! on save. Nothing merges the files apart from this module, so it works with any
+//! folder that some other tool keeps in step.
+//!
+//! The last writer wins: the settings as a whole, by when they were written, and each
+//! event by its `updated_at`. An event deleted on one Mac stays in the shared file as a
+//! deletion for a while, so the others delete it too instead of bringing it back, unless
+//! they edited it after it was deleted. `folder_sync.json` in the app data directory
+//! records which events were there at the last sync, which tells a deletion here apart
+//! from an event that is new in the folder.
+
+use crate::events::{self, Event};
+use crate::logging::LogError;
+use crate::{settings_migration, AppSettings};
+use chrono::{DateTime, Duration, Utc};
+use notify::Watcher;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Mutex;
+use tauri::{Emitter, Listener};
+
+const FOLDER_NAME: &str = "ZemenBar";
+const SETTINGS_FILE: &str = "settings.json";
+const EVENTS_FILE: &str = "events.json";
+
+/// Settings that belong to one Mac and are neither written to nor taken from the folder.
+const LOCAL_SETTINGS: [&str; 2] = ["sync_directory", "launch_at_login"];
+
+/// How long a deletion is kept in the shared file for Macs that have not synced since.
+const DELETION_DAYS: i64 = 90;
+
+/// Wait after a change before syncing, so that a burst of file events syncs once.
+const DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SharedSettings {
+    updated_at: DateTime<Utc>,
+    /// `AppSettings` without the `LOCAL_SETTINGS`.
+    settings: Value,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct SharedEvents {
+    #[serde(default)]
+    events: Vec<Event>,
+    #[serde(default)]
+    deleted: Vec<Deletion>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Deletion {
+    id: String,
+    deleted_at: DateTime<Utc>,
+}
+
+/// What the last sync knew.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SyncState {
+    /// Folder the state belongs to; it starts over when the folder changes.
+    directory: String,
+    /// Events there were after the last sync.
+    event_ids: Vec<String>,
+    /// `updated_at` of the shared settings last written or taken.
+    settings_updated_at: Option<DateTime<Utc>>,
+}
+
+/// What one sync changed here.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FolderSyncResult {
+    /// The settings were replaced by the folder's.
+    pub settings_pulled: bool,
+    /// Events added or updated from the folder.
+    pub events_pulled: usize,
+    pub events_deleted: usize,
+}
+
+/// Held while a sync or a settings write runs.
+static SYNC_LOCK: Mutex<()> = Mutex::new(());
+
+/// The folder watcher, and the channel the sync thread waits on; dropped to stop watching.
+static WATCHER: Mutex<Option<(notify::RecommendedWatcher, Sender<()>)>> = Mutex::new(None);
+
+fn shared_dir(directory: &str) -> PathBuf {
+    Path::new(directory).join(FOLDER_NAME)
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path, what: &str) -> Result<Option<T>, String> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read {}: {}", what, e))?;
+    serde_json::from_str(&content).map(Some).map_err(|e| format!("Failed to parse {}: {}", what, e))
+}
+
+fn write_json(path: &Path, value: &impl Serialize, what: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create {} directory: {}", what, e))?;
+    }
+    let content = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize {}: {}", what, e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write {}: {}", what, e))
+}
+
+fn read_state(app: &tauri::AppHandle, directory: &str) -> Result<SyncState, String> {
+    let state: Option<SyncState> = read_json(&crate::get_folder_sync_state_path(app)?, "folder sync state")?;
+    Ok(state.filter(|state| state.directory == directory).unwrap_or_else(|| SyncState {
+        directory: directory.to_string(),
+        ..SyncState::default()
+    }))
+}
+
+fn write_state(app: &tauri::AppHandle, state: &SyncState) -> Result<(), String> {
+    write_json(&crate::get_folder_sync_state_path(app)?, state, "folder sync state")
+}
+
+/// `settings` as written to the folder.
+fn shared_settings_value(settings: &AppSettings) -> Result<Value, String> {
+    let mut value = serde_json::to_value(settings).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let Some(fields) = value.as_object_mut() {
+        for field in LOCAL_SETTINGS {
+            fields.remove(field);
+        }
+    }
+    Ok(value)
+}
+
+/// The folder's settings with this Mac's `LOCAL_SETTINGS` put back.
+fn adopt_settings(current: &AppSettings, shared: Value) -> Result<AppSettings, String> {
+    let mut value = shared;
+    let local = serde_json::to_value(current).map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    if let (Some(fields), Some(local)) = (value.as_object_mut(), local.as_object()) {
+        for field in LOCAL_SETTINGS {
+            if let Some(local_value) = local.get(field) {
+                fields.insert(field.to_string(), local_value.clone());
+            }
+        }
+    }
+    settings_migration::migrate(&mut value)?;
+    serde_json::from_value(value).map_err(|e| format!("Failed to parse shared settings: {}", e))
+}
+
+/// Writes settings saved here to the folder, unless it already has the same ones.
+///
+/// Nothing is written before the first sync with the folder, which decides whether the
+/// settings here or the folder's are kept.
+pub fn push_settings(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
+    let Some(directory) = settings.sync_directory.as_deref() else {
+        return Ok(());
+    };
+    let _guard = SYNC_LOCK.lock().map_err(|e| format!("Failed to lock folder sync: {}", e))?;
+    let mut state = read_state(app, directory)?;
+    if state.settings_updated_at.is_none() {
+        return Ok(());
+    }
+
+    let path = shared_dir(directory).join(SETTINGS_FILE);
+    let value = shared_settings_value(settings)?;
+    let shared: Option<SharedSettings> = read_json(&path, "shared settings")?;
+    if shared.is_some_and(|shared| shared.settings == value) {
+        return Ok(());
+    }
+    let updated_at = Utc::now();
+    write_json(&path, &SharedSettings { updated_at, settings: value }, "shared settings")?;
+    state.settings_updated_at = Some(updated_at);
+    write_state(app, &state)
+}
+
+/// Merges the events here with the folder's, following the rules in the module docs,
+/// and writes the result to both.
+fn merge_events(
+    events_path: &Path,
+    shared_path: &Path,
+    state: &mut SyncState,
+    result: &mut FolderSyncResult,
+) -> Result<(), String> {
+    let shared: SharedEvents = read_json(shared_path, "shared events")?.unwrap_or_default();
+    let local = events::all_events();
+    let now = Utc::now();
+
+    let local_ids: HashSet<&str> = local.iter().map(|event| event.id.as_str()).collect();
+    let mut deleted: HashMap<String, DateTime<Utc>> =
+        shared.deleted.iter().map(|deletion| (deletion.id.clone(), deletion.deleted_at)).collect();
+    for id in state.event_ids.iter().filter(|id| !local_ids.contains(id.as_str())) {
+        deleted.entry(id.clone()).or_insert(now);
+    }
+
+    // The latest version of each event; on a tie the one here is kept.
+    let mut merged: HashMap<String, Event> = HashMap::new();
+    for event in shared.events.iter().chain(local.iter()) {
+        let newer = merged.get(&event.id).is_none_or(|existing| event.updated_at >= existing.updated_at);
+        if newer {
+            merged.insert(event.id.clone(), event.clone());
+        }
+    }
+    // An edit made after the deletion brings the event back.
+    merged.retain(|id, event| {
+        deleted.get(id).is_none_or(|deleted_at| event.updated_at.is_some_and(|updated| updated > *deleted_at))
+    });
+    deleted.retain(|id, deleted_at| !merged.contains_key(id) && now - *deleted_at < Duration::days(DELETION_DAYS));
+
+    let mut merged: Vec<Event> = merged.into_values().collect();
+    merged.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.id.cmp(&b.id)));
+    let mut deleted: Vec<Deletion> = deleted.into_iter().map(|(id, deleted_at)| Deletion { id, deleted_at }).collect();
+    deleted.sort_by(|a, b| a.id.cmp(&b.id));
+
+    let local_by_id: HashMap<&str, &Event> = local.iter().map(|event| (event.id.as_str(), event)).collect();
+    result.events_pulled = merged.iter().filter(|event| local_by_id.get(event.id.as_str()) != Some(event)).count();
+    let merged_ids: HashSet<&str> = merged.iter().map(|event| event.id.as_str()).collect();
+    result.events_deleted = local_ids.difference(&merged_ids).count();
+    if result.events_pulled > 0 || result.events_deleted > 0 {
+        events::modify_events(events_path, |events| {
+            *events = merged.clone();
+            Ok(())
+        })?;
+    }
+
+    state.event_ids = merged.iter().map(|event| event.id.clone()).collect();
+    let updated = SharedEvents { events: merged, deleted };
+    if updated != shared {
+        write_json(shared_path, &updated, "shared events")?;
+    }
+    Ok(())
+}
+
+/// Syncs the settings and events with the folder in the settings, if there is one,
+/// and tells the windows when events changed.
+pub fn sync(app: &tauri::AppHandle) -> Result<FolderSyncResult, String> {
+    let current = crate::load_settings(app.clone())?;
+    let Some(directory) = current.sync_directory.clone() else {
+        return Ok(FolderSyncResult::default());
+    };
+    let mut result = FolderSyncResult::default();
+
+    let pulled_settings = {
+        let _guard = SYNC_LOCK.lock().map_err(|e| format!("Failed to lock folder sync: {}", e))?;
+        let folder = shared_dir(&directory);
+        let mut state = read_state(app, &directory)?;
+
+        let settings_path = folder.join(SETTINGS_FILE);
+        let pulled_settings = match read_json::<SharedSettings>(&settings_path, "shared settings")? {
+            Some(shared) if state.settings_updated_at.is_none_or(|seen| seen < shared.updated_at) => {
+                state.settings_updated_at = Some(shared.updated_at);
+                let local = shared_settings_value(&current)?;
+                if shared.settings == local {
+                    None
+                } else {
+                    Some(adopt_settings(&current, shared.settings)?)
+                }
+            }
+            Some(_) => None,
+            None => {
+                let updated_at = Utc::now();
+                let shared = SharedSettings { updated_at, settings: shared_settings_value(&current)? };
+                write_json(&settings_path, &shared, "shared settings")?;
+                state.settings_updated_at = Some(updated_at);
+                None
+            }
+        };
+
+        merge_events(&crate::get_events_path(app)?, &folder.join(EVENTS_FILE), &mut state, &mut result)?;
+        write_state(app, &state)?;
+        pulled_settings
+    };
+
+    // Saved once the lock is released, since saving writes the settings to the folder.
+    if let Some(settings) = pulled_settings {
+        crate::save_settings(app.clone(), settings)?;
+        result.settings_pulled = true;
+    }
+    if result.events_pulled > 0 || result.events_deleted > 0 {
+        app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    }
+    Ok(result)
+}
+
+/// Asks the sync thread for a sync, if a folder is being watched.
+fn request_sync() {
+    if let Ok(watcher) = WATCHER.lock() {
+        if let Some((_, requests)) = watcher.as_ref() {
+            let _ = requests.send(());
+        }
+    }
+}
+
+/// Watches `directory` for changes written by other Macs, in place of the folder watched
+/// before, and syncs with it right away; `None` stops watching.
+pub fn watch(app: &tauri::AppHandle, directory: Option<&str>) -> Result<(), String> {
+    let mut current = WATCHER.lock().map_err(|e| format!("Failed to lock folder watcher: {}", e))?;
+    *current = None;
+    let Some(directory) = directory else {
+        return Ok(());
+    };
+    let folder = shared_dir(directory);
+    std::fs::create_dir_all(&folder).map_err(|e| format!("Failed to create sync folder: {}", e))?;
+
+    let (requests, received) = mpsc::channel::<()>();
+    let file_requests = requests.clone();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let ours = event.is_ok_and(|event| {
+            let names = [SETTINGS_FILE, EVENTS_FILE];
+            event.paths.iter().any(|path| path.file_name().is_some_and(|name| names.iter().any(|file| name == *file)))
+        });
+        if ours {
+            let _ = file_requests.send(());
+        }
+    })
+    .map_err(|e| format!("Failed to watch sync folder: {}", e))?;
+    watcher
+        .watch(&folder, notify::RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch sync folder: {}", e))?;
+
+    // Ends once the watcher and its senders are dropped.
+    let app = app.clone();
+    std::thread::spawn(move || {
+        while received.recv().is_ok() {
+            std::thread::sleep(DEBOUNCE);
+            while received.try_recv().is_ok() {}
+            if let Err(e) = sync(&app) {
+                tracing::warn!("Folder sync failed: {}", e);
+            }
+        }
+    });
+
+    let _ = requests.send(());
+    *current = Some((watcher, requests));
+    Ok(())
+}
+
+/// Starts watching the folder in the settings, and syncs again whenever events change here.
+pub fn start(app: &tauri::AppHandle) {
+    app.listen("events-changed", |_| request_sync());
+    let directory = crate::load_settings(app.clone()).ok().and_then(|settings| settings.sync_directory);
+    if let Err(e) = watch(app, directory.as_deref()) {
+        tracing::warn!("{}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EthiopianDate;
+
+    /// Held by tests that go through the in-memory events store.
+    static STORE_LOCK: Mutex<()> = Mutex::new(());
+
+    fn event(id: &str, title: &str, updated_at: DateTime<Utc>) -> Event {
+        Event {
+            id: id.to_string(),
+            date: EthiopianDate::new(2017, 1, 1),
+            title: title.to_string(),
+            notes: String::new(),
+            color: None,
+            recurrence: None,
+            time: None,
+            reminder_minutes: Vec::new(),
+            updated_at: Some(updated_at),
+        }
+    }
+
+    fn deletion(id: &str, deleted_at: DateTime<Utc>) -> Deletion {
+        Deletion { id: id.to_string(), deleted_at }
+    }
+
+    fn ids(events: &[Event]) -> Vec<&str> {
+        events.iter().map(|event| event.id.as_str()).collect()
+    }
+
+    /// Merges `local`, as the events here, with `shared`, as the folder's, after a last
+    /// sync that saw `synced_ids`. Returns the events here, the folder's, and the result.
+    fn merge(
+        name: &str,
+        local: Vec<Event>,
+        shared: SharedEvents,
+        synced_ids: &[&str],
+    ) -> (Vec<Event>, SharedEvents, FolderSyncResult) {
+        let _guard = STORE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = std::env::temp_dir().join(format!("zemenbar-folder-sync-{}-{}", std::process::id(), name));
+        let events_path = dir.join("events.json");
+        let shared_path = dir.join(FOLDER_NAME).join(EVENTS_FILE);
+        events::modify_events(&events_path, |events| {
+            *events = local;
+            Ok(())
+        })
+        .unwrap();
+        write_json(&shared_path, &shared, "shared events").unwrap();
+
+        let mut state = SyncState {
+            directory: dir.display().to_string(),
+            event_ids: synced_ids.iter().map(|id| id.to_string()).collect(),
+            settings_updated_at: None,
+        };
+        let mut result = FolderSyncResult::default();
+        merge_events(&events_path, &shared_path, &mut state, &mut result).unwrap();
+
+        let merged: SharedEvents = read_json(&shared_path, "shared events").unwrap().unwrap();
+        let here = events::all_events();
+        assert_eq!(state.event_ids, ids(&here));
+        std::fs::remove_dir_all(&dir).unwrap();
+        (here, merged, result)
+    }
+
+    #[test]
+    fn deletion_here_is_written_to_the_folder() {
+        let before = Utc::now() - Duration::days(1);
+        let shared = SharedEvents {
+            events: vec![event("a", "A", before), event("b", "B", before)],
+            deleted: Vec::new(),
+        };
+        let (here, merged, result) = merge("deleted-here", vec![event("b", "B", before)], shared, &["a", "b"]);
+
+        assert_eq!(ids(&here), ["b"]);
+        assert_eq!(ids(&merged.events), ["b"]);
+        assert_eq!(merged.deleted.iter().map(|deletion| deletion.id.as_str()).collect::<Vec<_>>(), ["a"]);
+        assert_eq!((result.events_pulled, result.events_deleted), (0, 0));
+    }
+
+    #[test]
+    fn deletion_elsewhere_is_applied_here() {
+        let edited = Utc::now() - Duration::days(2);
+        let deleted_at = Utc::now() - Duration::days(1);
+        let shared = SharedEvents {
+            events: vec![event("b", "B", edited)],
+            deleted: vec![deletion("a", deleted_at)],
+        };
+        let local = vec![event("a", "A", edited), event("b", "B", edited)];
+        let (here, merged, result) = merge("deleted-elsewhere", local, shared, &["a", "b"]);
+
+        assert_eq!(ids(&here), ["b"]);
+        assert_eq!(merged.deleted, [deletion("a", deleted_at)]);
+        assert_eq!((result.events_pulled, result.events_deleted), (0, 1));
+    }
+
+    #[test]
+    fn edit_after_deletion_brings_the_event_back() {
+        let deleted_at = Utc::now() - Duration::days(1);
+        let edited = Utc::now();
+        let shared = SharedEvents {
+            events: Vec::new(),
+            deleted: vec![deletion("a", deleted_at)],
+        };
+        let (here, merged, result) = merge("edited-after-deletion", vec![event("a", "A", edited)], shared, &["a"]);
+
+        assert_eq!(ids(&here), ["a"]);
+        assert_eq!(ids(&merged.events), ["a"]);
+        assert!(merged.deleted.is_empty());
+        assert_eq!((result.events_pulled, result.events_deleted), (0, 0));
+    }
+
+    #[test]
+    fn edit_before_deletion_does_not_bring_the_event_back() {
+        let edited = Utc::now() - Duration::days(2);
+        let shared = SharedEvents {
+            events: vec![event("a", "Edited", edited)],
+            deleted: vec![deletion("a", Utc::now() - Duration::days(1))],
+        };
+        let (here, merged, _) = merge("edited-before-deletion", Vec::new(), shared, &[]);
+
+        assert!(here.is_empty());
+        assert!(merged.events.is_empty());
+    }
+
+    #[test]
+    fn newer_edit_in_the_folder_is_pulled() {
+        let before = Utc::now() - Duration::days(1);
+        let shared = SharedEvents {
+            events: vec![event("a", "There", Utc::now())],
+            deleted: Vec::new(),
+        };
+        let (here, merged, result) = merge("newer-there", vec![event("a", "Here", before)], shared, &["a"]);
+
+        assert_eq!(here[0].title, "There");
+        assert_eq!(merged.events[0].title, "There");
+        assert_eq!((result.events_pulled, result.events_deleted), (1, 0));
+    }
+
+    #[test]
+    fn tie_on_updated_at_keeps_the_event_here() {
+        let updated_at = Utc::now() - Duration::days(1);
+        let shared = SharedEvents {
+            events: vec![event("a", "There", updated_at)],
+            deleted: Vec::new(),
+        };
+        let (here, merged, result) = merge("tie", vec![event("a", "Here", updated_at)], shared, &["a"]);
+
+        assert_eq!(here[0].title, "Here");
+        assert_eq!(merged.events[0].title, "Here");
+        assert_eq!(result.events_pulled, 0);
+    }
+
+    #[test]
+    fn deletions_expire_after_deletion_days() {
+        let shared = SharedEvents {
+            events: Vec::new(),
+            deleted: vec![
+                deletion("old", Utc::now() - Duration::days(DELETION_DAYS + 1)),
+                deletion("recent", Utc::now() - Duration::days(DELETION_DAYS - 1)),
+            ],
+        };
+        let (_, merged, _) = merge("expiry", Vec::new(), shared, &[]);
+
+        assert_eq!(merged.deleted.iter().map(|deletion| deletion.id.as_str()).collect::<Vec<_>>(), ["recent"]);
+    }
+
+    #[test]
+    fn adopt_settings_keeps_local_settings() {
+        let current = AppSettings {
+            sync_directory: Some("/Users/a/iCloud".to_string()),
+            launch_at_login: true,
+            ..AppSettings::default()
+        };
+        let elsewhere = AppSettings {
+            sync_directory: Some("/Users/b/iCloud".to_string()),
+            launch_at_login: false,
+            show_desktop_widget: !current.show_desktop_widget,
+            ..AppSettings::default()
+        };
+        let shared = shared_settings_value(&elsewhere).unwrap();
+        for field in LOCAL_SETTINGS {
+            assert!(shared.get(field).is_none(), "{}", field);
+        }
+
+        let adopted = adopt_settings(&current, shared).unwrap();
+        assert_eq!(adopted.sync_directory, current.sync_directory);
+        assert!(adopted.launch_at_login);
+        assert_eq!(adopted.show_desktop_widget, elsewhere.show_desktop_widget);
+    }
+}
@@ -0,0 +1,647 @@
+//! Google Calendar connector: pushes events and holidays to a dedicated "Ethiopian Calendar"
+//! Google calendar, and pulls the user's own Google events for display on Ethiopian dates.
+//!
+//! Signing in uses OAuth 2.0 for installed apps: the browser opens Google's consent
+//! page, which redirects back to a one-off listener on 127.0.0.1, and the code is
+//! exchanged with PKCE. The OAuth client comes from `ZEMENBAR_GOOGLE_CLIENT_ID` and
+//! `ZEMENBAR_GOOGLE_CLIENT_SECRET` at build time; builds without them cannot connect.
+//! Tokens live in the keychain, and the access token is refreshed shortly before it
+//! expires.
+//!
+//! Each stored event, and each holiday of the current and next Ethiopian year, becomes
+//! a Google event whose id is derived from its own, so pushing again updates instead of
+//! duplicating. `google_calendar_sync.json` in the app data directory remembers what was
+//! pushed: unchanged entries are skipped and entries that no longer exist are deleted.
+
+use crate::logging::LogError;
+use crate::system_calendar::{self, SystemEvent};
+use crate::{events, holidays, ics, EthiopianDate};
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const API_URL: &str = "https://www.googleapis.com/calendar/v3";
+
+/// Create and manage calendars made by ZemenBar, and read the user's events.
+const SCOPES: &str =
+    "https://www.googleapis.com/auth/calendar.app.created https://www.googleapis.com/auth/calendar.events.readonly";
+
+const CALENDAR_NAME: &str = "Ethiopian Calendar";
+
+const KEYCHAIN_SERVICE: &str = "ZemenBar Google Calendar";
+const KEYCHAIN_ACCOUNT: &str = "oauth";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long to wait for the user to finish signing in in the browser.
+const SIGN_IN_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Access tokens are refreshed this long before they expire.
+const REFRESH_MARGIN_SECS: i64 = 60;
+
+/// How often the background thread pushes events and holidays.
+const PUSH_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Google keeps at most five reminders per event, each at most four weeks ahead.
+const MAX_REMINDERS: usize = 5;
+const MAX_REMINDER_MINUTES: u32 = 4 * 7 * 24 * 60;
+
+/// What the connector does once signed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GoogleCalendarSettings {
+    /// Copy stored events to the "Ethiopian Calendar" Google calendar.
+    pub push_events: bool,
+    /// Copy the shown holiday categories to the same calendar.
+    pub push_holidays: bool,
+    /// Show events from the primary Google calendar on the month grid.
+    pub pull_events: bool,
+}
+
+impl Default for GoogleCalendarSettings {
+    fn default() -> Self {
+        Self {
+            push_events: true,
+            push_holidays: true,
+            pull_events: true,
+        }
+    }
+}
+
+/// What one push changed.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GooglePushResult {
+    pub written: usize,
+    pub deleted: usize,
+    pub unchanged: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Tokens {
+    access_token: String,
+    refresh_token: String,
+    expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct PushState {
+    /// The "Ethiopian Calendar" calendar, once created.
+    calendar_id: Option<String>,
+    /// Google event id to a hash of the body last pushed.
+    pushed: HashMap<String, String>,
+}
+
+/// Held while a push runs, so the background thread and `sync_google_calendar` never overlap.
+static PUSH_LOCK: Mutex<()> = Mutex::new(());
+
+fn client_credentials() -> Result<(&'static str, &'static str), String> {
+    match (option_env!("ZEMENBAR_GOOGLE_CLIENT_ID"), option_env!("ZEMENBAR_GOOGLE_CLIENT_SECRET")) {
+        (Some(id), Some(secret)) if !id.is_empty() => Ok((id, secret)),
+        _ => Err("This build of ZemenBar has no Google Calendar client configured".to_string()),
+    }
+}
+
+fn agent() -> ureq::Agent {
+    ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build()
+}
+
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        let escaped = (bytes[index] == b'%')
+            .then(|| text.get(index + 1..index + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[index]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
+            (None, b'+') => decoded.push(b' '),
+            (None, byte) => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// A random URL-safe string from `bytes` bytes of system randomness.
+fn random_token(bytes: usize) -> Result<String, String> {
+    let mut buffer = vec![0u8; bytes];
+    getrandom::getrandom(&mut buffer).map_err(|e| format!("Failed to generate sign-in code: {}", e))?;
+    Ok(base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(buffer))
+}
+
+fn keychain_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT).map_err(|e| format!("Failed to open keychain entry: {}", e))
+}
+
+fn read_tokens() -> Result<Tokens, String> {
+    let content = keychain_entry()?
+        .get_password()
+        .map_err(|e| format!("Failed to read Google tokens from the keychain: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse Google tokens: {}", e))
+}
+
+fn write_tokens(tokens: &Tokens) -> Result<(), String> {
+    let content = serde_json::to_string(tokens).map_err(|e| format!("Failed to serialize Google tokens: {}", e))?;
+    keychain_entry()?
+        .set_password(&content)
+        .map_err(|e| format!("Failed to save Google tokens to the keychain: {}", e))
+}
+
+/// Signs out by forgetting the tokens.
+pub fn delete_tokens() -> Result<(), String> {
+    match keychain_entry()?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove Google tokens from the keychain: {}", e)),
+    }
+}
+
+/// Tokens from a token endpoint response; `previous_refresh` is kept when Google sends no new one.
+fn tokens_from_response(response: Value, previous_refresh: Option<String>) -> Result<Tokens, String> {
+    let access_token = response["access_token"]
+        .as_str()
+        .ok_or_else(|| "Google sent no access token".to_string())?
+        .to_string();
+    let refresh_token = response["refresh_token"]
+        .as_str()
+        .map(str::to_string)
+        .or(previous_refresh)
+        .ok_or_else(|| "Google sent no refresh token".to_string())?;
+    let expires_in = response["expires_in"].as_i64().unwrap_or(3600);
+    Ok(Tokens {
+        access_token,
+        refresh_token,
+        expires_at: Utc::now() + chrono::Duration::seconds(expires_in),
+    })
+}
+
+fn post_token_request(form: &[(&str, &str)]) -> Result<Value, String> {
+    agent()
+        .post(TOKEN_URL)
+        .send_form(form)
+        .map_err(|e| format!("Failed to get Google token: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Failed to read Google token response: {}", e))
+}
+
+/// A valid access token, refreshing it first when it is about to expire.
+fn access_token() -> Result<String, String> {
+    let tokens = read_tokens()?;
+    if tokens.expires_at - chrono::Duration::seconds(REFRESH_MARGIN_SECS) > Utc::now() {
+        return Ok(tokens.access_token);
+    }
+
+    let (client_id, client_secret) = client_credentials()?;
+    let response = post_token_request(&[
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+        ("refresh_token", &tokens.refresh_token),
+        ("grant_type", "refresh_token"),
+    ])?;
+    let refreshed = tokens_from_response(response, Some(tokens.refresh_token))?;
+    write_tokens(&refreshed)?;
+    Ok(refreshed.access_token)
+}
+
+/// The `code` from the browser's redirect to the loopback listener.
+fn wait_for_code(listener: &TcpListener, state: &str) -> Result<String, String> {
+    listener
+        .set_nonblocking(true)
+        .map_err(|e| format!("Failed to prepare Google sign-in: {}", e))?;
+    let deadline = Instant::now() + SIGN_IN_TIMEOUT;
+
+    while Instant::now() < deadline {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => {
+                std::thread::sleep(Duration::from_millis(200));
+                continue;
+            }
+            Err(e) => return Err(format!("Failed to receive Google sign-in: {}", e)),
+        };
+        stream.set_nonblocking(false).log_error("Failed to make the Google sign-in connection blocking");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .log_error("Failed to set the Google sign-in read timeout");
+        let mut buffer = [0u8; 8192];
+        let read = stream.read(&mut buffer).unwrap_or(0);
+        let request = String::from_utf8_lossy(&buffer[..read]);
+
+        // "GET /?code=...&state=... HTTP/1.1"
+        let target = request.split_whitespace().nth(1).unwrap_or_default();
+        let Some(query) = target.strip_prefix("/?") else {
+            stream
+                .write_all(b"HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .log_error("Failed to answer a stray request during Google sign-in");
+            continue;
+        };
+        let params: HashMap<String, String> = query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(key, value)| (key.to_string(), percent_decode(value)))
+            .collect();
+
+        let outcome = match (params.get("code"), params.get("error")) {
+            _ if params.get("state").map(String::as_str) != Some(state) => {
+                Err("Google sign-in answered a different request".to_string())
+            }
+            (Some(code), _) => Ok(code.clone()),
+            (None, error) => Err(format!("Google sign-in was cancelled: {}", error.map_or("no code", String::as_str))),
+        };
+        let message = if outcome.is_ok() {
+            "Signed in to Google Calendar. You can close this tab and return to ZemenBar."
+        } else {
+            "Google Calendar was not connected. You can close this tab."
+        };
+        let body = format!("<!doctype html><meta charset=\"utf-8\"><p>{}</p>", message);
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).log_error("Failed to answer the Google sign-in redirect");
+        return outcome;
+    }
+    Err("Timed out waiting for Google sign-in".to_string())
+}
+
+/// Runs the browser sign-in and stores the tokens. `open_url` shows Google's consent page.
+pub fn sign_in(open_url: impl FnOnce(&str) -> Result<(), String>) -> Result<(), String> {
+    let (client_id, client_secret) = client_credentials()?;
+    let listener = TcpListener::bind("127.0.0.1:0").map_err(|e| format!("Failed to prepare Google sign-in: {}", e))?;
+    let port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to prepare Google sign-in: {}", e))?
+        .port();
+    let redirect_uri = format!("http://127.0.0.1:{}", port);
+
+    let verifier = random_token(48)?;
+    let challenge = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+    let state = random_token(16)?;
+    let url = format!(
+        "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&code_challenge={}&code_challenge_method=S256&state={}&access_type=offline&prompt=consent",
+        AUTH_URL,
+        percent_encode(client_id),
+        percent_encode(&redirect_uri),
+        percent_encode(SCOPES),
+        challenge,
+        state
+    );
+    open_url(&url)?;
+
+    let code = wait_for_code(&listener, &state)?;
+    let response = post_token_request(&[
+        ("client_id", client_id),
+        ("client_secret", client_secret),
+        ("code", &code),
+        ("code_verifier", &verifier),
+        ("grant_type", "authorization_code"),
+        ("redirect_uri", &redirect_uri),
+    ])?;
+    write_tokens(&tokens_from_response(response, None)?)
+}
+
+struct Api {
+    agent: ureq::Agent,
+    authorization: String,
+}
+
+impl Api {
+    fn connect() -> Result<Self, String> {
+        Ok(Self {
+            agent: agent(),
+            authorization: format!("Bearer {}", access_token()?),
+        })
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        self.agent
+            .request(method, &format!("{}{}", API_URL, path))
+            .set("Authorization", &self.authorization)
+    }
+}
+
+fn read_state(path: &Path) -> Result<PushState, String> {
+    if !path.exists() {
+        return Ok(PushState::default());
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read Google Calendar sync state: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse Google Calendar sync state: {}", e))
+}
+
+fn write_state(path: &Path, state: &PushState) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create Google Calendar sync directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(state).map_err(|e| format!("Failed to serialize Google Calendar sync state: {}", e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write Google Calendar sync state: {}", e))
+}
+
+/// Forgets the pushed entries, e.g. after signing out.
+pub fn clear_state(path: &Path) -> Result<(), String> {
+    if path.exists() {
+        std::fs::remove_file(path).map_err(|e| format!("Failed to remove Google Calendar sync state: {}", e))?;
+    }
+    Ok(())
+}
+
+/// The "Ethiopian Calendar" calendar's id, creating the calendar when it does not exist (any more).
+fn ensure_calendar(api: &Api, state: &mut PushState) -> Result<String, String> {
+    if let Some(id) = &state.calendar_id {
+        match api.request("GET", &format!("/calendars/{}", percent_encode(id))).call() {
+            Ok(_) => return Ok(id.clone()),
+            Err(ureq::Error::Status(404, _)) => {}
+            Err(e) => return Err(format!("Failed to find the Google calendar: {}", e)),
+        }
+    }
+
+    let created: Value = api
+        .request("POST", "/calendars")
+        .send_json(json!({
+            "summary": CALENDAR_NAME,
+            "description": "Ethiopian holidays and events from ZemenBar",
+            "timeZone": crate::today_timezone().name(),
+        }))
+        .map_err(|e| format!("Failed to create the Google calendar: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Failed to read the Google calendar: {}", e))?;
+    let id = created["id"]
+        .as_str()
+        .ok_or_else(|| "Google sent no calendar id".to_string())?
+        .to_string();
+    state.calendar_id = Some(id.clone());
+    state.pushed.clear();
+    Ok(id)
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Google event ids take lowercase hex digits, so a hash of our own key makes a stable one.
+fn google_event_id(key: &str) -> String {
+    sha256_hex(key.as_bytes())
+}
+
+fn event_time(date: &EthiopianDate, time: Option<NaiveTime>) -> Option<Value> {
+    let day = date.to_gregorian()?;
+    let timezone = crate::today_timezone();
+    Some(match time {
+        Some(time) => json!({
+            "dateTime": timezone.from_local_datetime(&day.and_time(time)).earliest()?.to_rfc3339(),
+            "timeZone": timezone.name(),
+        }),
+        None => json!({ "date": day.format("%Y-%m-%d").to_string() }),
+    })
+}
+
+/// Start and end of an event; timed events take no time, like in the `.ics` export.
+fn event_span(date: &EthiopianDate, time: Option<NaiveTime>) -> Option<(Value, Value)> {
+    let start = event_time(date, time)?;
+    let end = match time {
+        Some(_) => start.clone(),
+        None => event_time(&date.add_days(1)?, None)?,
+    };
+    Some((start, end))
+}
+
+/// Google event bodies for the stored events and holidays, by Google event id.
+fn push_entries(settings: &GoogleCalendarSettings, now: DateTime<Utc>) -> Vec<(String, Value)> {
+    let mut entries = Vec::new();
+
+    if settings.push_events {
+        for event in events::all_events() {
+            let Some((start, end)) = event_span(&event.date, event.time) else {
+                continue;
+            };
+            let mut description = ics::ethiopian_date_text(&event.date);
+            if !event.notes.is_empty() {
+                description = format!("{}\n\n{}", description, event.notes);
+            }
+            let reminders: Vec<Value> = event
+                .reminder_minutes
+                .iter()
+                .filter(|minutes| **minutes <= MAX_REMINDER_MINUTES)
+                .take(MAX_REMINDERS)
+                .map(|minutes| json!({ "method": "popup", "minutes": minutes }))
+                .collect();
+            let id = google_event_id(&format!("event-{}", event.id));
+            entries.push((
+                id.clone(),
+                json!({
+                    "id": id,
+                    "status": "confirmed",
+                    "summary": event.title,
+                    "description": description,
+                    "start": start,
+                    "end": end,
+                    "recurrence": ics::recurrence_lines(&event, now),
+                    "reminders": { "useDefault": reminders.is_empty(), "overrides": reminders },
+                }),
+            ));
+        }
+    }
+
+    if settings.push_holidays {
+        let year = EthiopianDate::today().year;
+        for holiday in (year..=year + 1).flat_map(holidays::holidays_for_year).filter(|holiday| holiday.is_shown()) {
+            let Some((start, end)) = event_span(&holiday.date, None) else {
+                continue;
+            };
+            let mut description = ics::ethiopian_date_text(&holiday.date);
+            if holiday.is_approximate {
+                description.push_str("\nDepends on the moon sighting and may fall a day earlier or later.");
+            }
+            let id = google_event_id(&format!("holiday-{}-{}", holiday.id, holiday.date));
+            entries.push((
+                id.clone(),
+                json!({
+                    "id": id,
+                    "status": "confirmed",
+                    "summary": format!("{} ({})", holiday.name_amharic, holiday.name_english),
+                    "description": description,
+                    "start": start,
+                    "end": end,
+                    "transparency": "transparent",
+                    "reminders": { "useDefault": false, "overrides": [] },
+                }),
+            ));
+        }
+    }
+    entries
+}
+
+/// Writes changed events and holidays to the "Ethiopian Calendar" Google calendar and
+/// deletes the ones that are gone.
+pub fn push(settings: &GoogleCalendarSettings, state_path: &Path) -> Result<GooglePushResult, String> {
+    let _guard = PUSH_LOCK.lock().map_err(|e| format!("Failed to lock Google Calendar sync: {}", e))?;
+    let api = Api::connect()?;
+    let mut state = read_state(state_path)?;
+    let calendar = percent_encode(&ensure_calendar(&api, &mut state)?);
+    let entries = push_entries(settings, Utc::now());
+    let mut result = GooglePushResult::default();
+
+    let mut outcome = Ok(());
+    for (id, body) in &entries {
+        let hash = sha256_hex(body.to_string().as_bytes());
+        if state.pushed.get(id) == Some(&hash) {
+            result.unchanged += 1;
+            continue;
+        }
+        // Updating also revives an event deleted earlier, whose id Google keeps reserved.
+        let written = match api.request("PUT", &format!("/calendars/{}/events/{}", calendar, id)).send_json(body) {
+            Err(ureq::Error::Status(404, _)) => api
+                .request("POST", &format!("/calendars/{}/events", calendar))
+                .send_json(body)
+                .map(|_| ()),
+            other => other.map(|_| ()),
+        };
+        if let Err(e) = written {
+            outcome = Err(format!("Failed to write Google Calendar event: {}", e));
+            break;
+        }
+        state.pushed.insert(id.clone(), hash);
+        result.written += 1;
+    }
+
+    if outcome.is_ok() {
+        let current: std::collections::HashSet<&String> = entries.iter().map(|(id, _)| id).collect();
+        let removed: Vec<String> = state.pushed.keys().filter(|id| !current.contains(id)).cloned().collect();
+        for id in removed {
+            match api.request("DELETE", &format!("/calendars/{}/events/{}", calendar, id)).call() {
+                Ok(_) | Err(ureq::Error::Status(404 | 410, _)) => {
+                    state.pushed.remove(&id);
+                    result.deleted += 1;
+                }
+                Err(e) => {
+                    outcome = Err(format!("Failed to delete Google Calendar event: {}", e));
+                    break;
+                }
+            }
+        }
+    }
+
+    // Saved even after a failure, so the entries already written are not written again.
+    write_state(state_path, &state)?;
+    outcome.map(|()| result)
+}
+
+/// A Google event's `start` or `end` in the "today" timezone; all-day dates start at midnight.
+fn parse_event_time(value: &Value) -> Option<DateTime<chrono_tz::Tz>> {
+    let timezone = crate::today_timezone();
+    if let Some(date_time) = value["dateTime"].as_str() {
+        return DateTime::parse_from_rfc3339(date_time).ok().map(|time| time.with_timezone(&timezone));
+    }
+    let date = NaiveDate::parse_from_str(value["date"].as_str()?, "%Y-%m-%d").ok()?;
+    timezone.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest()
+}
+
+/// Events from the primary Google calendar on the days of an Ethiopian month.
+pub fn events_for_month(year: usize, month: usize) -> Result<Vec<SystemEvent>, String> {
+    let first_day = EthiopianDate::new(year, month, 1);
+    let last_day = EthiopianDate::new(year, month, first_day.days_in_month());
+    let timezone = crate::today_timezone();
+    let midnight = |date: Option<NaiveDate>| {
+        date.and_then(|date| timezone.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest())
+    };
+    let (Some(range_start), Some(range_end)) =
+        (midnight(first_day.to_gregorian()), midnight(last_day.add_days(1).and_then(|date| date.to_gregorian())))
+    else {
+        return Err(format!("Invalid Ethiopian month: {}-{}", year, month));
+    };
+
+    let api = Api::connect()?;
+    let mut result = Vec::new();
+    let mut page_token: Option<String> = None;
+    loop {
+        let mut request = api
+            .request("GET", "/calendars/primary/events")
+            .query("timeMin", &range_start.to_rfc3339())
+            .query("timeMax", &range_end.to_rfc3339())
+            .query("singleEvents", "true")
+            .query("orderBy", "startTime")
+            .query("maxResults", "250");
+        if let Some(token) = &page_token {
+            request = request.query("pageToken", token);
+        }
+        let page: Value = request
+            .call()
+            .map_err(|e| format!("Failed to read Google Calendar events: {}", e))?
+            .into_json()
+            .map_err(|e| format!("Failed to read Google Calendar events: {}", e))?;
+
+        for item in page["items"].as_array().into_iter().flatten() {
+            if item["status"].as_str() == Some("cancelled") {
+                continue;
+            }
+            let (Some(start), Some(end)) = (parse_event_time(&item["start"]), parse_event_time(&item["end"])) else {
+                continue;
+            };
+            let all_day = item["start"]["date"].is_string();
+            let id = item["id"].as_str().unwrap_or_default().to_string();
+            let title = item["summary"].as_str().unwrap_or_default().to_string();
+            result.extend(
+                system_calendar::event_days(start, end, all_day)
+                    .into_iter()
+                    .filter(|(date, _)| date.year == year && date.month == month)
+                    .map(|(date, time)| SystemEvent {
+                        id: id.clone(),
+                        title: title.clone(),
+                        calendar: "Google".to_string(),
+                        date,
+                        time,
+                        all_day,
+                    }),
+            );
+        }
+
+        page_token = page["nextPageToken"].as_str().map(str::to_string);
+        if page_token.is_none() {
+            break;
+        }
+    }
+    result.sort_by(|a, b| (&a.date, a.time).cmp(&(&b.date, b.time)));
+    Ok(result)
+}
+
+/// Starts the background thread that pushes every `PUSH_INTERVAL` while pushing is on.
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || loop {
+        std::thread::sleep(PUSH_INTERVAL);
+
+        let Some(settings) = crate::load_settings(app.clone()).ok().and_then(|settings| settings.google_calendar) else {
+            continue;
+        };
+        if !settings.push_events && !settings.push_holidays {
+            continue;
+        }
+        if let Err(e) = crate::get_google_calendar_state_path(&app).and_then(|path| push(&settings, &path)) {
+            tracing::warn!("Google Calendar sync failed: {}", e);
+        }
+    });
+}
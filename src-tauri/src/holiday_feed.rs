@@ -0,0 +1,203 @@
+//! A subscribable `ethiopian-holidays.ics` feed of the holidays in the next two years.
+//!
+//! The feed can be written to a chosen path, such as a shared iCloud Drive or Dropbox
+//! folder, and is rewritten whenever the day changes so it always starts from today.
+//! It can also be served over HTTP on a chosen port, so phones on the same network can
+//! subscribe to `http://<this Mac>:<port>/ethiopian-holidays.ics`; each request gets
+//! a freshly built feed.
+
+use crate::ics;
+use crate::logging::LogError;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::net::{TcpListener, TcpStream, UdpSocket};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+pub const FEED_FILE_NAME: &str = "ethiopian-holidays.ics";
+
+/// How often the background thread checks whether the feed file needs rewriting.
+const TICK: Duration = Duration::from_secs(15 * 60);
+
+/// How long the server sleeps between checks for new connections.
+const ACCEPT_POLL: Duration = Duration::from_millis(250);
+
+/// Where the feed goes; at least one of `path` and `port` is set.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HolidayFeedSettings {
+    /// File to keep up to date; a directory gets `ethiopian-holidays.ics` inside it.
+    pub path: Option<String>,
+    /// Port to serve the feed on, on every network interface.
+    pub port: Option<u16>,
+    /// Leave out fasts and other observances that are not days off.
+    pub public_only: bool,
+}
+
+impl HolidayFeedSettings {
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.is_none() && self.port.is_none() {
+            return Err("Choose a file or a port for the holiday feed".to_string());
+        }
+        if let Some(path) = &self.path {
+            if !Path::new(path).is_absolute() {
+                return Err(format!("Holiday feed path must be absolute: {}", path));
+            }
+        }
+        if let Some(port) = self.port {
+            if port < 1024 {
+                return Err(format!("Holiday feed port must be 1024 or higher, got {}", port));
+            }
+        }
+        Ok(())
+    }
+
+    /// The file the feed is written to, if any.
+    pub fn file_path(&self) -> Option<PathBuf> {
+        let path = PathBuf::from(self.path.as_ref()?);
+        Some(if path.is_dir() { path.join(FEED_FILE_NAME) } else { path })
+    }
+}
+
+/// The running server's thread and the flag that stops it.
+static SERVER: Mutex<Option<(JoinHandle<()>, Arc<AtomicBool>)>> = Mutex::new(None);
+
+/// The file last written and the day it was written for.
+static LAST_WRITTEN: Mutex<Option<(PathBuf, NaiveDate)>> = Mutex::new(None);
+
+/// Writes the feed to `path`, through a temporary file so subscribers never read half a feed.
+pub fn write_feed(path: &Path, public_only: bool) -> Result<usize, String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create holiday feed directory: {}", e))?;
+    }
+    let (content, count) = ics::holiday_feed(public_only, Utc::now());
+    let temporary = path.with_extension("ics.tmp");
+    std::fs::write(&temporary, content).map_err(|e| format!("Failed to write holiday feed: {}", e))?;
+    std::fs::rename(&temporary, path).map_err(|e| format!("Failed to write holiday feed: {}", e))?;
+    Ok(count)
+}
+
+/// Rewrites the feed file when the day or the path changed since the last write.
+fn refresh_file(settings: &HolidayFeedSettings, force: bool) -> Result<(), String> {
+    let Some(path) = settings.file_path() else {
+        return Ok(());
+    };
+    let today = Utc::now().with_timezone(&crate::today_timezone()).date_naive();
+    let mut last = LAST_WRITTEN.lock().map_err(|e| format!("Failed to lock holiday feed: {}", e))?;
+    if !force && last.as_ref() == Some(&(path.clone(), today)) {
+        return Ok(());
+    }
+    write_feed(&path, settings.public_only)?;
+    *last = Some((path, today));
+    Ok(())
+}
+
+fn respond(mut stream: TcpStream, public_only: bool) {
+    stream.set_nonblocking(false).log_error("Failed to make the holiday feed connection blocking");
+    stream
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .log_error("Failed to set the holiday feed read timeout");
+    let mut buffer = [0u8; 4096];
+    let read = stream.read(&mut buffer).unwrap_or(0);
+    let request = String::from_utf8_lossy(&buffer[..read]);
+    let mut parts = request.split_whitespace();
+    let (method, target) = (parts.next().unwrap_or_default(), parts.next().unwrap_or_default());
+    let path = target.split('?').next().unwrap_or_default();
+
+    let response = if !matches!(method, "GET" | "HEAD") {
+        "HTTP/1.1 405 Method Not Allowed\r\nAllow: GET, HEAD\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    } else if path != "/" && path != format!("/{}", FEED_FILE_NAME) {
+        "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string()
+    } else {
+        let (content, _) = ics::holiday_feed(public_only, Utc::now());
+        let body = if method == "HEAD" { "" } else { content.as_str() };
+        format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: text/calendar; charset=utf-8\r\nContent-Disposition: inline; filename=\"{}\"\r\nContent-Length: {}\r\nCache-Control: max-age=3600\r\nConnection: close\r\n\r\n{}",
+            FEED_FILE_NAME,
+            content.len(),
+            body
+        )
+    };
+    stream.write_all(response.as_bytes()).log_error("Failed to answer a holiday feed request");
+}
+
+fn serve(listener: TcpListener, public_only: bool, stop: Arc<AtomicBool>) {
+    while !stop.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((stream, _)) => respond(stream, public_only),
+            Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => std::thread::sleep(ACCEPT_POLL),
+            Err(e) => {
+                tracing::warn!("Holiday feed server failed: {}", e);
+                std::thread::sleep(ACCEPT_POLL);
+            }
+        }
+    }
+}
+
+/// Stops the server and waits for its thread to close the listener, so the port can be bound again.
+fn stop_server(server: &mut Option<(JoinHandle<()>, Arc<AtomicBool>)>) {
+    if let Some((thread, stop)) = server.take() {
+        stop.store(true, Ordering::Relaxed);
+        if thread.join().is_err() {
+            tracing::warn!("Holiday feed server panicked");
+        }
+    }
+}
+
+/// Starts, moves or stops the server, and writes the file right away, to match `settings`.
+pub fn apply(settings: Option<&HolidayFeedSettings>) -> Result<(), String> {
+    let mut server = SERVER.lock().map_err(|e| format!("Failed to lock holiday feed: {}", e))?;
+    stop_server(&mut server);
+
+    let Some(settings) = settings else {
+        return Ok(());
+    };
+    if let Some(port) = settings.port {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| format!("Failed to serve holiday feed on port {}: {}", port, e))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|e| format!("Failed to serve holiday feed on port {}: {}", port, e))?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let public_only = settings.public_only;
+        let thread_stop = stop.clone();
+        let thread = std::thread::spawn(move || serve(listener, public_only, thread_stop));
+        *server = Some((thread, stop));
+    }
+    refresh_file(settings, true)
+}
+
+/// The address other devices on the network can subscribe to, when the feed is served.
+pub fn subscription_url(settings: &HolidayFeedSettings) -> Option<String> {
+    let port = settings.port?;
+    // Connecting a UDP socket sends nothing; it only picks the interface that faces the network.
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    let host = socket
+        .connect("192.0.2.1:80")
+        .and_then(|()| socket.local_addr())
+        .map(|address| address.ip().to_string())
+        .unwrap_or_else(|_| "localhost".to_string());
+    Some(format!("http://{}:{}/{}", host, port, FEED_FILE_NAME))
+}
+
+/// Starts serving the feed from the saved settings, and keeps the feed file current.
+pub fn start(app: tauri::AppHandle) {
+    let settings = crate::load_settings(app.clone()).unwrap_or_default();
+    if let Err(e) = apply(settings.holiday_feed.as_ref()) {
+        tracing::warn!("{}", e);
+    }
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(TICK);
+        let Some(feed) = crate::load_settings(app.clone()).ok().and_then(|settings| settings.holiday_feed) else {
+            continue;
+        };
+        if let Err(e) = refresh_file(&feed, false) {
+            tracing::warn!("{}", e);
+        }
+    });
+}
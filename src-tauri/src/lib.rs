@@ -1,509 +1,1622 @@
 //!
 //! This library provides Ethiopian calendar functionality for Zemenbar with system tray integration.
+//!
+//! The calendar itself lives in the `zemenbar-core` crate; it is re-exported here so
+//! the app's modules can keep using `crate::EthiopianDate`, `crate::holidays` and so on.
+
+pub mod appearance;
+pub mod backup;
+pub mod caldav;
+pub mod clock_tray;
+pub mod contacts;
+pub mod deep_link;
+pub mod display;
+pub mod error;
+pub mod folder_sync;
+pub mod google_calendar;
+pub mod holiday_feed;
+pub mod logging;
+pub mod login_item;
+pub mod monitors;
+pub mod month_image;
+pub mod pdf;
+pub mod reminders;
+pub mod scheduler;
+pub mod settings_migration;
+pub mod shortcuts;
+pub mod system_calendar;
+pub mod tray_icon;
+pub mod updates;
+pub mod widget;
+
+pub use zemenbar_core::{
+    academic, age, astronomy, bahire_hasab, birthdays, business_days, cache, clock, custom_holidays, date_input,
+    date_serde, events, export, format, hijri, holidays, ics, launcher, locale, months, packs, paydays, query, seasons,
+    spoken, strings, synaxarium,
+};
+pub use zemenbar_core::{
+    pagume_6_policy, set_pagume_6_policy, set_today_timezone, today_timezone, CalendarDateInput, CalendarDay,
+    CalendarMonth, CalendarWeek, DateRange, Era, EthiopianDate, EthiopianDateInput, GeezParseError, Pagume6Policy,
+    PagumeInfo, TodaySummary, AMETE_ALEM_OFFSET, DEFAULT_TIMEZONE,
+};
 
-use chrono::{Datelike, FixedOffset, Utc};
-use ethiopic_calendar::{EthiopianYear, GregorianYear};
+use chrono_tz::Tz;
+use error::{CommandResult, ZemenbarError};
+use locale::Language;
+use logging::LogError;
 use serde::{Deserialize, Serialize};
-use std::sync::Mutex;
+use std::sync::{Mutex, RwLock};
 use tauri::{
-    menu::{Menu, MenuItem},
+    menu::{IsMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Manager,
+    Emitter, Manager,
 };
 use tauri_nspanel::{
     tauri_panel, CollectionBehavior, PanelLevel, StyleMask, WebviewWindowExt,
 };
 
-/// Represents a date in the Ethiopian calendar system.
-///
-/// The Ethiopian calendar has 13 months: 12 months of 30 days each,
-/// plus Pagumē with 5 or 6 days depending on leap years.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct EthiopianDate {
-    pub year: usize,
-    pub month: usize,
-    pub day: usize,
-    /// Day formatted in Geez numerals
-    pub day_geez: String,
-}
-
-impl EthiopianDate {
-    /// Creates an `EthiopianDate` representing today's date in EAT (GMT+3).
-    pub fn today() -> Self {
-        let eat_offset = FixedOffset::east_opt(3 * 3600).unwrap();
-        let today = Utc::now().with_timezone(&eat_offset).date_naive();
-        let gregorian = GregorianYear::new(today.year() as usize, today.month() as usize, today.day() as usize);
-        let ethiopian: EthiopianYear = gregorian.into();
-
-        let day = ethiopian.day();
-        Self {
-            year: ethiopian.year(),
-            month: ethiopian.month(),
-            day,
-            day_geez: Self::to_geez_number(day),
-        }
-    }
+/// What the tray title shows.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayMode {
+    /// Today's date.
+    #[default]
+    Date,
+    /// Days left until `AppSettings.countdown_target`, e.g. "23 ቀን ቀርቷል".
+    Countdown,
+}
+
+/// How large the calendar panel and its content are drawn.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UiScale {
+    Compact,
+    #[default]
+    Normal,
+    /// A quarter larger, for low vision.
+    Large,
+}
+
+impl UiScale {
+    /// Zoom of the panel's content, and so of its logical size.
+    pub fn factor(self) -> f64 {
+        match self {
+            Self::Compact => 0.9,
+            Self::Normal => 1.0,
+            Self::Large => 1.25,
+        }
+    }
+}
+
+tauri_panel! {
+    panel!(CalendarPanel {
+        config: {
+            can_become_key_window: true,
+            is_floating_panel: true
+        }
+    })
+}
+
+/// Global state for remembering tray icon position to position calendar window correctly.
+static LAST_TRAY_X: Mutex<Option<f64>> = Mutex::new(None);
+
+/// Application settings that control calendar display and behavior
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Layout of the settings file, upgraded on load by `settings_migration`.
+    pub version: u32,
+    pub language: Language,
+    pub use_geez_numbers: bool,
+    pub show_date_in_tray: bool,
+    /// Icon shown in the tray instead of the date while `show_date_in_tray` is off.
+    pub tray_icon: tray_icon::TrayIconVariant,
+    /// Show the Ethiopian time in a second tray item.
+    pub show_clock_in_tray: bool,
+    /// Size of the calendar panel; the panel grows with its content, so nothing is clipped.
+    pub ui_scale: UiScale,
+    pub use_numeric_format: bool,
+    /// Order and separator of numeric dates in the tray, the copy formats and exports.
+    pub numeric_format: format::NumericFormat,
+    pub show_qen: bool,
+    pub show_amete_mihret: bool,
+    /// Show each day's Synaxarium commemorations with its holidays.
+    pub show_synaxarium: bool,
+    pub panel_appearance: appearance::PanelAppearance,
+    pub show_desktop_widget: bool,
+    /// Start ZemenBar when the user logs in.
+    pub launch_at_login: bool,
+    /// How the login item is registered; some managed Macs block one or the other.
+    pub login_item: login_item::LoginItemMechanism,
+    /// Look for a newer release once a day and offer it in the tray menu.
+    pub check_for_updates: bool,
+    pub copy_templates: Vec<format::CopyTemplate>,
+    /// Weekday the calendar grid starts on (0 = Sunday, 1 = Monday).
+    pub week_starts_on: usize,
+    /// Whether the Ethiopian clock counts from 6:00 or from sunrise, and where.
+    pub clock: clock::ClockSettings,
+    /// Era used for the year in the tray; `show_amete_mihret` then shows its suffix.
+    pub era: Era,
+    /// IANA timezone that decides "today", e.g. `"Europe/London"`; Addis Ababa when unset.
+    pub timezone: Option<String>,
+    /// Show a notification on the day of each event.
+    pub notify_events: bool,
+    /// Show a notification on birthdays and anniversaries.
+    pub notify_birthdays: bool,
+    /// Show a notification on holidays.
+    pub notify_holidays: bool,
+    /// Show a notification on paydays and bill dates, or as many days ahead as each asks.
+    pub notify_paydays: bool,
+    /// Mark the tray date with a badge on paydays.
+    pub show_payday_badge: bool,
+    /// When Pagumē 6 birthdays and yearly events are kept in years without a Pagumē 6.
+    pub pagume_6_policy: Pagume6Policy,
+    /// Limit holiday notifications to public holidays, skipping fasts and other observances.
+    pub notify_public_holidays_only: bool,
+    /// Holidays to announce ahead of time, whether or not `notify_holidays` is on.
+    pub holiday_countdowns: Vec<reminders::HolidayCountdown>,
+    /// Notifications due in this window wait until it ends; `None` turns quiet hours off.
+    pub quiet_hours: Option<reminders::QuietHours>,
+    pub tray_mode: TrayMode,
+    /// Date counted down to in `TrayMode::Countdown`; once it has passed, the tray shows the date again.
+    #[serde(with = "date_serde::compact::option")]
+    pub countdown_target: Option<EthiopianDate>,
+    /// Holiday categories shown in the grid, tray and notifications.
+    pub holiday_categories: holidays::HolidayCategories,
+    /// School, university and exam periods listed among the holidays.
+    pub academic_presets: academic::AcademicPresets,
+    /// Overlay events from the macOS Calendar app on the month grid.
+    pub show_system_calendar_events: bool,
+    /// CalDAV calendar the events are synced with; `None` when sync is off.
+    pub caldav: Option<caldav::CalDavAccount>,
+    /// What the Google Calendar connector does; `None` while not signed in.
+    pub google_calendar: Option<google_calendar::GoogleCalendarSettings>,
+    /// Where the subscribable holiday feed is written or served; `None` when it is off.
+    pub holiday_feed: Option<holiday_feed::HolidayFeedSettings>,
+    /// Folder, e.g. in iCloud Drive, the settings and events are shared through; `None` when sync is off.
+    pub sync_directory: Option<String>,
+    /// Global keyboard shortcuts for the panel and for copying the date.
+    pub shortcuts: shortcuts::ShortcutSettings,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            version: settings_migration::CURRENT_VERSION,
+            language: Language::Amharic,
+            use_geez_numbers: false,
+            show_date_in_tray: true,
+            tray_icon: tray_icon::TrayIconVariant::Auto,
+            show_clock_in_tray: false,
+            ui_scale: UiScale::Normal,
+            use_numeric_format: false,
+            numeric_format: format::NumericFormat::default(),
+            show_qen: false,
+            show_amete_mihret: false,
+            show_synaxarium: false,
+            panel_appearance: appearance::PanelAppearance::default(),
+            show_desktop_widget: false,
+            launch_at_login: false,
+            login_item: login_item::LoginItemMechanism::LaunchAgent,
+            check_for_updates: true,
+            copy_templates: format::default_copy_templates(),
+            week_starts_on: 0,
+            clock: clock::ClockSettings::default(),
+            era: Era::AmeteMihret,
+            timezone: None,
+            notify_events: true,
+            notify_birthdays: true,
+            notify_holidays: true,
+            notify_paydays: true,
+            show_payday_badge: true,
+            pagume_6_policy: Pagume6Policy::Pagume5,
+            notify_public_holidays_only: true,
+            holiday_countdowns: Vec::new(),
+            quiet_hours: None,
+            tray_mode: TrayMode::Date,
+            countdown_target: None,
+            holiday_categories: holidays::HolidayCategories::default(),
+            academic_presets: academic::AcademicPresets::default(),
+            show_system_calendar_events: false,
+            caldav: None,
+            google_calendar: None,
+            holiday_feed: None,
+            sync_directory: None,
+            shortcuts: shortcuts::ShortcutSettings::default(),
+        }
+    }
+}
+
+impl AppSettings {
+    /// Defaults adjusted to the system locale, for the first launch.
+    pub fn for_system_locale() -> Self {
+        let mut settings = Self::default();
+        if let Some(preferences) = locale::LocalePreferences::from_system() {
+            settings.language = preferences.language;
+            settings.use_geez_numbers = preferences.use_geez_numbers;
+            settings.use_numeric_format = preferences.use_numeric_format;
+        }
+        settings
+    }
+
+    /// The chosen timezone, or `None` for the Addis Ababa default.
+    pub fn parsed_timezone(&self) -> Result<Option<Tz>, String> {
+        self.timezone
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .map(|name| name.trim().parse::<Tz>().map_err(|_| format!("Unknown timezone: {}", name)))
+            .transpose()
+    }
+
+    /// Checks everything `save_settings` would reject, before anything is written.
+    pub fn validate(&self) -> Result<(), String> {
+        self.parsed_timezone()?;
+        shortcuts::validate(&self.shortcuts)?;
+        reminders::validate_countdowns(&self.holiday_countdowns)?;
+        if let Some(feed) = &self.holiday_feed {
+            feed.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[tauri::command]
+fn get_current_ethiopian_date() -> EthiopianDate {
+    EthiopianDate::today()
+}
+
+/// Rejects a year of 0 and, when given, a month outside Meskerem to Pagume.
+fn check_year_month(year: usize, month: Option<usize>) -> CommandResult<()> {
+    if year == 0 {
+        return Err(ZemenbarError::InvalidInput("Year must be positive".to_string()));
+    }
+    match month {
+        Some(month) if !(1..=13).contains(&month) => {
+            Err(ZemenbarError::InvalidInput(format!("Month must be between 1 and 13, got {}", month)))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tauri::command]
+fn get_ethiopian_calendar_month(app: tauri::AppHandle, year: usize, month: usize) -> CommandResult<CalendarMonth> {
+    check_year_month(year, Some(month))?;
+    let settings = load_settings(app).unwrap_or_default();
+    Ok(CalendarMonth::localized(year, month, settings.week_starts_on, settings.language))
+}
+
+/// Returns all 13 months of a year in one payload for the year-at-a-glance view.
+#[tauri::command]
+fn get_ethiopian_calendar_year(app: tauri::AppHandle, year: usize) -> CommandResult<Vec<CalendarMonth>> {
+    check_year_month(year, None)?;
+    let settings = load_settings(app).unwrap_or_default();
+    Ok((1..=13)
+        .map(|month| CalendarMonth::localized(year, month, settings.week_starts_on, settings.language))
+        .collect())
+}
+
+/// Returns the week around a date; `week_start` defaults to the `week_starts_on` setting.
+#[tauri::command]
+fn get_ethiopian_calendar_week(
+    app: tauri::AppHandle,
+    date: EthiopianDateInput,
+    week_start: Option<usize>,
+) -> CommandResult<CalendarWeek> {
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    let settings = load_settings(app).unwrap_or_default();
+    let week_start = week_start.unwrap_or(settings.week_starts_on);
+    Ok(CalendarWeek::new(&date, week_start, settings.language))
+}
+
+/// The current Ethiopian time of day, counted as `AppSettings.clock` says.
+#[tauri::command]
+fn get_ethiopian_time(app: tauri::AppHandle) -> clock::EthiopianTime {
+    let settings = load_settings(app).unwrap_or_default();
+    clock::ethiopian_time(chrono::Utc::now().with_timezone(&today_timezone()), &settings.clock)
+}
+
+#[tauri::command]
+fn get_today_summary() -> TodaySummary {
+    TodaySummary::for_date(EthiopianDate::today())
+}
+
+/// Tauri command reporting Pagumē's length for a year and when the next Pagumē 6 falls.
+#[tauri::command]
+fn get_pagume_info(year: usize) -> PagumeInfo {
+    PagumeInfo::for_year(year)
+}
+
+/// Tauri command returning every UI label and holiday name in one language.
+#[tauri::command]
+fn get_ui_strings(language: Language) -> strings::UiStrings {
+    strings::ui_strings(language)
+}
+
+/// Signed number of days from `from` to `to`, for "days since/until" calculations.
+#[tauri::command]
+fn date_diff(from: EthiopianDateInput, to: EthiopianDateInput) -> CommandResult<i64> {
+    let from = from.to_date().map_err(ZemenbarError::Conversion)?;
+    let to = to.to_date().map_err(ZemenbarError::Conversion)?;
+    from.days_between(&to)
+        .ok_or_else(|| ZemenbarError::Conversion("Failed to convert dates".to_string()))
+}
+
+/// Working days after `from` up to and including `to`, skipping weekends and shown public holidays.
+#[tauri::command]
+fn working_days_between(from: EthiopianDateInput, to: EthiopianDateInput) -> CommandResult<i64> {
+    let from = from.to_date().map_err(ZemenbarError::Conversion)?;
+    let to = to.to_date().map_err(ZemenbarError::Conversion)?;
+    Ok(business_days::working_days_between(&from, &to))
+}
+
+/// The date `days` working days after `date`, e.g. a deadline in 10 working days.
+#[tauri::command]
+fn add_working_days(date: EthiopianDateInput, days: i64) -> CommandResult<EthiopianDate> {
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    business_days::add_working_days(&date, days)
+        .ok_or_else(|| ZemenbarError::Conversion("Date is out of range".to_string()))
+}
+
+/// Age of someone born on `birth_date`, as of today, in Ethiopian and Gregorian years, months and days.
+#[tauri::command]
+fn calculate_age(birth_date: EthiopianDateInput) -> CommandResult<age::DateDuration> {
+    let birth_date = birth_date.to_date().map_err(ZemenbarError::Conversion)?;
+    age::calculate_age(&birth_date, &EthiopianDate::today())
+        .ok_or_else(|| ZemenbarError::InvalidInput("Birth date is in the future".to_string()))
+}
+
+/// Years, months and days between two dates, in both calendars.
+#[tauri::command]
+fn duration_between(from: EthiopianDateInput, to: EthiopianDateInput) -> CommandResult<age::DateDuration> {
+    let from = from.to_date().map_err(ZemenbarError::Conversion)?;
+    let to = to.to_date().map_err(ZemenbarError::Conversion)?;
+    age::duration_between(&from, &to).ok_or_else(|| ZemenbarError::Conversion("Failed to convert dates".to_string()))
+}
+
+/// Both dates in both calendars, the days, weeks and months between them and the holidays in between.
+#[tauri::command]
+fn compare_dates(a: CalendarDateInput, b: CalendarDateInput) -> CommandResult<age::DateComparison> {
+    let a = a.to_date().map_err(ZemenbarError::Conversion)?;
+    let b = b.to_date().map_err(ZemenbarError::Conversion)?;
+    age::compare_dates(&a, &b).ok_or_else(|| ZemenbarError::Conversion("Failed to convert dates".to_string()))
+}
+
+/// Tauri command to convert Gregorian date to Ethiopian calendar.
+#[tauri::command]
+fn convert_gregorian_to_ethiopian(year: i32, month: u32, day: u32) -> Option<EthiopianDate> {
+    EthiopianDate::from_gregorian(year, month, day)
+}
+
+/// Converts many Gregorian `(year, month, day)` dates in one call, e.g. for the year view;
+/// dates that do not exist come back as `null`.
+#[tauri::command]
+fn convert_gregorian_batch(dates: Vec<(i32, u32, u32)>) -> Vec<Option<EthiopianDate>> {
+    dates
+        .into_iter()
+        .map(|(year, month, day)| EthiopianDate::from_gregorian(year, month, day))
+        .collect()
+}
+
+/// Converts many Ethiopian `(year, month, day)` dates to Gregorian `(year, month, day)` in
+/// one call; dates that do not exist come back as `null`.
+#[tauri::command]
+fn convert_ethiopian_batch(dates: Vec<(usize, usize, usize)>) -> Vec<Option<(i32, u32, u32)>> {
+    use chrono::Datelike;
+
+    dates
+        .into_iter()
+        .map(|(year, month, day)| {
+            let date = EthiopianDateInput { year, month, day }.to_date().ok()?.to_gregorian()?;
+            Some((date.year(), date.month(), date.day()))
+        })
+        .collect()
+}
+
+/// Drops the memoized holiday and feast tables, for debugging; they are rebuilt on next use.
+#[tauri::command]
+fn clear_caches() {
+    cache::clear_caches();
+}
+
+/// Tauri command to list the movable feasts of an Ethiopian year with their Gregorian dates.
+#[tauri::command]
+fn get_movable_feasts(year: usize) -> Vec<bahire_hasab::MovableFeastDate> {
+    bahire_hasab::movable_feasts(year)
+}
+
+/// Tauri command backing the quick-jump field: resolves typed input to a month index.
+#[tauri::command]
+fn resolve_month(input: String) -> Option<usize> {
+    months::resolve_month(&input)
+}
+
+/// Validates a date field as it is typed; see `date_input` for the accepted forms.
+#[tauri::command]
+fn normalize_date_input(input: String) -> date_input::DateInputResult {
+    date_input::normalize_date_input(&input)
+}
+
+/// Completes a partially typed date with today's year and month.
+#[tauri::command]
+fn complete_partial_date(input: String) -> CommandResult<EthiopianDate> {
+    date_input::complete_partial_date(&input, &EthiopianDate::today()).map_err(ZemenbarError::Conversion)
+}
+
+/// Writes a CSV conversion table for `start_year..=end_year` and returns the number of rows.
+#[tauri::command]
+fn export_conversion_table(
+    app: tauri::AppHandle,
+    start_year: usize,
+    end_year: usize,
+    path: String,
+) -> CommandResult<usize> {
+    let settings = load_settings(app).unwrap_or_default();
+    let path = std::path::Path::new(&path);
+    Ok(export::write_conversion_table(start_year, end_year, path, settings.numeric_format)?)
+}
+
+/// Writes the days of a month, or of the whole year without `month`, with their holidays and
+/// events as CSV or JSON, and returns the number of days written.
+#[tauri::command]
+fn export_calendar(
+    app: tauri::AppHandle,
+    path: String,
+    year: usize,
+    month: Option<usize>,
+    format: export::ExportFormat,
+) -> CommandResult<usize> {
+    let settings = load_settings(app).unwrap_or_default();
+    let path = std::path::Path::new(&path);
+    Ok(export::write_calendar(path, year, month, format, settings.language, settings.numeric_format)?)
+}
+
+/// Writes a printable A4 PDF of a month, or of the whole year without `month`.
+#[tauri::command]
+async fn export_pdf(app: tauri::AppHandle, path: String, year: usize, month: Option<usize>) -> CommandResult<()> {
+    let settings = load_settings(app).unwrap_or_default();
+    Ok(pdf::write_pdf(std::path::Path::new(&path), year, month, settings.week_starts_on)?)
+}
+
+/// Draws a month grid to a PNG in the app's cache directory and returns its path, for sharing.
+#[tauri::command]
+async fn render_month_image(
+    app: tauri::AppHandle,
+    year: usize,
+    month: usize,
+    theme: month_image::ImageTheme,
+) -> CommandResult<String> {
+    let dir = app.path().app_cache_dir().map_err(|e| format!("Failed to get app cache directory: {}", e))?;
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Failed to create app cache directory: {}", e))?;
+    let path = dir.join(format!("ZemenBar {}-{:02}.png", year, month));
+    let settings = load_settings(app).unwrap_or_default();
+    month_image::render_month(&path, year, month, settings.week_starts_on, theme)?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+/// Writes this month's PDF to the temporary directory and opens it in the default viewer to print.
+fn print_calendar_from_menu(app: &tauri::AppHandle) {
+    use tauri_plugin_opener::OpenerExt;
+
+    let app = app.clone();
+    std::thread::spawn(move || {
+        let settings = load_settings(app.clone()).unwrap_or_default();
+        let today = EthiopianDate::today();
+        let path = std::env::temp_dir().join(format!("ZemenBar {}-{:02}.pdf", today.year, today.month));
+        let result = pdf::write_pdf(&path, today.year, Some(today.month), settings.week_starts_on).and_then(|()| {
+            app.opener()
+                .open_path(path.to_string_lossy(), None::<&str>)
+                .map_err(|e| format!("Failed to open PDF: {}", e))
+        });
+        if let Err(e) = result {
+            use tauri_plugin_notification::NotificationExt;
+
+            tracing::warn!("{}", e);
+            let title = strings::label(settings.language, "print_calendar");
+            let notification = app.notification().builder().title(title).body(e);
+            notification.show().log_error("Failed to show notification");
+        }
+    });
+}
+
+/// Writes an iCalendar file of a year's holidays and/or events and returns the number of events.
+#[tauri::command]
+fn export_ics(path: String, options: Option<ics::IcsExportOptions>) -> CommandResult<usize> {
+    Ok(ics::export_ics(std::path::Path::new(&path), &options.unwrap_or_default())?)
+}
+
+/// Turns the subscribable holiday feed on, changes it, or turns it off with `None`.
+///
+/// Returns the address to subscribe to from other devices when the feed is served.
+#[tauri::command]
+fn set_holiday_feed(
+    app: tauri::AppHandle,
+    feed: Option<holiday_feed::HolidayFeedSettings>,
+) -> CommandResult<Option<String>> {
+    if let Some(feed) = &feed {
+        feed.validate()?;
+    }
+    holiday_feed::apply(feed.as_ref())?;
+
+    let mut settings = load_settings(app.clone())?;
+    settings.holiday_feed = feed.clone();
+    save_settings(app, settings)?;
+    Ok(feed.as_ref().and_then(holiday_feed::subscription_url))
+}
+
+/// Adds the events in an iCalendar file to the events store.
+#[tauri::command]
+fn import_ics(app: tauri::AppHandle, path: String) -> CommandResult<ics::IcsImportResult> {
+    let result = ics::import_ics(std::path::Path::new(&path), &get_events_path(&app)?)?;
+    app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    Ok(result)
+}
+
+/// Width of the calendar panel, in logical pixels.
+const PANEL_WIDTH: f64 = 360.0;
+
+/// The `ui_scale` setting, read from the settings the app holds.
+fn ui_scale(app: &tauri::AppHandle) -> UiScale {
+    load_settings(app.clone()).map(|settings| settings.ui_scale).unwrap_or_default()
+}
+
+/// The monitor spanning physical x coordinate `x`, if one still does.
+fn monitor_at_x(window: &tauri::WebviewWindow, x: f64) -> Option<tauri::Monitor> {
+    window.available_monitors().ok()?.into_iter().find(|monitor| {
+        let left = monitor.position().x as f64;
+        (left..left + monitor.size().width as f64).contains(&x)
+    })
+}
+
+/// Positions the calendar window relative to the tray icon, kept within the monitor the
+/// tray was last clicked on, or the primary monitor when that one has been unplugged.
+#[tauri::command]
+fn position_calendar_window(app: tauri::AppHandle, tray_x: Option<f64>) -> CommandResult<()> {
+    if let Some(window) = app.get_webview_window("settings") {
+        if let Some(tray_x) = tray_x {
+            if let Ok(mut last_x) = LAST_TRAY_X.lock() {
+                *last_x = Some(tray_x);
+            }
+        }
+        let tray_x = tray_x.or_else(|| LAST_TRAY_X.lock().ok().and_then(|last_x| *last_x));
+        let monitor = tray_x
+            .and_then(|tray_x| monitor_at_x(&window, tray_x))
+            .or_else(|| window.primary_monitor().ok().flatten());
+
+        if let Some(monitor) = monitor {
+            let panel_width = PANEL_WIDTH * ui_scale(&app).factor();
+            let scale_factor = monitor.scale_factor();
+            let left = monitor.position().x as f64 / scale_factor;
+            let right = left + monitor.size().width as f64 / scale_factor;
+            let x = tray_x.map_or(right - 380.0, |tray_x| tray_x / scale_factor);
+            let x = x.min(right - panel_width).max(left);
+            let y = monitor.position().y as f64 / scale_factor + 28.0;
+            window
+                .set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }))
+                .map_err(|e| ZemenbarError::Panel(format!("Failed to position calendar window: {}", e)))?;
+        }
+    }
+    Ok(())
+}
+
+/// Sizes the calendar window for content `height` CSS pixels tall, zoomed and scaled
+/// up or down by the `ui_scale` setting.
+#[tauri::command]
+fn resize_calendar_window(app: tauri::AppHandle, height: f64) -> CommandResult<()> {
+    if let Some(window) = app.get_webview_window("settings") {
+        let factor = ui_scale(&app).factor();
+        window
+            .set_zoom(factor)
+            .map_err(|e| ZemenbarError::Panel(format!("Failed to scale calendar window: {}", e)))?;
+        let size = tauri::LogicalSize { width: PANEL_WIDTH * factor, height: height * factor };
+        window
+            .set_size(tauri::Size::Logical(size))
+            .map_err(|e| ZemenbarError::Panel(format!("Failed to resize calendar window: {}", e)))?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn set_tray_text(app: tauri::AppHandle, text: String) -> CommandResult<()> {
+    if let Some(tray) = app.tray_by_id("main") {
+        tray.set_title(Some(&text))
+            .map_err(|e| ZemenbarError::Panel(format!("Failed to set tray text: {}", e)))?;
+    }
+    Ok(())
+}
+
+/// Shows the tray icon instead of the date until the tray display is next refreshed.
+#[tauri::command]
+fn set_tray_icon(app: tauri::AppHandle) -> CommandResult<()> {
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    tray_icon::show(&app, settings.tray_icon).map_err(ZemenbarError::Panel)?;
+    if let Some(tray) = app.tray_by_id("main") {
+        tray.set_title(None::<&str>)
+            .map_err(|e| ZemenbarError::Panel(format!("Failed to set tray text: {}", e)))?;
+    }
+    Ok(())
+}
+
+fn get_packs_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("packs"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Lists installed data packs, including the ones rejected by the version, checksum or signature checks.
+#[tauri::command]
+fn list_packs() -> Vec<packs::PackInfo> {
+    packs::pack_status()
+}
+
+fn get_custom_holidays_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("custom_holidays.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Rereads `custom_holidays.json` and returns its entries.
+#[tauri::command]
+fn reload_custom_holidays(app: tauri::AppHandle) -> CommandResult<Vec<custom_holidays::CustomHoliday>> {
+    let holidays = custom_holidays::load_custom_holidays(&get_custom_holidays_path(&app)?)?;
+    app.emit("holidays-changed", &holidays).log_error("Failed to emit holidays-changed");
+    Ok(holidays)
+}
+
+/// Rescans the packs directory and returns the new pack status.
+#[tauri::command]
+fn reload_packs(app: tauri::AppHandle) -> CommandResult<Vec<packs::PackInfo>> {
+    let status = packs::load_packs(&get_packs_dir(&app)?);
+    app.emit("packs-changed", &status).log_error("Failed to emit packs-changed");
+    Ok(status)
+}
+
+fn get_events_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("events.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+#[tauri::command]
+fn get_events_for_month(year: usize, month: usize) -> Vec<events::Event> {
+    events::events_for_month(year, month)
+}
+
+#[tauri::command]
+fn add_event(app: tauri::AppHandle, event: events::EventInput) -> CommandResult<events::Event> {
+    let event = events::add_event(&get_events_path(&app)?, event)?;
+    app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    Ok(event)
+}
+
+#[tauri::command]
+fn update_event(app: tauri::AppHandle, id: String, event: events::EventInput) -> CommandResult<events::Event> {
+    let event = events::update_event(&get_events_path(&app)?, &id, event)?;
+    app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    Ok(event)
+}
+
+#[tauri::command]
+fn delete_event(app: tauri::AppHandle, id: String) -> CommandResult<()> {
+    events::delete_event(&get_events_path(&app)?, &id)?;
+    app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    Ok(())
+}
+
+fn get_paydays_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("paydays.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Tells the windows about a changed payday and redraws the tray, whose badge may change.
+fn paydays_changed(app: &tauri::AppHandle) {
+    app.emit("paydays-changed", ()).log_error("Failed to emit paydays-changed");
+    if let Err(e) = refresh_tray_display(app.clone()) {
+        tracing::warn!("Failed to refresh tray display: {}", e);
+    }
+}
+
+#[tauri::command]
+fn list_paydays() -> Vec<paydays::Payday> {
+    paydays::list_paydays()
+}
+
+#[tauri::command]
+fn add_payday(app: tauri::AppHandle, payday: paydays::PaydayInput) -> CommandResult<paydays::Payday> {
+    let payday = paydays::add_payday(&get_paydays_path(&app)?, payday)?;
+    paydays_changed(&app);
+    Ok(payday)
+}
+
+#[tauri::command]
+fn update_payday(app: tauri::AppHandle, id: String, payday: paydays::PaydayInput) -> CommandResult<paydays::Payday> {
+    let payday = paydays::update_payday(&get_paydays_path(&app)?, &id, payday)?;
+    paydays_changed(&app);
+    Ok(payday)
+}
+
+#[tauri::command]
+fn delete_payday(app: tauri::AppHandle, id: String) -> CommandResult<()> {
+    paydays::delete_payday(&get_paydays_path(&app)?, &id)?;
+    paydays_changed(&app);
+    Ok(())
+}
+
+fn get_birthdays_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("birthdays.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+#[tauri::command]
+fn list_birthdays() -> Vec<birthdays::Birthday> {
+    birthdays::list_birthdays()
+}
+
+#[tauri::command]
+fn add_birthday(app: tauri::AppHandle, birthday: birthdays::BirthdayInput) -> CommandResult<birthdays::Birthday> {
+    let birthday = birthdays::add_birthday(&get_birthdays_path(&app)?, birthday)?;
+    app.emit("birthdays-changed", ()).log_error("Failed to emit birthdays-changed");
+    Ok(birthday)
+}
+
+#[tauri::command]
+fn update_birthday(
+    app: tauri::AppHandle,
+    id: String,
+    birthday: birthdays::BirthdayInput,
+) -> CommandResult<birthdays::Birthday> {
+    let birthday = birthdays::update_birthday(&get_birthdays_path(&app)?, &id, birthday)?;
+    app.emit("birthdays-changed", ()).log_error("Failed to emit birthdays-changed");
+    Ok(birthday)
+}
+
+#[tauri::command]
+fn delete_birthday(app: tauri::AppHandle, id: String) -> CommandResult<()> {
+    birthdays::delete_birthday(&get_birthdays_path(&app)?, &id)?;
+    app.emit("birthdays-changed", ()).log_error("Failed to emit birthdays-changed");
+    Ok(())
+}
+
+/// Imports birthdays from macOS Contacts, asking for access the first time.
+#[tauri::command]
+async fn sync_contacts_birthdays(app: tauri::AppHandle) -> CommandResult<birthdays::ContactsSyncResult> {
+    if !contacts::request_access()? {
+        return Err(ZemenbarError::PermissionDenied("Contacts access was not granted".to_string()));
+    }
+    let found = contacts::read_birthdays()?;
+    let result = birthdays::sync_contact_birthdays(&get_birthdays_path(&app)?, found)?;
+    app.emit("birthdays-changed", ()).log_error("Failed to emit birthdays-changed");
+    Ok(result)
+}
+
+/// Runs the Contacts import from the tray menu and reports the outcome in a notification.
+fn sync_contacts_birthdays_from_menu(app: &tauri::AppHandle) {
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        use tauri_plugin_notification::NotificationExt;
+
+        let body = match sync_contacts_birthdays(app.clone()).await {
+            Ok(result) => format!(
+                "{} added, {} updated, {} without a birth year skipped",
+                result.added, result.updated, result.skipped_without_year
+            ),
+            Err(e) => e.to_string(),
+        };
+        let settings = load_settings(app.clone()).unwrap_or_default();
+        let title = strings::label(settings.language, "import_contacts_birthdays");
+        if let Err(e) = app.notification().builder().title(title).body(body).show() {
+            tracing::warn!("Failed to show notification: {}", e);
+        }
+    });
+}
+
+fn get_reminders_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("reminders.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+fn get_caldav_state_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("caldav_sync.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Connects a CalDAV calendar for two-way event sync and runs the first sync.
+///
+/// The password goes to the keychain; the account is only saved once the first sync succeeds.
+#[tauri::command]
+async fn set_caldav_account(
+    app: tauri::AppHandle,
+    account: caldav::CalDavAccount,
+    password: String,
+) -> CommandResult<caldav::CalDavSyncResult> {
+    account.validate()?;
+    let previous = load_settings(app.clone())?.caldav;
+    if previous.as_ref().is_some_and(|previous| previous.url != account.url) {
+        caldav::clear_state(&get_caldav_state_path(&app)?)?;
+    }
+    account.store_password(&password)?;
+    let result = caldav::sync(&app, &account)?;
+
+    let mut settings = load_settings(app.clone())?;
+    settings.caldav = Some(account.clone());
+    save_settings(app, settings)?;
+    if let Some(previous) = previous.filter(|previous| *previous != account) {
+        previous.delete_password()?;
+    }
+    Ok(result)
+}
+
+/// Stops CalDAV sync and forgets the password. Events already synced stay in the store.
+#[tauri::command]
+fn remove_caldav_account(app: tauri::AppHandle) -> CommandResult<()> {
+    let mut settings = load_settings(app.clone())?;
+    let Some(account) = settings.caldav.take() else {
+        return Ok(());
+    };
+    save_settings(app.clone(), settings)?;
+    account.delete_password()?;
+    Ok(caldav::clear_state(&get_caldav_state_path(&app)?)?)
+}
+
+/// Syncs the events with the CalDAV calendar now, without waiting for the interval.
+#[tauri::command]
+async fn sync_caldav(app: tauri::AppHandle) -> CommandResult<caldav::CalDavSyncResult> {
+    let account = load_settings(app.clone())?
+        .caldav
+        .ok_or_else(|| "No CalDAV account is set up".to_string())?;
+    Ok(caldav::sync(&app, &account)?)
+}
+
+fn get_google_calendar_state_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("google_calendar_sync.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// Signs in to Google in the browser and pushes events and holidays for the first time.
+#[tauri::command]
+async fn connect_google_calendar(app: tauri::AppHandle) -> CommandResult<google_calendar::GooglePushResult> {
+    use tauri_plugin_opener::OpenerExt;
+
+    google_calendar::sign_in(|url| {
+        app.opener()
+            .open_url(url, None::<&str>)
+            .map_err(|e| format!("Failed to open the browser: {}", e))
+    })?;
+    google_calendar::clear_state(&get_google_calendar_state_path(&app)?)?;
+
+    let options = google_calendar::GoogleCalendarSettings::default();
+    let mut settings = load_settings(app.clone())?;
+    settings.google_calendar = Some(options);
+    save_settings(app.clone(), settings)?;
+    Ok(google_calendar::push(&options, &get_google_calendar_state_path(&app)?)?)
+}
+
+/// Signs out of Google. The "Ethiopian Calendar" calendar stays in the Google account.
+#[tauri::command]
+fn disconnect_google_calendar(app: tauri::AppHandle) -> CommandResult<()> {
+    let mut settings = load_settings(app.clone())?;
+    settings.google_calendar = None;
+    save_settings(app.clone(), settings)?;
+    google_calendar::delete_tokens()?;
+    Ok(google_calendar::clear_state(&get_google_calendar_state_path(&app)?)?)
+}
+
+#[tauri::command]
+fn set_google_calendar_options(
+    app: tauri::AppHandle,
+    options: google_calendar::GoogleCalendarSettings,
+) -> CommandResult<()> {
+    let mut settings = load_settings(app.clone())?;
+    if settings.google_calendar.is_none() {
+        return Err(ZemenbarError::Other("Google Calendar is not connected".to_string()));
+    }
+    settings.google_calendar = Some(options);
+    save_settings(app, settings)
+}
+
+/// Pushes events and holidays to Google Calendar now, without waiting for the interval.
+#[tauri::command]
+async fn sync_google_calendar(app: tauri::AppHandle) -> CommandResult<google_calendar::GooglePushResult> {
+    let options = load_settings(app.clone())?
+        .google_calendar
+        .ok_or_else(|| "Google Calendar is not connected".to_string())?;
+    Ok(google_calendar::push(&options, &get_google_calendar_state_path(&app)?)?)
+}
+
+/// Events from the primary Google calendar on the days of an Ethiopian month; empty unless pulling is on.
+#[tauri::command]
+async fn get_google_events_for_month(
+    app: tauri::AppHandle,
+    year: usize,
+    month: usize,
+) -> CommandResult<Vec<system_calendar::SystemEvent>> {
+    let settings = load_settings(app).unwrap_or_default();
+    if !settings.google_calendar.is_some_and(|options| options.pull_events) {
+        return Ok(Vec::new());
+    }
+    Ok(google_calendar::events_for_month(year, month)?)
+}
+
+fn get_folder_sync_state_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("folder_sync.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+fn get_logs_dir(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("logs"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// The last `lines` lines of the diagnostics log, oldest first, to attach to a bug report.
+#[tauri::command]
+fn get_recent_logs(app: tauri::AppHandle, lines: Option<usize>) -> CommandResult<Vec<String>> {
+    Ok(logging::recent_lines(&get_logs_dir(&app)?, lines.unwrap_or(logging::DEFAULT_RECENT_LINES))?)
+}
+
+/// Opens today's log file in the default text editor.
+#[tauri::command]
+fn open_log_file(app: tauri::AppHandle) -> CommandResult<()> {
+    use tauri_plugin_opener::OpenerExt;
+
+    let path = logging::latest_log_file(&get_logs_dir(&app)?)?;
+    app.opener()
+        .open_path(path.to_string_lossy(), None::<&str>)
+        .map_err(|e| ZemenbarError::Other(format!("Failed to open log file: {}", e)))
+}
+
+fn get_settings_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("settings.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+/// The settings as last loaded or saved, held in managed state so commands, the tray
+/// and the scheduler read them without going to disk.
+struct SettingsState(RwLock<AppSettings>);
 
-    /// Converts a Gregorian date to Ethiopian calendar.
-    ///
-    /// Returns `None` if the conversion fails.
-    pub fn from_gregorian(year: i32, month: u32, day: u32) -> Option<Self> {
-        let gregorian = GregorianYear::new(year as usize, month as usize, day as usize);
-        let ethiopian: EthiopianYear = gregorian.into();
+/// Reads `settings.json`, upgrading an old layout first, or the locale defaults on first launch.
+fn read_settings_file(app: &tauri::AppHandle) -> Result<AppSettings, String> {
+    let settings_path = get_settings_path(app)?;
 
-        let day = ethiopian.day();
-        Some(Self {
-            year: ethiopian.year(),
-            month: ethiopian.month(),
-            day,
-            day_geez: Self::to_geez_number(day),
-        })
-    }
+    if settings_path.exists() {
+        let content = std::fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read settings file: {}", e))?;
 
-    pub fn amharic_month(&self) -> &'static str {
-        match self.month {
-            1 => "መስከረም",
-            2 => "ጥቅምት",
-            3 => "ኅዳር",
-            4 => "ታኅሣሥ",
-            5 => "ጥር",
-            6 => "የካቲት",
-            7 => "መጋቢት",
-            8 => "ሚያዝያ",
-            9 => "ግንቦት",
-            10 => "ሰኔ",
-            11 => "ሐምሌ",
-            12 => "ነሐሴ",
-            13 => "ጳጉሜ",
-            _ => "Unknown",
+        let mut value: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse settings: {}", e))?;
+        let migrated_from = settings_migration::migrate(&mut value)?;
+        let settings: AppSettings = serde_json::from_value(value)
+            .map_err(|e| format!("Failed to parse settings: {}", e))?;
+        if let Some(version) = migrated_from {
+            // Keep the old file before rewriting it, in case the upgrade lost something.
+            settings_migration::back_up(&settings_path, &content, version)?;
+            write_settings_file(&settings_path, &settings)?;
         }
+        Ok(settings)
+    } else {
+        Ok(AppSettings::for_system_locale())
     }
+}
 
-    pub fn english_month(&self) -> &'static str {
-        match self.month {
-            1 => "Meskerem",
-            2 => "Tikimt",
-            3 => "Hidar",
-            4 => "Tahsas",
-            5 => "Tir",
-            6 => "Yekatit",
-            7 => "Megabit",
-            8 => "Miazia",
-            9 => "Ginbot",
-            10 => "Sene",
-            11 => "Hamle",
-            12 => "Nehase",
-            13 => "Pagume",
-            _ => "Unknown",
-        }
-    }
+/// Hands the settings the date and holiday code reads from its own globals.
+fn apply_core_settings(settings: &AppSettings) {
+    set_pagume_6_policy(settings.pagume_6_policy);
+    holidays::set_shown_categories(settings.holiday_categories);
+    academic::set_enabled_presets(settings.academic_presets);
+}
 
-    pub fn days_in_month(&self) -> usize {
-        if self.month == 13 {
-            if self.year % 4 == 3 {
-                6
-            } else {
-                5
-            }
-        } else {
-            30
-        }
+/// The current settings, from managed state once the app is set up.
+#[tauri::command]
+fn load_settings(app: tauri::AppHandle) -> CommandResult<AppSettings> {
+    match app.try_state::<SettingsState>() {
+        Some(state) => state
+            .0
+            .read()
+            .map(|settings| settings.clone())
+            .map_err(|e| ZemenbarError::SettingsIo(format!("Failed to lock settings: {}", e))),
+        None => read_settings_file(&app).map_err(ZemenbarError::SettingsIo),
     }
+}
 
-    pub fn weekday(&self) -> usize {
-        let ethiopian = EthiopianYear::new(self.year, self.month, self.day);
-        let gregorian: GregorianYear = ethiopian.into();
+/// Copies text to the system clipboard.
+#[tauri::command]
+async fn copy_to_clipboard(app: tauri::AppHandle, text: String) -> CommandResult<()> {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
 
-        if let Some(date) = chrono::NaiveDate::from_ymd_opt(gregorian.year() as i32, gregorian.month() as u32, gregorian.day() as u32) {
-            date.weekday().num_days_from_sunday() as usize
-        } else {
-            0
-        }
-    }
+    app.clipboard()
+        .write_text(text)
+        .map_err(|e| ZemenbarError::Other(format!("Failed to copy to clipboard: {}", e)))
+}
 
-    pub fn amharic_weekday(&self) -> &'static str {
-        match self.weekday() {
-            0 => "እሁድ",
-            1 => "ሰኞ",
-            2 => "ማክሰኞ",
-            3 => "ረቡዕ",
-            4 => "ሐሙስ",
-            5 => "ዓርብ",
-            6 => "ቅዳሜ",
-            _ => "Unknown",
-        }
-    }
+/// Renders a date with one of the user's named copy templates and copies it.
+///
+/// Returns the copied text so the caller can show it as confirmation.
+#[tauri::command]
+async fn copy_date_with_template(
+    app: tauri::AppHandle,
+    name: String,
+    date: EthiopianDateInput,
+) -> CommandResult<String> {
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    let template = settings
+        .copy_templates
+        .iter()
+        .find(|t| t.name == name)
+        .ok_or_else(|| ZemenbarError::InvalidInput(format!("Unknown copy template: {}", name)))?;
+
+    let text = date.format_with(&template.template, display::format_options(&settings));
+    copy_to_clipboard(app, text.clone()).await?;
+    Ok(text)
+}
 
-    pub fn english_weekday(&self) -> &'static str {
-        match self.weekday() {
-            0 => "Sunday",
-            1 => "Monday",
-            2 => "Tuesday",
-            3 => "Wednesday",
-            4 => "Thursday",
-            5 => "Friday",
-            6 => "Saturday",
-            _ => "Unknown",
-        }
+/// A date as a spelled-out sentence for VoiceOver; `language` defaults to the display language.
+#[tauri::command]
+fn get_spoken_date(
+    app: tauri::AppHandle,
+    date: EthiopianDateInput,
+    language: Option<Language>,
+) -> CommandResult<String> {
+    let language = language.unwrap_or_else(|| load_settings(app).unwrap_or_default().language);
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    Ok(spoken::spoken_date(&date, language))
+}
+
+/// Every built-in copy format rendered for a date, for the copy menu.
+#[tauri::command]
+fn get_copy_formats(app: tauri::AppHandle, date: EthiopianDateInput) -> CommandResult<Vec<format::CopyFormatText>> {
+    let settings = load_settings(app).unwrap_or_default();
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    Ok(format::copy_format_texts(&date, display::format_options(&settings)))
+}
+
+/// Copies a date in one of the built-in formats and returns the copied text.
+#[tauri::command]
+async fn copy_date_as(
+    app: tauri::AppHandle,
+    date: EthiopianDateInput,
+    format: format::CopyFormat,
+) -> CommandResult<String> {
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    let date = date.to_date().map_err(ZemenbarError::Conversion)?;
+    let text = format
+        .render(&date, display::format_options(&settings))
+        .ok_or_else(|| ZemenbarError::Conversion(format!("Failed to convert {} to Gregorian", date)))?;
+    copy_to_clipboard(app, text.clone()).await?;
+    Ok(text)
+}
+
+/// Copies today's date exactly as the tray shows it and returns the copied text.
+#[tauri::command]
+async fn copy_today(app: tauri::AppHandle) -> CommandResult<String> {
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    let text = EthiopianDate::today().format_with(&display::display_template(&settings), display::format_options(&settings));
+    copy_to_clipboard(app, text.clone()).await?;
+    Ok(text)
+}
+
+/// Copies today's official-letter date stamp (ቁጥር/ቀን lines) and returns it.
+#[tauri::command]
+async fn copy_letter_date_stamp(
+    app: tauri::AppHandle,
+    layout: format::LetterStampLayout,
+    style: format::LetterDateStyle,
+    reference_number: Option<String>,
+) -> CommandResult<String> {
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    let text = format::letter_date_stamp(
+        &EthiopianDate::today(),
+        layout,
+        style,
+        settings.use_geez_numbers,
+        reference_number.as_deref(),
+    );
+    copy_to_clipboard(app, text.clone()).await?;
+    Ok(text)
+}
+
+fn write_settings_file(settings_path: &std::path::Path, settings: &AppSettings) -> Result<(), String> {
+    if let Some(parent) = settings_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
     }
 
-    /// Converts Arabic numerals to Geez numerals.
-    pub fn to_geez_number(num: usize) -> String {
-        if num == 0 {
-            return "".to_string();
-        }
+    let content = serde_json::to_string_pretty(settings)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
 
-        let geez_digits = ["", "፩", "፪", "፫", "፬", "፭", "፮", "፯", "፰", "፱"];
-        let geez_tens = ["", "፲", "፳", "፴", "፵", "፶", "፷", "፰", "፱"];
-
-        if num < 10 {
-            geez_digits[num].to_string()
-        } else if num < 100 {
-            let tens = num / 10;
-            let ones = num % 10;
-            if tens == 1 {
-                if ones == 0 {
-                    "፲".to_string()
-                } else {
-                    format!("፲{}", geez_digits[ones])
-                }
-            } else if ones == 0 {
-                geez_tens[tens].to_string()
-            } else {
-                format!("{}{}", geez_tens[tens], geez_digits[ones])
-            }
-        } else if num < 1000 {
-            let hundreds = num / 100;
-            let remainder = num % 100;
-            let hundred_part = if hundreds == 1 {
-                "፻".to_string()
-            } else {
-                format!("{}{}", geez_digits[hundreds], "፻")
-            };
-
-            if remainder == 0 {
-                hundred_part
-            } else {
-                format!("{}{}", hundred_part, Self::to_geez_number(remainder))
-            }
-        } else if num < 10000 {
-            let thousands = num / 100;
-            let remainder = num % 100;
-
-            let hundred_part = if thousands < 10 {
-                format!("{}፻", geez_digits[thousands])
-            } else if thousands < 100 {
-                let tens = thousands / 10;
-                let ones = thousands % 10;
-                if tens == 1 {
-                    if ones == 0 {
-                        "፲፻".to_string()
-                    } else {
-                        format!("፲{}፻", geez_digits[ones])
-                    }
-                } else if ones == 0 {
-                    format!("{}፻", geez_tens[tens])
-                } else {
-                    format!("{}{}፻", geez_tens[tens], geez_digits[ones])
-                }
-            } else {
-                format!("{}፻", Self::to_geez_number(thousands))
-            };
-
-            if remainder == 0 {
-                hundred_part
-            } else {
-                format!("{}{}", hundred_part, Self::to_geez_number(remainder))
-            }
-        } else {
-            num.to_string()
-        }
+    std::fs::write(settings_path, content)
+        .map_err(|e| format!("Failed to write settings file: {}", e))
+}
+
+/// Persists the settings, applies them everywhere and sends them to every window as `settings-changed`.
+#[tauri::command]
+fn save_settings(app: tauri::AppHandle, mut settings: AppSettings) -> CommandResult<()> {
+    let settings_path = get_settings_path(&app).map_err(ZemenbarError::SettingsIo)?;
+    settings.validate().map_err(ZemenbarError::InvalidInput)?;
+    let timezone = settings.parsed_timezone().map_err(ZemenbarError::InvalidInput)?;
+    // The frontend sends back what it loaded; whatever it sends is in this build's layout.
+    settings.version = settings_migration::CURRENT_VERSION;
+    let (launch_at_login_changed, previous_login_item) = app
+        .try_state::<SettingsState>()
+        .and_then(|state| {
+            let current = state.0.read().ok()?;
+            Some((current.launch_at_login != settings.launch_at_login, current.login_item))
+        })
+        .unwrap_or((true, settings.login_item));
+    if previous_login_item != settings.login_item {
+        // Only one mechanism should start the app; remove the old login item before adding the new one
+        apply_launch_at_login(&app, previous_login_item, false).map_err(ZemenbarError::Other)?;
+    }
+    if launch_at_login_changed || previous_login_item != settings.login_item {
+        apply_launch_at_login(&app, settings.login_item, settings.launch_at_login).map_err(ZemenbarError::Other)?;
+    }
+    write_settings_file(&settings_path, &settings).map_err(ZemenbarError::SettingsIo)?;
+    if let Some(state) = app.try_state::<SettingsState>() {
+        let mut current =
+            state.0.write().map_err(|e| ZemenbarError::SettingsIo(format!("Failed to lock settings: {}", e)))?;
+        *current = settings.clone();
     }
 
-    pub fn day_geez(&self) -> String {
-        Self::to_geez_number(self.day)
+    // A new timezone can move "today"; update the tray and windows right away
+    apply_core_settings(&settings);
+    let previous_today = EthiopianDate::today();
+    set_today_timezone(timezone);
+    let today = EthiopianDate::today();
+    if today != previous_today {
+        scheduler::refresh_all_surfaces(&app, &today);
     }
 
-    pub fn year_geez(&self) -> String {
-        Self::to_geez_number(self.year)
+    if let Err(e) = folder_sync::push_settings(&app, &settings) {
+        tracing::warn!("Failed to write settings to the sync folder: {}", e);
     }
+
+    app.emit("settings-changed", &settings).log_error("Failed to emit settings-changed");
+    refresh_tray_display(app.clone())?;
+    clock_tray::refresh(&app, &settings).map_err(ZemenbarError::Panel)?;
+    refresh_tray_menu(&app, &settings).map_err(ZemenbarError::Panel)?;
+    // The settings are saved either way; report shortcuts another app already holds
+    shortcuts::register(&app, &settings.shortcuts).map_err(ZemenbarError::InvalidInput)
 }
 
-/// Represents a complete month view for the Ethiopian calendar.
-#[derive(Serialize, Deserialize)]
-pub struct CalendarMonth {
-    pub year: usize,
-    pub year_geez: String,
-    pub month: usize,
-    pub month_name_amharic: String,
-    pub month_name_english: String,
-    pub days: Vec<CalendarDay>,
-    pub first_day_weekday: usize,
+/// Turns folder sync on with `directory`, moves it to another folder, or turns it off with `None`.
+#[tauri::command]
+async fn set_sync_directory(app: tauri::AppHandle, directory: Option<String>) -> CommandResult<()> {
+    let directory = directory.map(|directory| directory.trim().to_string()).filter(|directory| !directory.is_empty());
+    if let Some(directory) = &directory {
+        if !std::path::Path::new(directory).is_dir() {
+            return Err(ZemenbarError::InvalidInput(format!("Sync folder {} does not exist", directory)));
+        }
+    }
+    let mut settings = load_settings(app.clone())?;
+    settings.sync_directory = directory.clone();
+    save_settings(app.clone(), settings)?;
+    Ok(folder_sync::watch(&app, directory.as_deref())?)
 }
 
-#[derive(Serialize, Deserialize)]
-pub struct CalendarDay {
-    pub day: usize,
-    pub day_geez: String,
-    pub is_today: bool,
-    pub weekday: usize,
-    pub weekday_name_amharic: String,
-    pub weekday_name_english: String,
+/// Checks GitHub for a newer release, even with background checks turned off.
+#[tauri::command]
+async fn check_for_updates(app: tauri::AppHandle) -> CommandResult<updates::UpdateInfo> {
+    Ok(updates::check(&app)?)
 }
 
-impl CalendarMonth {
-    pub fn new(year: usize, month: usize) -> Self {
-        let first_day = EthiopianDate { year, month, day: 1, day_geez: EthiopianDate::to_geez_number(1) };
-        let days_in_month = first_day.days_in_month();
-        let first_day_weekday = first_day.weekday();
-        let today = EthiopianDate::today();
+/// Syncs with the sync folder right away.
+#[tauri::command]
+async fn sync_folder(app: tauri::AppHandle) -> CommandResult<folder_sync::FolderSyncResult> {
+    Ok(folder_sync::sync(&app)?)
+}
 
-        let mut days = Vec::new();
-        for day in 1..=days_in_month {
-            let date = EthiopianDate { year, month, day, day_geez: EthiopianDate::to_geez_number(day) };
-            let is_today = date.year == today.year && date.month == today.month && date.day == today.day;
-            days.push(CalendarDay {
-                day,
-                day_geez: date.day_geez(),
-                is_today,
-                weekday: date.weekday(),
-                weekday_name_amharic: date.amharic_weekday().to_string(),
-                weekday_name_english: date.english_weekday().to_string(),
-            });
-        }
+fn store_paths(app: &tauri::AppHandle) -> Result<backup::StorePaths, String> {
+    Ok(backup::StorePaths {
+        events: get_events_path(app)?,
+        birthdays: get_birthdays_path(app)?,
+        custom_holidays: get_custom_holidays_path(app)?,
+        paydays: get_paydays_path(app)?,
+    })
+}
 
-        Self {
-            year,
-            year_geez: EthiopianDate::to_geez_number(year),
-            month,
-            month_name_amharic: first_day.amharic_month().to_string(),
-            month_name_english: first_day.english_month().to_string(),
-            days,
-            first_day_weekday,
-        }
-    }
+/// Everything currently loaded, as a backup.
+fn current_backup(app: &tauri::AppHandle) -> Result<backup::Backup, String> {
+    let settings = serde_json::to_value(load_settings(app.clone())?)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+    Ok(backup::Backup::new(
+        settings,
+        events::all_events(),
+        birthdays::list_birthdays(),
+        custom_holidays::custom_holidays(),
+        paydays::list_paydays(),
+    ))
 }
 
+/// Writes the settings, events, birthdays, custom holidays and paydays to a backup file.
+#[tauri::command]
+async fn export_backup(app: tauri::AppHandle, path: String) -> CommandResult<backup::BackupSummary> {
+    let backup = current_backup(&app)?;
+    backup::write_backup(std::path::Path::new(&path), &backup)?;
+    Ok(backup.summary())
+}
 
-tauri_panel! {
-    panel!(CalendarPanel {
-        config: {
-            can_become_key_window: true,
-            is_floating_panel: true
+/// Replaces all app data with a backup file's, after checking the whole file.
+///
+/// The data it replaces is kept in `backup-before-restore.json` in the app data directory,
+/// and put back if the restore fails part way.
+#[tauri::command]
+async fn import_backup(app: tauri::AppHandle, path: String) -> CommandResult<backup::BackupSummary> {
+    // A file that is not a backup this build can read is bad input, not a failure of the app
+    let mut backup = backup::read_backup(std::path::Path::new(&path)).map_err(ZemenbarError::InvalidInput)?;
+    settings_migration::migrate(&mut backup.settings).map_err(ZemenbarError::InvalidInput)?;
+    let settings: AppSettings = serde_json::from_value(backup.settings.clone())
+        .map_err(|e| ZemenbarError::InvalidInput(format!("Failed to parse backup settings: {}", e)))?;
+    // Check the settings first, so a backup save_settings would reject leaves everything as it was
+    settings.validate().map_err(ZemenbarError::InvalidInput)?;
+
+    let previous_settings = load_settings(app.clone())?;
+    let previous = current_backup(&app).map_err(ZemenbarError::Other)?;
+    let data_dir = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| ZemenbarError::Other(format!("Failed to get app data directory: {}", e)))?;
+    backup::write_backup(&data_dir.join(backup::PRE_RESTORE_FILE), &previous).map_err(ZemenbarError::Other)?;
+    let paths = store_paths(&app).map_err(ZemenbarError::Other)?;
+
+    // Saving the settings can still fail (the login item, shortcuts, the file), so it goes first,
+    // and whatever step fails, the settings and data from before are put back
+    let put_back_settings = || {
+        save_settings(app.clone(), previous_settings.clone()).log_error("Failed to put back the previous settings");
+    };
+    if let Err(e) = save_settings(app.clone(), settings) {
+        put_back_settings();
+        return Err(e);
+    }
+    let holidays = match restore_and_reload(&backup, &paths) {
+        Ok(holidays) => holidays,
+        Err(e) => {
+            restore_and_reload(&previous, &paths).log_error("Failed to put back the previous data");
+            put_back_settings();
+            return Err(ZemenbarError::Other(e));
         }
-    })
-}
+    };
 
-/// Global state for remembering tray icon position to position calendar window correctly.
-static LAST_TRAY_X: Mutex<Option<f64>> = Mutex::new(None);
+    app.emit("events-changed", ()).log_error("Failed to emit events-changed");
+    app.emit("birthdays-changed", ()).log_error("Failed to emit birthdays-changed");
+    app.emit("holidays-changed", &holidays).log_error("Failed to emit holidays-changed");
+    paydays_changed(&app);
+    Ok(backup.summary())
+}
 
-/// Application settings that control calendar display and behavior
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct AppSettings {
-    pub use_amharic: bool,
-    pub use_geez_numbers: bool,
-    pub show_date_in_tray: bool,
-    pub use_numeric_format: bool,
-    pub show_qen: bool,
-    pub show_amete_mihret: bool,
+/// Writes the stores in `backup` and loads them, returning the custom holidays now in effect.
+fn restore_and_reload(backup: &backup::Backup, paths: &backup::StorePaths) -> Result<Vec<custom_holidays::CustomHoliday>, String> {
+    backup::restore_stores(backup, paths)?;
+    events::load_events(&paths.events)?;
+    birthdays::load_birthdays(&paths.birthdays)?;
+    let holidays = custom_holidays::load_custom_holidays(&paths.custom_holidays)?;
+    paydays::load_paydays(&paths.paydays)?;
+    Ok(holidays)
 }
 
-impl Default for AppSettings {
-    fn default() -> Self {
-        Self {
-            use_amharic: true,
-            use_geez_numbers: false,
-            show_date_in_tray: true,
-            use_numeric_format: false,
-            show_qen: false,
-            show_amete_mihret: false,
-        }
+/// Registers or removes the `mechanism` login item so it matches `enabled`, leaving it alone when it already does.
+fn apply_launch_at_login(
+    app: &tauri::AppHandle,
+    mechanism: login_item::LoginItemMechanism,
+    enabled: bool,
+) -> Result<(), String> {
+    if login_item::is_enabled(app, mechanism)? == enabled {
+        return Ok(());
     }
+    login_item::set_enabled(app, mechanism, enabled)
 }
 
+/// Whether ZemenBar is currently registered to start at login.
 #[tauri::command]
-fn get_current_ethiopian_date() -> EthiopianDate {
-    EthiopianDate::today()
+fn get_autostart_status(app: tauri::AppHandle) -> CommandResult<bool> {
+    let settings = load_settings(app.clone())?;
+    Ok(login_item::is_enabled(&app, settings.login_item)?)
 }
 
+/// Turns launching at login on or off and remembers the choice.
 #[tauri::command]
-fn get_ethiopian_calendar_month(year: usize, month: usize) -> CalendarMonth {
-    CalendarMonth::new(year, month)
+fn set_autostart(app: tauri::AppHandle, enabled: bool) -> CommandResult<()> {
+    let mut settings = load_settings(app.clone())?;
+    apply_launch_at_login(&app, settings.login_item, enabled)?;
+    settings.launch_at_login = enabled;
+    save_settings(app, settings)
 }
 
-/// Tauri command to convert Gregorian date to Ethiopian calendar.
-#[tauri::command]
-fn convert_gregorian_to_ethiopian(year: i32, month: u32, day: u32) -> Option<EthiopianDate> {
-    EthiopianDate::from_gregorian(year, month, day)
+/// Rebuilds the tray menu, whose copy entries and upcoming holidays depend on the settings and the date.
+fn refresh_tray_menu(app: &tauri::AppHandle, settings: &AppSettings) -> Result<(), String> {
+    if let Some(tray) = app.tray_by_id("main") {
+        let menu = build_tray_menu(app, settings).map_err(|e| format!("Failed to build tray menu: {}", e))?;
+        tray.set_menu(Some(menu)).map_err(|e| format!("Failed to set tray menu: {}", e))?;
+    }
+    Ok(())
 }
 
-/// Positions the calendar window relative to the tray icon. Maybe it would be to have it left align to tray? TODO
+/// Applies and persists the panel's vibrancy material, opacity and corner radius.
 #[tauri::command]
-fn position_calendar_window(app: tauri::AppHandle, tray_x: Option<f64>) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("settings") {
-        if let Ok(Some(monitor)) = window.primary_monitor() {
-            let scale_factor = monitor.scale_factor();
+fn set_panel_appearance(app: tauri::AppHandle, appearance: appearance::PanelAppearance) -> CommandResult<()> {
+    let appearance = appearance.clamped();
+    appearance::apply_panel_appearance(&app, &appearance).map_err(ZemenbarError::Panel)?;
 
-            let x = if let Some(tray_x) = tray_x {
-                if let Ok(mut last_x) = LAST_TRAY_X.lock() {
-                    *last_x = Some(tray_x);
-                }
-                tray_x / scale_factor
-            } else if let Ok(last_x) = LAST_TRAY_X.lock() {
-                if let Some(stored_x) = *last_x {
-                    stored_x / scale_factor
-                } else {
-                    let size = monitor.size();
-                    let logical_width = (size.width as f64) / scale_factor;
-                    logical_width - 380.0
-                }
-            } else {
-                let size = monitor.size();
-                let logical_width = (size.width as f64) / scale_factor;
-                logical_width - 380.0
-            };
-
-            let y = 28.0;
-            let _ = window.set_position(tauri::Position::Logical(tauri::LogicalPosition { x, y }));
-        }
+    let mut settings = load_settings(app.clone()).unwrap_or_default();
+    settings.panel_appearance = appearance;
+    save_settings(app, settings)
+}
+
+/// Shows or closes the desktop widget and remembers the choice.
+#[tauri::command]
+fn set_desktop_widget(app: tauri::AppHandle, enabled: bool) -> CommandResult<()> {
+    if enabled {
+        widget::show_widget(&app).map_err(ZemenbarError::Panel)?;
+    } else {
+        widget::close_widget(&app).map_err(ZemenbarError::Panel)?;
     }
-    Ok(())
+
+    let mut settings = load_settings(app.clone()).unwrap_or_default();
+    settings.show_desktop_widget = enabled;
+    save_settings(app, settings)
 }
 
+/// Turns the macOS Calendar overlay on or off, asking for calendar access when turning it on.
 #[tauri::command]
-fn resize_calendar_window(app: tauri::AppHandle, height: f64) -> Result<(), String> {
-    if let Some(window) = app.get_webview_window("settings") {
-        let _ = window.set_size(tauri::Size::Logical(tauri::LogicalSize { width: 360.0, height }));
+async fn set_system_calendar_events(app: tauri::AppHandle, enabled: bool) -> CommandResult<()> {
+    if enabled && !system_calendar::request_access()? {
+        return Err(ZemenbarError::PermissionDenied("Calendar access was not granted".to_string()));
     }
-    Ok(())
+
+    let mut settings = load_settings(app.clone()).unwrap_or_default();
+    settings.show_system_calendar_events = enabled;
+    save_settings(app, settings)
 }
 
+/// Events from the macOS Calendar app on the days of an Ethiopian month; empty while the overlay is off.
 #[tauri::command]
-fn set_tray_text(app: tauri::AppHandle, text: String) -> Result<(), String> {
-    if let Some(tray) = app.tray_by_id("main") {
-        let _ = tray.set_title(Some(&text));
+fn get_system_events_for_month(
+    app: tauri::AppHandle,
+    year: usize,
+    month: usize,
+) -> CommandResult<Vec<system_calendar::SystemEvent>> {
+    let settings = load_settings(app).unwrap_or_default();
+    if !settings.show_system_calendar_events {
+        return Ok(Vec::new());
     }
-    Ok(())
+    Ok(system_calendar::events_for_month(year, month)?)
 }
 
 #[tauri::command]
-fn set_tray_icon(app: tauri::AppHandle) -> Result<(), String> {
+fn refresh_tray_display(app: tauri::AppHandle) -> CommandResult<()> {
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    // Icon mode shows the icon alone
+    let text = if settings.show_date_in_tray {
+        tray_icon::hide(&app).map_err(ZemenbarError::Panel)?;
+        display::tray_text(&settings, &EthiopianDate::today())
+    } else {
+        tray_icon::show(&app, settings.tray_icon).map_err(ZemenbarError::Panel)?;
+        String::new()
+    };
+
     if let Some(tray) = app.tray_by_id("main") {
-        let _ = tray.set_title(Some("📅"));
+        tray.set_title(Some(&text))
+            .map_err(|e| ZemenbarError::Panel(format!("Failed to set tray text: {}", e)))?;
     }
+
     Ok(())
 }
 
-fn get_settings_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
-    app.path()
-        .app_data_dir()
-        .map(|dir| dir.join("settings.json"))
-        .map_err(|e| format!("Failed to get app data directory: {}", e))
+/// The Synaxarium commemorations of an Ethiopian month and day.
+#[tauri::command]
+fn get_synaxarium_entry(month: usize, day: usize) -> Option<synaxarium::SynaxariumEntry> {
+    synaxarium::entry(month, day)
 }
 
+/// The Synaxarium commemorations of each day of an Ethiopian month, for the month view.
 #[tauri::command]
-fn load_settings(app: tauri::AppHandle) -> Result<AppSettings, String> {
-    let settings_path = get_settings_path(&app)?;
+fn get_synaxarium_month(month: usize) -> Vec<synaxarium::SynaxariumEntry> {
+    synaxarium::entries_in_month(month)
+}
 
-    if settings_path.exists() {
-        let content = std::fs::read_to_string(&settings_path)
-            .map_err(|e| format!("Failed to read settings file: {}", e))?;
+/// Next date on `month`/`day` from `from` (today when not given), e.g. the next Meskerem 1.
+#[tauri::command]
+fn next_occurrence(month: usize, day: usize, from: Option<EthiopianDateInput>) -> CommandResult<EthiopianDate> {
+    let from = match from {
+        Some(from) => from.to_date().map_err(ZemenbarError::Conversion)?,
+        None => EthiopianDate::today(),
+    };
+    EthiopianDate::next_occurrence(month, day, &from, pagume_6_policy())
+        .ok_or_else(|| ZemenbarError::InvalidInput(format!("Invalid Ethiopian month and day: {}/{}", month, day)))
+}
 
-        serde_json::from_str(&content)
-            .map_err(|e| format!("Failed to parse settings: {}", e))
-    } else {
-        Ok(AppSettings::default())
-    }
+/// The next `count` holidays and observances from today, named in the display language.
+#[tauri::command]
+fn get_upcoming_holidays(app: tauri::AppHandle, count: usize) -> Vec<holidays::UpcomingHoliday> {
+    let settings = load_settings(app).unwrap_or_default();
+    holidays::upcoming_holidays(&EthiopianDate::today(), count, settings.language)
 }
 
-/// Copies text to the system clipboard.
+/// Launcher items (Alfred/Raycast Script Filter JSON) for `query`, in the display language.
 #[tauri::command]
-async fn copy_to_clipboard(app: tauri::AppHandle, text: String) -> Result<(), String> {
-    use tauri_plugin_clipboard_manager::ClipboardExt;
+fn get_script_filter(app: tauri::AppHandle, query: Option<String>) -> launcher::ScriptFilter {
+    let settings = load_settings(app).unwrap_or_default();
+    launcher::script_filter(query.as_deref(), settings.language)
+}
 
-    app.clipboard()
-        .write_text(text)
-        .map_err(|e| format!("Failed to copy to clipboard: {}", e))
+/// Holidays of `year` (this year when not given) matching `query` by name, best matches first.
+#[tauri::command]
+fn find_holiday(query: String, year: Option<usize>) -> Vec<holidays::HolidayMatch> {
+    holidays::find_holiday(&query, year.unwrap_or_else(|| EthiopianDate::today().year))
 }
 
+/// Terms, semesters and exam periods of the enabled academic presets in `year`.
 #[tauri::command]
-fn save_settings(app: tauri::AppHandle, settings: AppSettings) -> Result<(), String> {
-    let settings_path = get_settings_path(&app)?;
+fn get_academic_periods(app: tauri::AppHandle, year: usize) -> Vec<academic::AcademicPeriod> {
+    academic::periods_for_year(year, load_settings(app).unwrap_or_default().academic_presets)
+}
 
-    if let Some(parent) = settings_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create settings directory: {}", e))?;
-    }
+/// The enabled academic periods under way on `date` (today when not given), with their week number.
+#[tauri::command]
+fn get_current_academic_periods(date: Option<EthiopianDateInput>) -> CommandResult<Vec<academic::CurrentPeriod>> {
+    let date = match date {
+        Some(date) => date.to_date().map_err(ZemenbarError::Conversion)?,
+        None => EthiopianDate::today(),
+    };
+    Ok(academic::current_periods(&date))
+}
 
-    let content = serde_json::to_string_pretty(&settings)
-        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+/// Resolves free text such as "next Friday", "in 40 days" or "Fasika 2017" to a day.
+#[tauri::command]
+fn query_date(text: String) -> Option<query::DateQuery> {
+    query::query_date(&text, &EthiopianDate::today())
+}
 
-    std::fs::write(&settings_path, content)
-        .map_err(|e| format!("Failed to write settings file: {}", e))?;
-    Ok(())
+/// Every holiday and observance from `start` to `end` inclusive, with either bound in either calendar.
+#[tauri::command]
+fn get_holidays_between(start: CalendarDateInput, end: CalendarDateInput) -> CommandResult<Vec<holidays::Holiday>> {
+    let start = start.to_date().map_err(ZemenbarError::Conversion)?;
+    let end = end.to_date().map_err(ZemenbarError::Conversion)?;
+    if start > end {
+        return Err(ZemenbarError::InvalidInput("Start date is after end date".to_string()));
+    }
+    Ok(holidays::holidays_between(&start, &end))
 }
 
+/// Counts the tray down to `target`, or goes back to showing the date when `target` is `None`.
 #[tauri::command]
-fn refresh_tray_display(app: tauri::AppHandle) -> Result<(), String> {
-    let settings = load_settings(app.clone()).unwrap_or_default();
-    let today = EthiopianDate::today();
-    let month_meta = CalendarMonth::new(today.year, today.month);
+fn set_countdown(app: tauri::AppHandle, target: Option<EthiopianDateInput>) -> CommandResult<AppSettings> {
+    let mut settings = load_settings(app.clone())?;
+    settings.countdown_target = target.map(|target| target.to_date()).transpose().map_err(ZemenbarError::Conversion)?;
+    settings.tray_mode = if settings.countdown_target.is_some() { TrayMode::Countdown } else { TrayMode::Date };
+    save_settings(app.clone(), settings.clone())?;
+    refresh_tray_display(app)?;
+    Ok(settings)
+}
 
-    let text = if settings.use_numeric_format {
-        let dd = if settings.use_geez_numbers {
-            EthiopianDate::to_geez_number(today.day)
-        } else {
-            format!("{:02}", today.day)
-        };
-        let mm = if settings.use_geez_numbers {
-            EthiopianDate::to_geez_number(today.month)
-        } else {
-            format!("{:02}", today.month)
-        };
-        let yyyy = if settings.use_geez_numbers {
-            EthiopianDate::to_geez_number(today.year)
-        } else {
-            today.year.to_string()
-        };
+/// Number of holidays listed in the tray menu's "Upcoming Holidays" submenu.
+const UPCOMING_MENU_HOLIDAYS: usize = 5;
+
+/// Builds the tray menu in the display language, including one "Copy Date" entry per copy template.
+fn build_tray_menu(app: &tauri::AppHandle, settings: &AppSettings) -> tauri::Result<Menu<tauri::Wry>> {
+    let label = |key: &str| strings::label(settings.language, key);
+    let show_item = MenuItem::with_id(app, "show", label("show_calendar"), true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "quit", label("quit"), true, None::<&str>)?;
+
+    let template_items = settings
+        .copy_templates
+        .iter()
+        .enumerate()
+        .map(|(index, template)| {
+            MenuItem::with_id(app, format!("copy_template:{}", index), &template.name, true, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let format_items = format::copy_format_texts(&EthiopianDate::today(), display::format_options(settings))
+        .iter()
+        .enumerate()
+        .map(|(index, format)| {
+            MenuItem::with_id(app, format!("copy_format:{}", index), &format.text, true, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let separator = PredefinedMenuItem::separator(app)?;
+    let formats_separator = PredefinedMenuItem::separator(app)?;
+    let letter_item = MenuItem::with_id(app, "copy_letter_stamp", label("letter_date_stamp"), true, None::<&str>)?;
+
+    let mut copy_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        template_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    if !copy_refs.is_empty() {
+        copy_refs.push(&separator);
+    }
+    copy_refs.extend(format_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>));
+    copy_refs.push(&formats_separator);
+    copy_refs.push(&letter_item);
+    let copy_menu = Submenu::with_id_and_items(app, "copy", label("copy_date"), true, &copy_refs)?;
+
+    let upcoming_items = holidays::upcoming_holidays(&EthiopianDate::today(), UPCOMING_MENU_HOLIDAYS, settings.language)
+        .iter()
+        .map(|holiday| {
+            let text = format!("{} · {}", holiday.name, display::days_left_text(holiday.days_remaining as usize, settings));
+            MenuItem::with_id(app, format!("upcoming:{}", holiday.id), text, false, None::<&str>)
+        })
+        .collect::<tauri::Result<Vec<_>>>()?;
+    let upcoming_refs: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        upcoming_items.iter().map(|item| item as &dyn IsMenuItem<tauri::Wry>).collect();
+    let upcoming_menu = Submenu::with_id_and_items(app, "upcoming", label("upcoming_holidays"), true, &upcoming_refs)?;
+
+    let print_item = MenuItem::with_id(app, "print_calendar", label("print"), true, None::<&str>)?;
+    let contacts_item =
+        MenuItem::with_id(app, "sync_contacts_birthdays", label("import_contacts_birthdays"), true, None::<&str>)?;
+
+    let update_item = updates::available_update()
+        .map(|update| {
+            let text = label("update_available").replace("{version}", &update.latest_version);
+            MenuItem::with_id(app, "open_update", text, true, None::<&str>)
+        })
+        .transpose()?;
 
-        let parts = vec![dd, mm, yyyy];
-        parts.join("/")
-    } else {
-        let month_name = if settings.use_amharic {
-            month_meta.month_name_amharic.clone()
-        } else {
-            month_meta.month_name_english.clone()
-        };
-        let day_txt = if settings.use_geez_numbers {
-            today.day_geez.clone()
-        } else {
-            today.day.to_string()
-        };
-        let year_txt = if settings.use_geez_numbers {
-            month_meta.year_geez.clone()
-        } else {
-            today.year.to_string()
-        };
+    let mut items: Vec<&dyn IsMenuItem<tauri::Wry>> =
+        vec![&show_item, &copy_menu, &upcoming_menu, &print_item, &contacts_item];
+    if let Some(update_item) = &update_item {
+        items.push(update_item);
+    }
+    items.push(&quit_item);
+    Menu::with_items(app, &items)
+}
 
-        let mut parts = Vec::new();
-        parts.push(month_name);
-        parts.push(day_txt);
-        if settings.use_amharic && settings.show_qen {
-            parts.push("ቀን".to_string());
-        }
-        parts.push(year_txt);
-        if settings.use_amharic && settings.show_amete_mihret {
-            parts.push("ዓ.ም.".to_string());
+/// Opens the release page of the update offered in the tray menu.
+fn open_update_from_menu(app: &tauri::AppHandle) {
+    use tauri_plugin_opener::OpenerExt;
+
+    if let Some(update) = updates::available_update() {
+        if let Err(e) = app.opener().open_url(update.url, None::<&str>) {
+            tracing::warn!("Failed to open the release page: {}", e);
         }
-        parts.join(" ")
+    }
+}
+
+/// Handles the tray's "Copy Date" submenu entries.
+fn copy_today_from_menu(app: &tauri::AppHandle, id: &str) {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let settings = load_settings(app.clone()).unwrap_or_default();
+    let today = EthiopianDate::today();
+    let text = if id == "copy_letter_stamp" {
+        Some(format::letter_date_stamp(
+            &today,
+            format::LetterStampLayout::NumberAndDate,
+            format::LetterDateStyle::Numeric,
+            settings.use_geez_numbers,
+            None,
+        ))
+    } else if let Some(index) = id.strip_prefix("copy_format:") {
+        index
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| format::CopyFormat::ALL.get(index))
+            .and_then(|format| format.render(&today, display::format_options(&settings)))
+    } else {
+        id.strip_prefix("copy_template:")
+            .and_then(|index| index.parse::<usize>().ok())
+            .and_then(|index| settings.copy_templates.get(index))
+            .map(|template| today.format_with(&template.template, display::format_options(&settings)))
     };
 
-    if let Some(tray) = app.tray_by_id("main") {
-        let _ = tray.set_title(Some(&text));
+    if let Some(text) = text {
+        if let Err(e) = app.clipboard().write_text(text) {
+            tracing::warn!("Failed to copy to clipboard: {}", e);
+        }
     }
-
-    Ok(())
 }
 
 fn create_calendar_panel(app: &tauri::App) -> Result<(), String> {
@@ -536,28 +1649,80 @@ pub fn run() {
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_clipboard_manager::init())
+        .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_single_instance::init(|app, _argv, _cwd| {
             if let Some(window) = app.get_webview_window("settings") {
-                let _ = window.show();
-                let _ = window.set_focus();
+                window.show().log_error("Failed to show calendar window");
+                window.set_focus().log_error("Failed to focus calendar window");
             }
         }))
+        // Handles the LaunchAgent login item; `login_item` adds the SMAppService one
         .plugin(tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, None))
+        .plugin(tauri_plugin_global_shortcut::Builder::new().with_handler(shortcuts::handle).build())
         .setup(|app| {
+            match get_logs_dir(app.handle()).and_then(|dir| logging::init(&dir)) {
+                Ok(()) => tracing::info!("ZemenBar {} starting", env!("CARGO_PKG_VERSION")),
+                Err(e) => eprintln!("{}", e),
+            }
             #[cfg(target_os = "macos")]
             {
                 app.set_activation_policy(tauri::ActivationPolicy::Accessory);
             }
 
-            {
-                use tauri_plugin_autostart::ManagerExt;
-                if let Ok(false) = app.autolaunch().is_enabled() {
-                    let _ = app.autolaunch().enable();
+            match get_packs_dir(app.handle()) {
+                Ok(dir) => {
+                    packs::load_packs(&dir);
                 }
+                Err(e) => tracing::warn!("{}", e),
             }
-            let quit_item = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
-            let show_item = MenuItem::with_id(app, "show", "Show Calendar", true, None::<&str>)?;
-            let menu = Menu::with_items(app, &[&show_item, &quit_item])?;
+            if let Err(e) = get_custom_holidays_path(app.handle()).and_then(|path| custom_holidays::load_custom_holidays(&path)) {
+                tracing::warn!("Failed to load custom holidays: {}", e);
+            }
+            if let Err(e) = get_events_path(app.handle()).and_then(|path| events::load_events(&path)) {
+                tracing::warn!("Failed to load events: {}", e);
+            }
+            if let Err(e) = get_birthdays_path(app.handle()).and_then(|path| birthdays::load_birthdays(&path)) {
+                tracing::warn!("Failed to load birthdays: {}", e);
+            }
+            if let Err(e) = get_paydays_path(app.handle()).and_then(|path| paydays::load_paydays(&path)) {
+                tracing::warn!("Failed to load paydays: {}", e);
+            }
+
+            let mut settings = read_settings_file(app.handle()).unwrap_or_else(|e| {
+                tracing::warn!("{}", e);
+                AppSettings::default()
+            });
+            let first_launch = get_settings_path(app.handle()).is_ok_and(|path| !path.exists());
+            if !first_launch {
+                // The login item may have been removed or added in the system settings since; follow it
+                if let Ok(enabled) = login_item::is_enabled(app.handle(), settings.login_item) {
+                    if enabled != settings.launch_at_login {
+                        settings.launch_at_login = enabled;
+                        let saved = get_settings_path(app.handle()).and_then(|path| write_settings_file(&path, &settings));
+                        if let Err(e) = saved {
+                            tracing::warn!("Failed to save launch at login: {}", e);
+                        }
+                    }
+                }
+            }
+            match settings.parsed_timezone() {
+                Ok(timezone) => set_today_timezone(timezone),
+                Err(e) => tracing::warn!("Ignoring timezone setting: {}", e),
+            }
+            tracing::info!("Today is {} in {}", EthiopianDate::today(), today_timezone());
+            apply_core_settings(&settings);
+            app.manage(SettingsState(RwLock::new(settings.clone())));
+            // On first launch, keep the settings detected from the system locale; the login item waits for the user
+            if first_launch {
+                if let Err(e) = apply_launch_at_login(app.handle(), settings.login_item, settings.launch_at_login) {
+                    tracing::warn!("{}", e);
+                }
+                if let Err(e) = save_settings(app.handle().clone(), settings.clone()) {
+                    tracing::warn!("Failed to save initial settings: {}", e);
+                }
+            }
+            let menu = build_tray_menu(app.handle(), &settings)?;
 
             // Create system tray
             let _tray = TrayIconBuilder::with_id("main")
@@ -571,10 +1736,14 @@ pub fn run() {
                     }
                     "show" => {
                         if let Some(window) = app.get_webview_window("settings") {
-                            let _ = position_calendar_window(app.clone(), None);
-                            let _ = window.show();
+                            position_calendar_window(app.clone(), None).log_error("Failed to position calendar window");
+                            window.show().log_error("Failed to show calendar window");
                         }
                     }
+                    "sync_contacts_birthdays" => sync_contacts_birthdays_from_menu(app),
+                    "print_calendar" => print_calendar_from_menu(app),
+                    "open_update" => open_update_from_menu(app),
+                    id if id.starts_with("copy_") => copy_today_from_menu(app, id),
                     _ => {}
                 })
                 .on_tray_icon_event(|tray, event| if let TrayIconEvent::Click {
@@ -586,40 +1755,74 @@ pub fn run() {
                     let app = tray.app_handle();
                     if let Some(window) = app.get_webview_window("settings") {
                         if window.is_visible().unwrap_or(false) {
-                            let _ = window.hide();
+                            window.hide().log_error("Failed to hide calendar window");
                         } else {
                             let tray_x = position.x - 180.0;
-                            let _ = position_calendar_window(app.clone(), Some(tray_x));
-                            let _ = window.show();
+                            position_calendar_window(app.clone(), Some(tray_x))
+                                .log_error("Failed to position calendar window");
+                            window.show().log_error("Failed to show calendar window");
                         }
                     }
                 })
                 .build(app)?;
-            {
-                let settings = load_settings(app.handle().clone()).unwrap_or_default();
-                let today = EthiopianDate::today();
-                let month_meta = CalendarMonth::new(today.year, today.month);
-                let month_name = if settings.use_amharic { month_meta.month_name_amharic.clone() } else { month_meta.month_name_english.clone() };
-                let day_txt = if settings.use_geez_numbers { today.day_geez.clone() } else { today.day.to_string() };
-                let year_txt = if settings.use_geez_numbers { month_meta.year_geez.clone() } else { today.year.to_string() };
-                let text = format!("{} {} {}", month_name, day_txt, year_txt);
-                if let Some(tray) = app.tray_by_id("main") {
-                    let _ = tray.set_title(Some(&text));
-                }
+            if let Err(e) = refresh_tray_display(app.handle().clone()) {
+                tracing::warn!("Failed to set tray text: {}", e);
+            }
+            if let Err(e) = clock_tray::refresh(app.handle(), &settings) {
+                tracing::warn!("{}", e);
             }
             if let Some(window) = app.get_webview_window("settings") {
                 let window_clone = window.clone();
-                window.on_window_event(move |event| {
-                    if let tauri::WindowEvent::Focused(false) = event {
-                        if window_clone.is_visible().unwrap_or(false) {
-                            let _ = window_clone.hide();
-                        }
+                let handle = app.handle().clone();
+                window.on_window_event(move |event| match event {
+                    tauri::WindowEvent::Focused(false) if window_clone.is_visible().unwrap_or(false) => {
+                        window_clone.hide().log_error("Failed to hide calendar window");
                     }
+                    // Moved to a monitor with another scale factor, or that monitor's resolution changed
+                    tauri::WindowEvent::ScaleFactorChanged { .. } => monitors::reposition_panel(&handle),
+                    // The window follows the system appearance, and so does the menu bar the icon is on
+                    tauri::WindowEvent::ThemeChanged(_) => {
+                        refresh_tray_display(handle.clone()).log_error("Failed to update tray icon");
+                    }
+                    _ => {}
                 });
             }
 
             if let Err(e) = create_calendar_panel(app) {
-                eprintln!("Failed to setup calendar panel: {}", e);
+                tracing::error!("Failed to setup calendar panel: {}", e);
+            }
+
+            if let Err(e) = appearance::apply_panel_appearance(app.handle(), &settings.panel_appearance) {
+                tracing::warn!("Failed to apply panel appearance: {}", e);
+            }
+            if let Err(e) = shortcuts::register(app.handle(), &settings.shortcuts) {
+                tracing::warn!("{}", e);
+            }
+
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        if let Err(e) = deep_link::handle(&handle, &url) {
+                            tracing::warn!("Failed to open link {}: {}", url, e);
+                        }
+                    }
+                });
+            }
+
+            scheduler::start(app.handle().clone());
+            caldav::start(app.handle().clone());
+            folder_sync::start(app.handle());
+            google_calendar::start(app.handle().clone());
+            holiday_feed::start(app.handle().clone());
+            monitors::start(app.handle().clone());
+            updates::start(app.handle().clone());
+
+            if settings.show_desktop_widget {
+                if let Err(e) = widget::show_widget(app.handle()) {
+                    tracing::warn!("Failed to show desktop widget: {}", e);
+                }
             }
 
             Ok(())
@@ -627,15 +1830,95 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             get_current_ethiopian_date,
             get_ethiopian_calendar_month,
+            get_ethiopian_calendar_year,
+            get_ethiopian_calendar_week,
+            get_today_summary,
+            get_ethiopian_time,
+            get_pagume_info,
+            get_ui_strings,
+            get_events_for_month,
+            add_event,
+            update_event,
+            delete_event,
+            list_birthdays,
+            add_birthday,
+            update_birthday,
+            delete_birthday,
+            list_paydays,
+            add_payday,
+            update_payday,
+            delete_payday,
+            date_diff,
+            working_days_between,
+            add_working_days,
+            calculate_age,
+            duration_between,
+            compare_dates,
+            next_occurrence,
+            get_upcoming_holidays,
+            get_synaxarium_entry,
+            get_synaxarium_month,
+            get_script_filter,
+            find_holiday,
+            query_date,
+            get_academic_periods,
+            get_current_academic_periods,
+            get_holidays_between,
             convert_gregorian_to_ethiopian,
+            convert_gregorian_batch,
+            convert_ethiopian_batch,
+            clear_caches,
+            get_movable_feasts,
+            resolve_month,
+            normalize_date_input,
+            complete_partial_date,
+            export_conversion_table,
+            export_calendar,
+            export_pdf,
+            render_month_image,
+            export_ics,
+            import_ics,
+            set_holiday_feed,
+            set_caldav_account,
+            remove_caldav_account,
+            sync_caldav,
+            connect_google_calendar,
+            disconnect_google_calendar,
+            set_google_calendar_options,
+            sync_google_calendar,
+            get_google_events_for_month,
             position_calendar_window,
             resize_calendar_window,
             set_tray_text,
             set_tray_icon,
             load_settings,
             save_settings,
+            get_autostart_status,
+            set_autostart,
+            export_backup,
+            import_backup,
+            set_sync_directory,
+            sync_folder,
+            check_for_updates,
+            get_recent_logs,
+            open_log_file,
             copy_to_clipboard,
-            refresh_tray_display
+            copy_date_with_template,
+            copy_today,
+            get_copy_formats,
+            get_spoken_date,
+            copy_date_as,
+            copy_letter_date_stamp,
+            refresh_tray_display,
+            set_countdown,
+            set_panel_appearance,
+            set_desktop_widget,
+            set_system_calendar_events,
+            get_system_events_for_month,
+            sync_contacts_birthdays,
+            list_packs,
+            reload_packs,
+            reload_custom_holidays
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -0,0 +1,92 @@
+//! Diagnostics log, written with `tracing` to `logs/` in the app data directory.
+//!
+//! A new file is started every day, e.g. `zemenbar.2025-01-25.log` (named by the
+//! Gregorian date), and only the last week's files are kept. The same lines go to
+//! stderr when the app runs from a terminal. `ZEMENBAR_LOG` sets the level with the
+//! usual `tracing` filter syntax, e.g. `ZEMENBAR_LOG=debug`; it is `info` otherwise.
+
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use tracing_subscriber::prelude::*;
+use tracing_subscriber::EnvFilter;
+
+const FILE_PREFIX: &str = "zemenbar";
+const FILE_SUFFIX: &str = "log";
+
+/// Daily files kept before the oldest is deleted.
+const MAX_LOG_FILES: usize = 7;
+
+/// Lines `get_recent_logs` returns when not asked for a number.
+pub const DEFAULT_RECENT_LINES: usize = 200;
+
+/// Sends `tracing` output to the daily files in `dir` and to stderr. Called once, at startup.
+pub fn init(dir: &Path) -> Result<(), String> {
+    let appender = tracing_appender::rolling::Builder::new()
+        .rotation(tracing_appender::rolling::Rotation::DAILY)
+        .filename_prefix(FILE_PREFIX)
+        .filename_suffix(FILE_SUFFIX)
+        .max_log_files(MAX_LOG_FILES)
+        .build(dir)
+        .map_err(|e| format!("Failed to create log file: {}", e))?;
+    let filter = EnvFilter::try_from_env("ZEMENBAR_LOG").unwrap_or_else(|_| EnvFilter::new("info"));
+
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer().with_writer(appender).with_ansi(false))
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .try_init()
+        .map_err(|e| format!("Failed to set up logging: {}", e))
+}
+
+/// The log files in `dir`, oldest first.
+fn log_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("Failed to read log directory: {}", e))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with(FILE_PREFIX) && name.ends_with(FILE_SUFFIX))
+        })
+        .collect();
+    // The date in the name sorts the same way as the days.
+    files.sort();
+    Ok(files)
+}
+
+/// Today's log file, or the latest one there is.
+pub fn latest_log_file(dir: &Path) -> Result<PathBuf, String> {
+    log_files(dir)?.pop().ok_or_else(|| "No log file has been written yet".to_string())
+}
+
+/// The last `count` lines logged, oldest first, reading back into earlier days as needed.
+pub fn recent_lines(dir: &Path, count: usize) -> Result<Vec<String>, String> {
+    let mut lines: Vec<String> = Vec::new();
+    for path in log_files(dir)?.iter().rev() {
+        if lines.len() >= count {
+            break;
+        }
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read log file: {}", e))?;
+        let wanted = count - lines.len();
+        let file_lines: Vec<&str> = content.lines().collect();
+        let start = file_lines.len().saturating_sub(wanted);
+        lines.splice(0..0, file_lines[start..].iter().map(|line| line.to_string()));
+    }
+    Ok(lines)
+}
+
+/// Logs the error of a result that is otherwise ignored, such as a window that failed to show.
+pub trait LogError {
+    fn log_error(self, what: &str);
+}
+
+impl<T, E: Display> LogError for Result<T, E> {
+    fn log_error(self, what: &str) {
+        if let Err(e) = self {
+            tracing::warn!("{}: {}", what, e);
+        }
+    }
+}
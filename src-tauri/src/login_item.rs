@@ -0,0 +1,56 @@
+//! Starting ZemenBar at login, through either of the mechanisms macOS offers.
+//!
+//! `LaunchAgent` writes a plist to `~/Library/LaunchAgents` through the autostart plugin.
+//! `AppService` registers the app with `SMAppService` (macOS 13 and later), listed under
+//! Login Items in System Settings. Managed Macs may block one or the other, so the
+//! mechanism is a setting; switching it removes the login item the other one added.
+
+use objc2_service_management::{SMAppService, SMAppServiceStatus};
+use serde::{Deserialize, Serialize};
+use tauri_plugin_autostart::ManagerExt;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoginItemMechanism {
+    /// A LaunchAgent plist, written by the autostart plugin.
+    #[default]
+    LaunchAgent,
+    /// The app's `SMAppService` login item.
+    AppService,
+}
+
+/// Whether a login item is registered with `mechanism`, including an `AppService` one
+/// still waiting for the user's approval in System Settings.
+pub fn is_enabled(app: &tauri::AppHandle, mechanism: LoginItemMechanism) -> Result<bool, String> {
+    match mechanism {
+        LoginItemMechanism::LaunchAgent => app
+            .autolaunch()
+            .is_enabled()
+            .map_err(|e| format!("Failed to read launch at login: {}", e)),
+        LoginItemMechanism::AppService => {
+            let status = unsafe { SMAppService::mainAppService().status() };
+            Ok(status == SMAppServiceStatus::Enabled || status == SMAppServiceStatus::RequiresApproval)
+        }
+    }
+}
+
+/// Registers or removes the login item of `mechanism`.
+pub fn set_enabled(app: &tauri::AppHandle, mechanism: LoginItemMechanism, enabled: bool) -> Result<(), String> {
+    let result = match mechanism {
+        LoginItemMechanism::LaunchAgent => {
+            let autolaunch = app.autolaunch();
+            let result = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+            result.map_err(|e| e.to_string())
+        }
+        LoginItemMechanism::AppService => {
+            let service = unsafe { SMAppService::mainAppService() };
+            let result = if enabled {
+                unsafe { service.registerAndReturnError() }
+            } else {
+                unsafe { service.unregisterAndReturnError() }
+            };
+            result.map_err(|e| e.to_string())
+        }
+    };
+    result.map_err(|e| format!("Failed to update launch at login: {}", e))
+}
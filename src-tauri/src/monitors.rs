@@ -0,0 +1,53 @@
+//! Keeps the calendar panel on screen when displays are plugged in, unplugged or rearranged.
+//!
+//! Tauri has no event for a change of display configuration, so a background thread
+//! compares the monitor layout every couple of seconds. When it changes while the
+//! panel is open, the panel is positioned again, which moves it back onto a monitor
+//! that is still there.
+
+use crate::logging::LogError;
+use std::time::Duration;
+use tauri::Manager;
+
+const POLL: Duration = Duration::from_secs(2);
+
+/// Position, size and scale factor of every monitor.
+type Layout = Vec<(i32, i32, u32, u32, u64)>;
+
+fn layout(app: &tauri::AppHandle) -> Option<Layout> {
+    let monitors = app.available_monitors().ok()?;
+    Some(
+        monitors
+            .iter()
+            .map(|monitor| {
+                let (position, size) = (monitor.position(), monitor.size());
+                (position.x, position.y, size.width, size.height, monitor.scale_factor().to_bits())
+            })
+            .collect(),
+    )
+}
+
+/// Positions the panel again if it is open.
+pub fn reposition_panel(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        if window.is_visible().unwrap_or(false) {
+            crate::position_calendar_window(app.clone(), None).log_error("Failed to position calendar window");
+        }
+    }
+}
+
+/// Starts the thread that watches the monitor layout.
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut last = layout(&app);
+        loop {
+            std::thread::sleep(POLL);
+            let current = layout(&app);
+            if current.is_some() && current != last {
+                tracing::info!("Display configuration changed");
+                last = current;
+                reposition_panel(&app);
+            }
+        }
+    });
+}
@@ -0,0 +1,184 @@
+//! A month grid drawn to a PNG, for sharing in chats or using as a wallpaper.
+//!
+//! Days are written in Geez numerals under Amharic weekday names, with the Gregorian
+//! day in small type below; today is circled and holidays are drawn in the accent
+//! colour with a dot. Text uses the same installed Ethiopic font as the PDF export.
+
+use crate::locale::Language;
+use crate::{pdf, CalendarDay, CalendarMonth};
+use ab_glyph::{point, Font, FontVec, PxScale, ScaleFont};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use tiny_skia::{Color, FillRule, Paint, PathBuilder, Pixmap, PremultipliedColorU8, Rect, Transform};
+
+const WIDTH: u32 = 1400;
+const HEIGHT: u32 = 1100;
+const MARGIN: f32 = 70.0;
+const HEADER_HEIGHT: f32 = 230.0;
+
+/// Colour scheme of the image.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImageTheme {
+    #[default]
+    Light,
+    Dark,
+}
+
+struct Palette {
+    background: [u8; 3],
+    text: [u8; 3],
+    muted: [u8; 3],
+    accent: [u8; 3],
+    today_text: [u8; 3],
+}
+
+impl ImageTheme {
+    fn palette(self) -> Palette {
+        match self {
+            ImageTheme::Light => Palette {
+                background: [250, 249, 246],
+                text: [28, 28, 30],
+                muted: [130, 130, 135],
+                accent: [200, 40, 40],
+                today_text: [255, 255, 255],
+            },
+            ImageTheme::Dark => Palette {
+                background: [28, 28, 30],
+                text: [235, 235, 240],
+                muted: [140, 140, 148],
+                accent: [255, 105, 97],
+                today_text: [28, 28, 30],
+            },
+        }
+    }
+}
+
+fn paint([r, g, b]: [u8; 3]) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(r, g, b, 255);
+    paint.anti_alias = true;
+    paint
+}
+
+struct Canvas {
+    pixmap: Pixmap,
+    font: FontVec,
+}
+
+impl Canvas {
+    fn text_width(&self, text: &str, size: f32) -> f32 {
+        let scaled = self.font.as_scaled(PxScale::from(size));
+        text.chars().map(|c| scaled.h_advance(self.font.glyph_id(c))).sum()
+    }
+
+    /// Draws `text` with its baseline at `y`, starting at `x`.
+    fn text(&mut self, text: &str, size: f32, x: f32, y: f32, [r, g, b]: [u8; 3]) {
+        let scale = PxScale::from(size);
+        let advance_font = self.font.as_scaled(scale);
+        let width = self.pixmap.width() as i32;
+        let height = self.pixmap.height() as i32;
+        let mut caret = x;
+        for c in text.chars() {
+            let id = self.font.glyph_id(c);
+            let glyph = id.with_scale_and_position(scale, point(caret, y));
+            caret += advance_font.h_advance(id);
+            let Some(outlined) = self.font.outline_glyph(glyph) else {
+                continue;
+            };
+            let bounds = outlined.px_bounds();
+            let pixels = self.pixmap.pixels_mut();
+            outlined.draw(|gx, gy, coverage| {
+                let px = bounds.min.x as i32 + gx as i32;
+                let py = bounds.min.y as i32 + gy as i32;
+                if px < 0 || py < 0 || px >= width || py >= height {
+                    return;
+                }
+                // The background is opaque, so blending the straight colours is enough.
+                let pixel = &mut pixels[(py * width + px) as usize];
+                let coverage = coverage.min(1.0);
+                let blend = |under: u8, over: u8| (under as f32 + (over as f32 - under as f32) * coverage) as u8;
+                let (red, green, blue) = (blend(pixel.red(), r), blend(pixel.green(), g), blend(pixel.blue(), b));
+                if let Some(blended) = PremultipliedColorU8::from_rgba(red, green, blue, 255) {
+                    *pixel = blended;
+                }
+            });
+        }
+    }
+
+    fn centered_text(&mut self, text: &str, size: f32, center_x: f32, y: f32, color: [u8; 3]) {
+        let width = self.text_width(text, size);
+        self.text(text, size, center_x - width / 2.0, y, color);
+    }
+
+    fn circle(&mut self, x: f32, y: f32, radius: f32, color: [u8; 3]) {
+        if let Some(path) = PathBuilder::from_circle(x, y, radius) {
+            self.pixmap.fill_path(&path, &paint(color), FillRule::Winding, Transform::identity(), None);
+        }
+    }
+}
+
+fn draw_day(canvas: &mut Canvas, palette: &Palette, day: &CalendarDay, x: f32, y: f32, cell: (f32, f32)) {
+    let (cell_width, cell_height) = cell;
+    let center_x = x + cell_width / 2.0;
+    let number_y = y + cell_height * 0.5;
+
+    let mut color = if day.holidays.is_empty() { palette.text } else { palette.accent };
+    if day.is_today {
+        canvas.circle(center_x, number_y - 18.0, 42.0, palette.accent);
+        color = palette.today_text;
+    }
+    canvas.centered_text(&day.day_geez, 48.0, center_x, number_y, color);
+    canvas.centered_text(&day.gregorian_day.to_string(), 22.0, center_x, y + cell_height * 0.82, palette.muted);
+    if !day.holidays.is_empty() && !day.is_today {
+        canvas.circle(center_x, y + cell_height * 0.93, 5.0, palette.accent);
+    }
+}
+
+/// Draws the month grid and writes it to `path` as a PNG.
+pub fn render_month(
+    path: &Path,
+    year: usize,
+    month: usize,
+    week_start: usize,
+    theme: ImageTheme,
+) -> Result<(), String> {
+    if year == 0 || !(1..=13).contains(&month) {
+        return Err(format!("Invalid month: {}-{}", year, month));
+    }
+    let font_path = pdf::ethiopic_font_path()?;
+    let bytes = std::fs::read(font_path).map_err(|e| format!("Failed to open font {}: {}", font_path, e))?;
+    let font =
+        FontVec::try_from_vec_and_index(bytes, 0).map_err(|e| format!("Failed to load font {}: {}", font_path, e))?;
+    let mut pixmap = Pixmap::new(WIDTH, HEIGHT).ok_or_else(|| "Failed to create image".to_string())?;
+    let palette = theme.palette();
+    let [r, g, b] = palette.background;
+    pixmap.fill(Color::from_rgba8(r, g, b, 255));
+    let mut canvas = Canvas { pixmap, font };
+
+    let calendar = CalendarMonth::localized(year, month, week_start, Language::Amharic);
+    let title = format!("{} {}", calendar.month_name_amharic, calendar.year_geez);
+    canvas.text(&title, 72.0, MARGIN, MARGIN + 60.0, palette.text);
+    let subtitle = format!("{} {} E.C.", calendar.month_name_english, calendar.year);
+    canvas.text(&subtitle, 30.0, MARGIN, MARGIN + 110.0, palette.muted);
+
+    let cell_width = (WIDTH as f32 - 2.0 * MARGIN) / 7.0;
+    let rows = (calendar.first_day_weekday + calendar.days.len()).div_ceil(7);
+    let cell_height = (HEIGHT as f32 - HEADER_HEIGHT - MARGIN) / rows.max(5) as f32;
+    for (column, name) in calendar.weekday_names.iter().enumerate() {
+        let center_x = MARGIN + (column as f32 + 0.5) * cell_width;
+        canvas.centered_text(name, 26.0, center_x, HEADER_HEIGHT - 20.0, palette.muted);
+    }
+    if let Some(line) = Rect::from_xywh(MARGIN, HEADER_HEIGHT - 5.0, WIDTH as f32 - 2.0 * MARGIN, 2.0) {
+        canvas.pixmap.fill_rect(line, &paint(palette.muted), Transform::identity(), None);
+    }
+
+    for (index, day) in calendar.days.iter().enumerate() {
+        let cell = calendar.first_day_weekday + index;
+        let x = MARGIN + (cell % 7) as f32 * cell_width;
+        let y = HEADER_HEIGHT + (cell / 7) as f32 * cell_height;
+        draw_day(&mut canvas, &palette, day, x, y, (cell_width, cell_height));
+    }
+
+    canvas.pixmap.save_png(path).map_err(|e| format!("Failed to write image: {}", e))
+}
@@ -0,0 +1,215 @@
+//! Printable A4 calendars: one month per page, or the whole year on one page.
+//!
+//! Days are written in Geez numerals with Amharic month and weekday names, and the
+//! Gregorian day in the corner of each cell. PDF's built-in fonts have no Ethiopic
+//! letters, so an installed Ethiopic font is embedded; macOS ships Kefa.
+
+use crate::locale::Language;
+use crate::{holidays, CalendarDay, CalendarMonth, EthiopianDate};
+use chrono::NaiveDate;
+use printpdf::{
+    Color, IndirectFontRef, Mm, PaintMode, PdfDocument, PdfDocumentReference, PdfLayerReference, Rect, Rgb,
+};
+use std::io::BufWriter;
+use std::path::Path;
+
+/// Fonts tried in order; the first one that exists is embedded.
+const ETHIOPIC_FONTS: [&str; 5] = [
+    "/System/Library/Fonts/Supplemental/Kefa.ttc",
+    "/System/Library/Fonts/Kefa.ttc",
+    "/Library/Fonts/NotoSansEthiopic-Regular.ttf",
+    "/Library/Fonts/AbyssinicaSIL-Regular.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSansEthiopic-Regular.ttf",
+];
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 15.0;
+
+/// Holiday names printed in a month cell; the rest are in the list under the grid.
+const HOLIDAYS_PER_CELL: usize = 2;
+
+/// Characters of a holiday name that fit on one line of a month cell.
+const CELL_NAME_CHARS: usize = 16;
+
+fn rgb(r: f32, g: f32, b: f32) -> Color {
+    Color::Rgb(Rgb::new(r, g, b, None))
+}
+
+fn text_color() -> Color {
+    rgb(0.1, 0.1, 0.1)
+}
+
+fn muted_color() -> Color {
+    rgb(0.45, 0.45, 0.45)
+}
+
+fn holiday_color() -> Color {
+    rgb(0.75, 0.1, 0.1)
+}
+
+/// The first installed font of `ETHIOPIC_FONTS`.
+pub(crate) fn ethiopic_font_path() -> Result<&'static str, String> {
+    ETHIOPIC_FONTS
+        .iter()
+        .copied()
+        .find(|path| Path::new(path).exists())
+        .ok_or_else(|| "No Ethiopic font found; install Noto Sans Ethiopic".to_string())
+}
+
+fn load_font(doc: &PdfDocumentReference) -> Result<IndirectFontRef, String> {
+    let path = ethiopic_font_path()?;
+    let file = std::fs::File::open(path).map_err(|e| format!("Failed to open font {}: {}", path, e))?;
+    doc.add_external_font(file).map_err(|e| format!("Failed to load font {}: {}", path, e))
+}
+
+fn rect(layer: &PdfLayerReference, x: f32, y: f32, width: f32, height: f32, mode: PaintMode) {
+    layer.add_rect(Rect::new(Mm(x), Mm(y), Mm(x + width), Mm(y + height)).with_mode(mode));
+}
+
+fn text(layer: &PdfLayerReference, font: &IndirectFontRef, value: &str, size: f32, x: f32, y: f32, color: Color) {
+    layer.set_fill_color(color);
+    layer.use_text(value, size, Mm(x), Mm(y), font);
+}
+
+/// Shortens `name` to `chars` characters, marking the cut with an ellipsis.
+fn truncate(name: &str, chars: usize) -> String {
+    if name.chars().count() <= chars {
+        name.to_string()
+    } else {
+        format!("{}…", name.chars().take(chars - 1).collect::<String>())
+    }
+}
+
+/// "ጥር ፳፻፲፯ ዓ.ም. · Tir 2017" and "January 9 – February 7, 2025" for the page heading.
+fn month_titles(month: &CalendarMonth) -> (String, String) {
+    let title = format!(
+        "{} {} ዓ.ም. · {} {}",
+        month.month_name_amharic, month.year_geez, month.month_name_english, month.year
+    );
+    let gregorian =
+        |day: &CalendarDay| NaiveDate::from_ymd_opt(day.gregorian_year, day.gregorian_month, day.gregorian_day);
+    let subtitle = match (month.days.first().and_then(gregorian), month.days.last().and_then(gregorian)) {
+        (Some(start), Some(end)) => format!("{} – {}", start.format("%B %-d"), end.format("%B %-d, %Y")),
+        _ => String::new(),
+    };
+    (title, subtitle)
+}
+
+fn draw_month_page(layer: &PdfLayerReference, font: &IndirectFontRef, month: &CalendarMonth) {
+    let (title, subtitle) = month_titles(month);
+    let mut y = PAGE_HEIGHT - MARGIN - 8.0;
+    text(layer, font, &title, 20.0, MARGIN, y, text_color());
+    y -= 7.0;
+    text(layer, font, &subtitle, 10.0, MARGIN, y, muted_color());
+
+    let grid_width = PAGE_WIDTH - 2.0 * MARGIN;
+    let cell_width = grid_width / 7.0;
+    let cell_height = 28.0;
+    y -= 10.0;
+    for (column, name) in month.weekday_names.iter().enumerate() {
+        text(layer, font, name, 9.0, MARGIN + column as f32 * cell_width + 2.0, y, muted_color());
+    }
+    y -= 3.0;
+
+    layer.set_outline_color(rgb(0.75, 0.75, 0.75));
+    layer.set_outline_thickness(0.4);
+    let grid_top = y;
+    for (index, day) in month.days.iter().enumerate() {
+        let cell = month.first_day_weekday + index;
+        let x = MARGIN + (cell % 7) as f32 * cell_width;
+        let top = grid_top - (cell / 7) as f32 * cell_height;
+        let bottom = top - cell_height;
+
+        if !day.holidays.is_empty() {
+            layer.set_fill_color(rgb(0.99, 0.93, 0.93));
+            rect(layer, x, bottom, cell_width, cell_height, PaintMode::Fill);
+        }
+        rect(layer, x, bottom, cell_width, cell_height, PaintMode::Stroke);
+
+        let color = if day.holidays.iter().any(|h| h.is_public) { holiday_color() } else { text_color() };
+        text(layer, font, &day.day_geez, 14.0, x + 2.0, top - 7.0, color);
+        text(layer, font, &day.gregorian_day.to_string(), 7.0, x + cell_width - 6.0, top - 5.0, muted_color());
+        for (line, holiday) in day.holidays.iter().take(HOLIDAYS_PER_CELL).enumerate() {
+            let name = truncate(&holiday.name_amharic, CELL_NAME_CHARS);
+            text(layer, font, &name, 6.0, x + 2.0, top - 14.0 - line as f32 * 3.5, holiday_color());
+        }
+    }
+
+    let rows = (month.first_day_weekday + month.days.len()).div_ceil(7);
+    y = grid_top - rows as f32 * cell_height - 10.0;
+    let month_holidays: Vec<&holidays::Holiday> = month.days.iter().flat_map(|day| &day.holidays).collect();
+    for holiday in month_holidays {
+        if y < MARGIN {
+            break;
+        }
+        let gregorian = holiday.date.to_gregorian().map(|g| g.format("%B %-d").to_string()).unwrap_or_default();
+        let names = format!("{} · {}", holiday.name_amharic, holiday.name_english);
+        let line = format!("{}  {} ({})", holiday.date.day_geez(), names, gregorian);
+        let color = if holiday.is_public { holiday_color() } else { text_color() };
+        text(layer, font, &line, 9.0, MARGIN, y, color);
+        y -= 5.0;
+    }
+}
+
+fn draw_year_page(layer: &PdfLayerReference, font: &IndirectFontRef, year: usize, months: &[CalendarMonth]) {
+    let title = format!("{} ዓ.ም. · {} E.C.", EthiopianDate::to_geez_number(year), year);
+    text(layer, font, &title, 20.0, MARGIN, PAGE_HEIGHT - MARGIN - 8.0, text_color());
+
+    let columns = 3;
+    let block_width = (PAGE_WIDTH - 2.0 * MARGIN) / columns as f32;
+    let block_height = 50.0;
+    let cell_width = (block_width - 4.0) / 7.0;
+    let cell_height = 6.0;
+    let top = PAGE_HEIGHT - MARGIN - 18.0;
+
+    for (index, month) in months.iter().enumerate() {
+        let x = MARGIN + (index % columns) as f32 * block_width;
+        let y = top - (index / columns) as f32 * block_height;
+        text(layer, font, &month.month_name_amharic, 11.0, x, y, text_color());
+        for (column, name) in month.weekday_names.iter().enumerate() {
+            let short: String = name.chars().take(1).collect();
+            text(layer, font, &short, 6.0, x + column as f32 * cell_width, y - 6.0, muted_color());
+        }
+        for (day_index, day) in month.days.iter().enumerate() {
+            let cell = month.first_day_weekday + day_index;
+            let color = if day.holidays.is_empty() { text_color() } else { holiday_color() };
+            let cell_x = x + (cell % 7) as f32 * cell_width;
+            let cell_y = y - 12.0 - (cell / 7) as f32 * cell_height;
+            text(layer, font, &day.day_geez, 7.0, cell_x, cell_y, color);
+        }
+    }
+}
+
+/// Writes an A4 PDF of an Ethiopian month, or of the whole year on one page when
+/// `month` is `None`, with weeks starting on `week_start` (0 = Sunday).
+pub fn write_pdf(path: &Path, year: usize, month: Option<usize>, week_start: usize) -> Result<(), String> {
+    if year == 0 {
+        return Err("Invalid year: 0".to_string());
+    }
+    if let Some(month) = month.filter(|month| !(1..=13).contains(month)) {
+        return Err(format!("Invalid month: {}", month));
+    }
+
+    let title = match month {
+        Some(month) => format!("ZemenBar {}-{:02}", year, month),
+        None => format!("ZemenBar {}", year),
+    };
+    let (doc, page, layer) = PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Calendar");
+    let font = load_font(&doc)?;
+    let layer = doc.get_page(page).get_layer(layer);
+
+    match month {
+        Some(month) => {
+            draw_month_page(&layer, &font, &CalendarMonth::localized(year, month, week_start, Language::Amharic))
+        }
+        None => {
+            let months: Vec<CalendarMonth> =
+                (1..=13).map(|month| CalendarMonth::localized(year, month, week_start, Language::Amharic)).collect();
+            draw_year_page(&layer, &font, year, &months);
+        }
+    }
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create PDF file: {}", e))?;
+    doc.save(&mut BufWriter::new(file)).map_err(|e| format!("Failed to write PDF file: {}", e))
+}
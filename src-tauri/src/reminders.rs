@@ -0,0 +1,325 @@
+//! Which events, birthdays, paydays and holidays to announce with a desktop notification, and which were already announced.
+//!
+//! The scheduler asks for the reminders due on each tick and shows only the ones not
+//! yet fired. A reminder is due from its time (the event start minus its lead time)
+//! until the end of the event's day; holidays and birthdays are due from midnight, and
+//! paydays from midnight of the day their lead time asks for. Holiday countdowns are
+//! due from midnight of each day the user asked to hear about a holiday ahead of it.
+//! While quiet hours are on, the scheduler holds back, so the reminders queue up until
+//! quiet hours end. Event reminders that came due after quiet hours began the evening
+//! before stay due through the next day, so the ones held back past midnight still go
+//! out in the morning.
+//! Fired reminders are remembered in `reminders.json` so that restarting the app does
+//! not repeat them.
+
+use crate::birthdays::{self, BirthdayKind};
+use crate::{display, events, holidays, paydays, strings, AppSettings, EthiopianDate};
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reminder {
+    /// Identifies the reminder for de-duplication, e.g. `"holiday:meskel:2017-01-17"`.
+    pub key: String,
+    /// Day of the event or holiday the reminder is about.
+    pub date: EthiopianDate,
+    pub title: String,
+    pub body: String,
+}
+
+/// Holidays can be announced at most this many days ahead.
+pub const MAX_COUNTDOWN_DAYS: u32 = 60;
+
+/// Days ahead of a holiday to announce it, e.g. 7 and 1 days before Genna.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HolidayCountdown {
+    /// Holiday id, e.g. `"genna"`.
+    pub holiday: String,
+    pub days_before: Vec<u32>,
+}
+
+/// Checks that each countdown asks for 1 to `MAX_COUNTDOWN_DAYS` days.
+///
+/// The holiday id is not checked: a custom holiday can be deleted or renamed after its
+/// countdown was set, and `due_reminders` skips countdowns of holidays that do not exist.
+pub fn validate_countdowns(countdowns: &[HolidayCountdown]) -> Result<(), String> {
+    for countdown in countdowns {
+        if let Some(days) = countdown.days_before.iter().find(|days| !(1..=MAX_COUNTDOWN_DAYS).contains(*days)) {
+            return Err(format!(
+                "Countdown days for {} must be between 1 and {}, got {}",
+                countdown.holiday, MAX_COUNTDOWN_DAYS, days
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Daily window, in the "today" timezone, in which no notifications are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuietHours {
+    pub start: NaiveTime,
+    /// May be earlier than `start` for a window spanning midnight, e.g. 22:00 to 07:00.
+    pub end: NaiveTime,
+}
+
+impl Default for QuietHours {
+    fn default() -> Self {
+        Self {
+            start: NaiveTime::from_hms_opt(22, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(7, 0, 0).unwrap(),
+        }
+    }
+}
+
+impl QuietHours {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        if self.start <= self.end {
+            self.start <= time && time < self.end
+        } else {
+            time >= self.start || time < self.end
+        }
+    }
+
+    /// For a window spanning midnight, when the one that began the evening before `now`'s
+    /// day started; reminders due from then on were held back past the end of their day.
+    pub fn held_since(&self, now: NaiveDateTime) -> Option<NaiveDateTime> {
+        (self.start > self.end).then(|| (now.date() - Duration::days(1)).and_time(self.start))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FiredReminder {
+    key: String,
+    #[serde(with = "crate::date_serde::compact")]
+    date: EthiopianDate,
+}
+
+fn reminder_body(date: &EthiopianDate, time: Option<NaiveTime>, settings: &AppSettings) -> String {
+    let text = date.format_with(&display::display_template(settings), display::format_options(settings));
+    match time {
+        Some(time) => format!("{} {}", text, time.format("%H:%M")),
+        None => text,
+    }
+}
+
+/// Lead time of the reminder to show at `now` for an occurrence starting at `start`: the
+/// one closest to the start among those due, or `None` when none is. With `since`, only
+/// a reminder that came due at or after it counts.
+fn due_lead_time(
+    start: NaiveDateTime,
+    reminder_minutes: &[u32],
+    now: NaiveDateTime,
+    since: Option<NaiveDateTime>,
+) -> Option<u32> {
+    let lead_times = if reminder_minutes.is_empty() { &[0][..] } else { reminder_minutes };
+    let minutes = lead_times
+        .iter()
+        .copied()
+        .filter(|minutes| start - Duration::minutes(*minutes as i64) <= now)
+        .min()?;
+    let due = start - Duration::minutes(minutes as i64);
+    since.is_none_or(|since| due >= since).then_some(minutes)
+}
+
+/// Reminders due at `now`, the wall-clock time in the "today" timezone.
+///
+/// When several lead times of one occurrence are due at once (the app was not running,
+/// or quiet hours held them back) only the one closest to the start is returned.
+/// Yesterday's events are included when their reminder came due during quiet hours.
+pub fn due_reminders(now: NaiveDateTime, settings: &AppSettings) -> Vec<Reminder> {
+    let Some(today) = EthiopianDate::from_naive_date(now.date()) else {
+        return Vec::new();
+    };
+    let mut reminders = Vec::new();
+
+    if settings.notify_events {
+        let held_since = settings.quiet_hours.and_then(|quiet| quiet.held_since(now));
+        let first_offset = if held_since.is_some() { -1 } else { 0 };
+        let lookahead_days = (events::MAX_REMINDER_MINUTES / (24 * 60)) as i64;
+        for offset in first_offset..=lookahead_days {
+            let Some(date) = today.add_days(offset) else {
+                continue;
+            };
+            let Some(gregorian) = date.to_gregorian() else {
+                continue;
+            };
+            let since = if offset < 0 { held_since } else { None };
+            for event in events::events_on(&date) {
+                let start = gregorian.and_time(event.time.unwrap_or(NaiveTime::MIN));
+                if let Some(minutes) = due_lead_time(start, &event.reminder_minutes, now, since) {
+                    reminders.push(Reminder {
+                        key: format!("event:{}:{}:{}", event.id, date, minutes),
+                        date: date.clone(),
+                        title: event.title,
+                        body: reminder_body(&date, event.time, settings),
+                    });
+                }
+            }
+        }
+    }
+
+    if settings.notify_birthdays {
+        reminders.extend(birthdays::celebrations_on(&today).into_iter().map(|celebration| {
+            let title_key = match celebration.kind {
+                BirthdayKind::Birthday => "birthday",
+                BirthdayKind::Anniversary => "anniversary",
+            };
+            Reminder {
+                key: format!("birthday:{}:{}", celebration.id, today),
+                date: today.clone(),
+                title: strings::label(settings.language, title_key),
+                body: celebration.message(settings.language, settings.use_geez_numbers),
+            }
+        }));
+    }
+
+    if settings.notify_paydays {
+        for offset in 0..=paydays::MAX_REMIND_DAYS_BEFORE as i64 {
+            let Some(date) = today.add_days(offset) else {
+                continue;
+            };
+            for payday in paydays::paydays_on(&date) {
+                if (payday.remind_days_before as i64) < offset {
+                    continue;
+                }
+                reminders.push(Reminder {
+                    key: format!("payday:{}:{}", payday.id, date),
+                    date: date.clone(),
+                    title: payday.title,
+                    body: reminder_body(&date, None, settings),
+                });
+            }
+        }
+    }
+
+    if settings.notify_holidays {
+        reminders.extend(
+            holidays::holidays_for_month(today.year, today.month)
+                .iter()
+                .filter(|holiday| holiday.date == today && settings.holiday_categories.includes(holiday.category))
+                .filter(|holiday| holiday.is_public || !settings.notify_public_holidays_only)
+                .map(|holiday| Reminder {
+                    key: format!("holiday:{}:{}", holiday.id, today),
+                    date: today.clone(),
+                    title: strings::holiday_name(holiday, settings.language),
+                    body: reminder_body(&today, None, settings),
+                }),
+        );
+    }
+
+    // A countdown whose holiday no longer exists finds no holiday and announces nothing
+    for countdown in &settings.holiday_countdowns {
+        for &days in &countdown.days_before {
+            let Some(date) = today.add_days(days as i64) else {
+                continue;
+            };
+            let holiday = holidays::holidays_for_month(date.year, date.month)
+                .into_iter()
+                .find(|holiday| holiday.id == countdown.holiday && holiday.date == date);
+            if let Some(holiday) = holiday {
+                reminders.push(Reminder {
+                    key: format!("countdown:{}:{}:{}", holiday.id, date, days),
+                    date: date.clone(),
+                    title: strings::holiday_name(&holiday, settings.language),
+                    body: format!(
+                        "{} · {}",
+                        display::days_left_text(days as usize, settings),
+                        reminder_body(&date, None, settings)
+                    ),
+                });
+            }
+        }
+    }
+
+    reminders
+}
+
+/// Keeps the reminders not fired yet and records them as fired.
+///
+/// Records for days before yesterday are dropped, since those reminders can no longer be
+/// due; yesterday's are kept for the reminders quiet hours held back overnight.
+pub fn take_unfired(state_path: &Path, today: &EthiopianDate, reminders: Vec<Reminder>) -> Result<Vec<Reminder>, String> {
+    let mut fired: Vec<FiredReminder> = std::fs::read_to_string(state_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default();
+    let count = fired.len();
+    let yesterday = today.add_days(-1).unwrap_or_else(|| today.clone());
+    fired.retain(|reminder| reminder.date >= yesterday);
+    let pruned = fired.len() != count;
+
+    let unfired: Vec<Reminder> = reminders
+        .into_iter()
+        .filter(|reminder| !fired.iter().any(|fired| fired.key == reminder.key))
+        .collect();
+    if unfired.is_empty() && !pruned {
+        return Ok(unfired);
+    }
+
+    fired.extend(unfired.iter().map(|reminder| FiredReminder {
+        key: reminder.key.clone(),
+        date: reminder.date.clone(),
+    }));
+    if let Some(parent) = state_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create reminders directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&fired).map_err(|e| format!("Failed to serialize reminders: {}", e))?;
+    std::fs::write(state_path, content).map_err(|e| format!("Failed to write reminders file: {}", e))?;
+    Ok(unfired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2025, 1, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn overnight_window() {
+        let quiet = QuietHours::default();
+        assert!(!quiet.contains(at(28, 21, 59).time()));
+        assert!(quiet.contains(at(28, 22, 0).time()));
+        assert!(quiet.contains(at(29, 0, 0).time()));
+        assert!(quiet.contains(at(29, 6, 59).time()));
+        assert!(!quiet.contains(at(29, 7, 0).time()));
+        assert_eq!(quiet.held_since(at(29, 7, 0)), Some(at(28, 22, 0)));
+
+        let afternoon = QuietHours { start: at(1, 13, 0).time(), end: at(1, 14, 0).time() };
+        assert_eq!(afternoon.held_since(at(29, 14, 0)), None);
+    }
+
+    #[test]
+    fn reminders_held_overnight_go_out_in_the_morning() {
+        let morning = at(29, 7, 0);
+        let held_since = QuietHours::default().held_since(morning);
+        // Due at 23:00 and 22:30 the evening before, during quiet hours.
+        assert_eq!(due_lead_time(at(28, 23, 0), &[], morning, held_since), Some(0));
+        assert_eq!(due_lead_time(at(28, 23, 30), &[60], morning, held_since), Some(60));
+        // Due at 21:00, before quiet hours began, so already shown.
+        assert_eq!(due_lead_time(at(28, 21, 0), &[], morning, held_since), None);
+        assert_eq!(due_lead_time(at(28, 22, 30), &[60], morning, held_since), None);
+    }
+
+    #[test]
+    fn countdowns_are_checked_for_their_days_only() {
+        let countdown = |holiday: &str, days_before: Vec<u32>| HolidayCountdown {
+            holiday: holiday.to_string(),
+            days_before,
+        };
+        assert!(validate_countdowns(&[countdown("genna", vec![7, 1])]).is_ok());
+        assert!(validate_countdowns(&[countdown("deleted_custom_holiday", vec![MAX_COUNTDOWN_DAYS])]).is_ok());
+        assert!(validate_countdowns(&[countdown("genna", vec![0])]).is_err());
+        assert!(validate_countdowns(&[countdown("genna", vec![MAX_COUNTDOWN_DAYS + 1])]).is_err());
+    }
+
+    #[test]
+    fn closest_due_lead_time_wins() {
+        let start = at(29, 10, 0);
+        assert_eq!(due_lead_time(start, &[24 * 60, 60], at(29, 7, 0), None), Some(24 * 60));
+        assert_eq!(due_lead_time(start, &[24 * 60, 60], at(29, 9, 0), None), Some(60));
+        assert_eq!(due_lead_time(start, &[60], at(29, 8, 59), None), None);
+    }
+}
@@ -0,0 +1,132 @@
+//! Background scheduler that keeps the tray and open windows on the current date.
+//!
+//! Wakes at the start of every minute, which also moves the clock tray item on, and
+//! besides catching the day rolling over it compares wall clock time against
+//! monotonic time, so a system date that jumps (manual change, dead CMOS battery, NTP
+//! correction) is noticed on the next tick. It also watches the system's UTC offset, so
+//! a timezone change (travel, DST, a manual change) refreshes every surface instead of
+//! leaving day-dependent state computed for the old zone.
+//! Each tick also shows the notifications for events and holidays that are due,
+//! unless it falls in quiet hours.
+
+use crate::logging::LogError;
+use crate::{clock_tray, reminders, EthiopianDate};
+use chrono::{Datelike, Local, Offset, Timelike, Utc};
+use std::time::{Duration, Instant};
+use tauri::Emitter;
+
+const TICK: Duration = Duration::from_secs(60);
+
+/// Wall clock drift beyond elapsed monotonic time that counts as a jump.
+const CLOCK_JUMP_TOLERANCE_SECS: i64 = 120;
+
+/// Forward jumps shorter than this are most likely sleep/wake and are not logged.
+const CLOCK_JUMP_LOG_SECS: i64 = 2 * 24 * 3600;
+
+/// Gregorian years before this are treated as a broken system clock.
+const MIN_PLAUSIBLE_YEAR: i32 = 2024;
+
+/// Recomputes the tray text and menu and tells every window the date changed.
+pub fn refresh_all_surfaces(app: &tauri::AppHandle, today: &EthiopianDate) {
+    if let Err(e) = crate::refresh_tray_display(app.clone()) {
+        tracing::warn!("Failed to refresh tray display: {}", e);
+    }
+    let settings = crate::load_settings(app.clone()).unwrap_or_default();
+    if let Err(e) = crate::refresh_tray_menu(app, &settings) {
+        tracing::warn!("{}", e);
+    }
+    app.emit("date-changed", today).log_error("Failed to emit date-changed");
+}
+
+/// Shows a notification for every due reminder that has not been shown yet.
+///
+/// Nothing is shown during quiet hours; the reminders stay due, including those of
+/// events on a day that ended during quiet hours, and go out on the first tick after
+/// quiet hours end.
+fn fire_due_reminders(app: &tauri::AppHandle, today: &EthiopianDate) {
+    use tauri_plugin_notification::NotificationExt;
+
+    let settings = crate::load_settings(app.clone()).unwrap_or_default();
+    let now = Utc::now().with_timezone(&crate::today_timezone()).naive_local();
+    if settings.quiet_hours.is_some_and(|quiet| quiet.contains(now.time())) {
+        return;
+    }
+    let due = reminders::due_reminders(now, &settings);
+    let unfired = match crate::get_reminders_path(app).and_then(|path| reminders::take_unfired(&path, today, due)) {
+        Ok(unfired) => unfired,
+        Err(e) => {
+            tracing::warn!("Failed to check reminders: {}", e);
+            return;
+        }
+    };
+
+    for reminder in unfired {
+        if let Err(e) = app.notification().builder().title(&reminder.title).body(&reminder.body).show() {
+            tracing::warn!("Failed to show notification: {}", e);
+        }
+    }
+}
+
+/// Time left until the next minute starts on the wall clock; at least a second, so a
+/// tick that wakes a little early does not run twice.
+fn until_next_minute() -> Duration {
+    let now = Utc::now();
+    let into_minute = Duration::from_secs(now.second() as u64) + Duration::from_nanos(now.nanosecond() as u64);
+    TICK.saturating_sub(into_minute).max(Duration::from_secs(1))
+}
+
+/// Current offset of the system timezone from UTC, in seconds.
+fn system_utc_offset() -> i32 {
+    Local::now().offset().fix().local_minus_utc()
+}
+
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        let mut cached_today = EthiopianDate::today();
+        let mut last_wall = Utc::now();
+        let mut last_instant = Instant::now();
+        let mut last_offset = system_utc_offset();
+        let mut warned_implausible = false;
+        fire_due_reminders(&app, &cached_today);
+
+        loop {
+            std::thread::sleep(until_next_minute());
+
+            let wall = Utc::now();
+            let monotonic_secs = last_instant.elapsed().as_secs() as i64;
+            let drift = (wall - last_wall).num_seconds() - monotonic_secs;
+            last_wall = wall;
+            last_instant = Instant::now();
+
+            let clock_jumped = drift.abs() > CLOCK_JUMP_TOLERANCE_SECS;
+            if clock_jumped && !(0..=CLOCK_JUMP_LOG_SECS).contains(&drift) {
+                tracing::info!("System clock jumped by {}s; revalidating today's date", drift);
+            }
+            let offset = system_utc_offset();
+            let timezone_changed = offset != last_offset;
+            if timezone_changed {
+                tracing::info!("System timezone changed (UTC offset {}s to {}s); revalidating today's date", last_offset, offset);
+            }
+            last_offset = offset;
+
+            let implausible = wall.year() < MIN_PLAUSIBLE_YEAR;
+            if implausible && !warned_implausible {
+                tracing::warn!("System date {} looks wrong; check the clock settings", wall.date_naive());
+            }
+            warned_implausible = implausible;
+
+            let today = EthiopianDate::today();
+            if clock_jumped || timezone_changed || today != cached_today {
+                refresh_all_surfaces(&app, &today);
+                cached_today = today;
+            }
+            if !implausible {
+                fire_due_reminders(&app, &cached_today);
+            }
+            let settings = crate::load_settings(app.clone()).unwrap_or_default();
+            if let Err(e) = clock_tray::refresh(&app, &settings) {
+                tracing::warn!("{}", e);
+            }
+        }
+    });
+}
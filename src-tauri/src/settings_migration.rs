@@ -0,0 +1,80 @@
+//! Upgrades `settings.json` files written by older versions, before they are parsed.
+//!
+//! The file records the `version` of its layout. Fields that are merely new come from
+//! `#[serde(default)]`, but a renamed or restructured field needs a step here that
+//! rewrites the old JSON into the new shape, one version at a time. Files without a
+//! `version` predate it and are version 0. A copy of the file as it was is kept next
+//! to it before the upgraded settings are written back.
+
+use serde_json::{Map, Value};
+use std::path::{Path, PathBuf};
+
+/// Layout version written by this build.
+pub const CURRENT_VERSION: u32 = 3;
+
+/// Steps from each version to the next; the step at index `n` upgrades version `n`.
+const MIGRATIONS: [fn(&mut Map<String, Value>); CURRENT_VERSION as usize] =
+    [language_from_use_amharic, keep_launch_at_login, drop_fixed_numeric_template];
+
+/// Version 1 replaced the `use_amharic` flag with `language`.
+fn language_from_use_amharic(settings: &mut Map<String, Value>) {
+    let Some(use_amharic) = settings.remove("use_amharic") else {
+        return;
+    };
+    let language = settings.get("language").and_then(Value::as_str);
+    if use_amharic.as_bool() == Some(false) && matches!(language, None | Some("amharic")) {
+        settings.insert("language".to_string(), Value::from("english"));
+    }
+}
+
+/// Version 2 turned launch at login off by default; earlier builds always registered
+/// the login item, so files without the field keep it on.
+fn keep_launch_at_login(settings: &mut Map<String, Value>) {
+    settings.entry("launch_at_login".to_string()).or_insert(Value::Bool(true));
+}
+
+/// Version 3 dropped the default "Numeric" copy template, whose fixed `%d/%m/%Y` ignored
+/// `numeric_format`; the copy menu's numeric entry follows the setting instead. A template
+/// the user renamed or edited is kept.
+fn drop_fixed_numeric_template(settings: &mut Map<String, Value>) {
+    let Some(Value::Array(templates)) = settings.get_mut("copy_templates") else {
+        return;
+    };
+    templates.retain(|template| {
+        template.get("name").and_then(Value::as_str) != Some("Numeric")
+            || template.get("template").and_then(Value::as_str) != Some("%d/%m/%Y")
+    });
+}
+
+/// Upgrades `settings` in place to `CURRENT_VERSION` and returns the version it had,
+/// or `None` when it was already current. Files from a newer build are left alone.
+pub fn migrate(settings: &mut Value) -> Result<Option<u32>, String> {
+    let fields = settings
+        .as_object_mut()
+        .ok_or_else(|| "Failed to parse settings: not a JSON object".to_string())?;
+    let version = fields.get("version").and_then(Value::as_u64).unwrap_or(0) as u32;
+    if version >= CURRENT_VERSION {
+        if version > CURRENT_VERSION {
+            tracing::warn!("Settings were written by a newer version (layout {}); reading them as is", version);
+        }
+        return Ok(None);
+    }
+
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(fields);
+    }
+    fields.insert("version".to_string(), Value::from(CURRENT_VERSION));
+    Ok(Some(version))
+}
+
+/// Where the copy of a version `version` settings file is kept, e.g. `settings.v0.json`.
+pub fn backup_path(settings_path: &Path, version: u32) -> PathBuf {
+    settings_path.with_file_name(format!("settings.v{}.json", version))
+}
+
+/// Saves the original `content` of a settings file before it is rewritten in the new layout.
+pub fn back_up(settings_path: &Path, content: &str, version: u32) -> Result<PathBuf, String> {
+    let path = backup_path(settings_path, version);
+    std::fs::write(&path, content).map_err(|e| format!("Failed to back up settings: {}", e))?;
+    Ok(path)
+}
@@ -0,0 +1,163 @@
+//! Global keyboard shortcuts.
+//!
+//! Each action in `ShortcutSettings` can be bound to an accelerator such as
+//! `CommandOrControl+Shift+E`; none is bound by default. The bindings are registered at
+//! launch and again whenever the settings are saved, replacing the previous ones. Two
+//! actions on the same shortcut are rejected before the settings are saved; a shortcut
+//! another app already holds fails to register and is reported once the rest is saved.
+
+use crate::logging::LogError;
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use tauri::{Emitter, Manager};
+use tauri_plugin_global_shortcut::{GlobalShortcutExt, Shortcut, ShortcutEvent, ShortcutState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShortcutAction {
+    /// Shows the calendar panel, or hides it when it is open.
+    TogglePanel,
+    /// Opens the calendar panel on the current month.
+    ShowToday,
+    /// Sent to the panel as a `shortcut` event.
+    NextMonth,
+    /// Sent to the panel as a `shortcut` event.
+    PrevMonth,
+    /// Copies today's date as the tray shows it.
+    CopyDate,
+}
+
+impl ShortcutAction {
+    fn label(self) -> &'static str {
+        match self {
+            Self::TogglePanel => "toggle calendar",
+            Self::ShowToday => "show today",
+            Self::NextMonth => "next month",
+            Self::PrevMonth => "previous month",
+            Self::CopyDate => "copy date",
+        }
+    }
+}
+
+/// Accelerator of each action, e.g. `"CommandOrControl+Shift+T"`; `None` leaves it unbound.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutSettings {
+    pub toggle_panel: Option<String>,
+    pub show_today: Option<String>,
+    pub next_month: Option<String>,
+    pub prev_month: Option<String>,
+    pub copy_date: Option<String>,
+}
+
+impl ShortcutSettings {
+    fn bindings(&self) -> impl Iterator<Item = (ShortcutAction, &str)> {
+        [
+            (ShortcutAction::TogglePanel, &self.toggle_panel),
+            (ShortcutAction::ShowToday, &self.show_today),
+            (ShortcutAction::NextMonth, &self.next_month),
+            (ShortcutAction::PrevMonth, &self.prev_month),
+            (ShortcutAction::CopyDate, &self.copy_date),
+        ]
+        .into_iter()
+        .filter_map(|(action, accelerator)| {
+            let accelerator = accelerator.as_deref()?.trim();
+            (!accelerator.is_empty()).then_some((action, accelerator))
+        })
+    }
+}
+
+/// The shortcuts registered by the last `register`.
+static REGISTERED: Mutex<Vec<(Shortcut, ShortcutAction)>> = Mutex::new(Vec::new());
+
+/// Parses every bound accelerator and checks that no two actions share a shortcut.
+pub fn validate(settings: &ShortcutSettings) -> Result<Vec<(Shortcut, ShortcutAction)>, String> {
+    let mut shortcuts: Vec<(Shortcut, ShortcutAction)> = Vec::new();
+    for (action, accelerator) in settings.bindings() {
+        let shortcut: Shortcut = accelerator
+            .parse()
+            .map_err(|e| format!("Invalid shortcut {} for {}: {}", accelerator, action.label(), e))?;
+        if let Some((_, other)) = shortcuts.iter().find(|(existing, _)| *existing == shortcut) {
+            return Err(format!(
+                "Shortcut {} is set for both {} and {}",
+                accelerator,
+                other.label(),
+                action.label()
+            ));
+        }
+        shortcuts.push((shortcut, action));
+    }
+    Ok(shortcuts)
+}
+
+/// Replaces the registered shortcuts with the ones in `settings`. Every shortcut that
+/// can be registered is; the error lists those that could not, e.g. because another
+/// app holds them.
+pub fn register(app: &tauri::AppHandle, settings: &ShortcutSettings) -> Result<(), String> {
+    let wanted = validate(settings)?;
+    let mut registered = REGISTERED.lock().map_err(|e| format!("Failed to lock shortcuts: {}", e))?;
+    if *registered == wanted {
+        return Ok(());
+    }
+
+    let global_shortcut = app.global_shortcut();
+    for (shortcut, _) in registered.drain(..) {
+        global_shortcut.unregister(shortcut).log_error("Failed to unregister shortcut");
+    }
+    let mut failed = Vec::new();
+    for (shortcut, action) in wanted {
+        match global_shortcut.register(shortcut) {
+            Ok(()) => registered.push((shortcut, action)),
+            Err(e) => failed.push(format!("{} ({})", action.label(), e)),
+        }
+    }
+    if failed.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Failed to register shortcuts, they may be in use by another app: {}", failed.join(", ")))
+    }
+}
+
+/// Shows the calendar panel, or hides it when it is open.
+pub(crate) fn toggle_panel(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("settings") {
+        if window.is_visible().unwrap_or(false) {
+            window.hide().log_error("Failed to hide calendar window");
+        } else {
+            crate::deep_link::show_calendar(app);
+        }
+    }
+}
+
+fn copy_date(app: &tauri::AppHandle) {
+    use tauri_plugin_clipboard_manager::ClipboardExt;
+
+    let settings = crate::load_settings(app.clone()).unwrap_or_default();
+    let text = EthiopianDate::today()
+        .format_with(&crate::display::display_template(&settings), crate::display::format_options(&settings));
+    app.clipboard().write_text(text).log_error("Failed to copy to clipboard");
+}
+
+/// Runs the action bound to `shortcut` when it is pressed.
+pub fn handle(app: &tauri::AppHandle, shortcut: &Shortcut, event: ShortcutEvent) {
+    if event.state() != ShortcutState::Pressed {
+        return;
+    }
+    let action = REGISTERED
+        .lock()
+        .ok()
+        .and_then(|registered| registered.iter().find(|(registered, _)| registered == shortcut).map(|(_, action)| *action));
+    match action {
+        Some(ShortcutAction::TogglePanel) => toggle_panel(app),
+        Some(ShortcutAction::ShowToday) => {
+            crate::deep_link::show_calendar(app);
+            app.emit("show-date", EthiopianDate::today()).log_error("Failed to emit show-date");
+        }
+        Some(action @ (ShortcutAction::NextMonth | ShortcutAction::PrevMonth)) => {
+            app.emit("shortcut", action).log_error("Failed to emit shortcut");
+        }
+        Some(ShortcutAction::CopyDate) => copy_date(app),
+        None => {}
+    }
+}
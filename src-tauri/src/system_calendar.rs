@@ -0,0 +1,141 @@
+//! Events from the macOS Calendar app, read through EventKit and overlaid on the Ethiopian month grid.
+//!
+//! Reading needs the user's permission, asked for once by `set_system_calendar_events`
+//! when the overlay is turned on. Events are read live for the month on screen, with
+//! their start and end converted from the system timezone to Ethiopian days; nothing
+//! is stored.
+
+use crate::EthiopianDate;
+use block2::RcBlock;
+use chrono::{DateTime, Local, NaiveTime, TimeZone};
+use objc2::runtime::Bool;
+use objc2::sel;
+use objc2_event_kit::{EKAuthorizationStatus, EKEntityType, EKEventStore};
+use objc2_foundation::{NSDate, NSError, NSObjectProtocol};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// How long to wait for the user to answer the permission prompt.
+const ACCESS_PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemEvent {
+    pub id: String,
+    pub title: String,
+    /// Name of the calendar the event belongs to, e.g. "Work".
+    pub calendar: String,
+    /// Day the event falls on; events spanning several days are listed once per day.
+    pub date: EthiopianDate,
+    /// Start time on the event's first day; `None` for all-day events and following days.
+    pub time: Option<NaiveTime>,
+    pub all_day: bool,
+}
+
+pub fn has_access() -> bool {
+    let status = unsafe { EKEventStore::authorizationStatusForEntityType(EKEntityType::Event) };
+    status == EKAuthorizationStatus::FullAccess
+}
+
+/// Asks for read access to the user's calendars, prompting the first time.
+pub fn request_access() -> Result<bool, String> {
+    if has_access() {
+        return Ok(true);
+    }
+
+    let (sender, receiver) = mpsc::channel();
+    let completion = RcBlock::new(move |granted: Bool, _error: *mut NSError| {
+        let _ = sender.send(granted.as_bool());
+    });
+    let store = unsafe { EKEventStore::new() };
+    // macOS 14 split calendar access into full and write-only access with a new request method.
+    if store.respondsToSelector(sel!(requestFullAccessToEventsWithCompletion:)) {
+        unsafe { store.requestFullAccessToEventsWithCompletion(RcBlock::as_ptr(&completion)) };
+    } else {
+        #[allow(deprecated)]
+        unsafe {
+            store.requestAccessToEntityType_completion(EKEntityType::Event, RcBlock::as_ptr(&completion))
+        };
+    }
+
+    receiver
+        .recv_timeout(ACCESS_PROMPT_TIMEOUT)
+        .map_err(|e| format!("Failed to get calendar access: {}", e))
+}
+
+fn local_time(seconds: f64) -> Option<DateTime<Local>> {
+    Local.timestamp_opt(seconds.floor() as i64, 0).single()
+}
+
+/// Ethiopian days covered by an event from `start` to `end`, with the start time on the first day.
+///
+/// `end` is exclusive, so an event ending at midnight does not spill into the next day.
+pub(crate) fn event_days<Z: TimeZone>(start: DateTime<Z>, end: DateTime<Z>, all_day: bool) -> Vec<(EthiopianDate, Option<NaiveTime>)> {
+    let first = start.date_naive();
+    let last = if end > start { (end - chrono::Duration::seconds(1)).date_naive() } else { first };
+
+    first
+        .iter_days()
+        .take_while(|day| *day <= last)
+        .filter_map(|day| {
+            let time = (day == first && !all_day).then(|| start.time());
+            Some((EthiopianDate::from_naive_date(day)?, time))
+        })
+        .collect()
+}
+
+/// Events from every calendar on the days of an Ethiopian month.
+pub fn events_for_month(year: usize, month: usize) -> Result<Vec<SystemEvent>, String> {
+    if !has_access() {
+        return Err("No access to the system calendars".to_string());
+    }
+
+    let first_day = EthiopianDate::new(year, month, 1);
+    let last_day = EthiopianDate::new(year, month, first_day.days_in_month());
+    let range_start = first_day.to_gregorian().and_then(|date| Local.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest());
+    let range_end = last_day
+        .add_days(1)
+        .and_then(|date| date.to_gregorian())
+        .and_then(|date| Local.from_local_datetime(&date.and_time(NaiveTime::MIN)).earliest());
+    let (Some(range_start), Some(range_end)) = (range_start, range_end) else {
+        return Err(format!("Invalid Ethiopian month: {}-{}", year, month));
+    };
+
+    let store = unsafe { EKEventStore::new() };
+    let start = NSDate::dateWithTimeIntervalSince1970(range_start.timestamp() as f64);
+    let end = NSDate::dateWithTimeIntervalSince1970(range_end.timestamp() as f64);
+    let predicate = unsafe { store.predicateForEventsWithStartDate_endDate_calendars(&start, &end, None) };
+    let events = unsafe { store.eventsMatchingPredicate(&predicate) };
+
+    let mut result = Vec::new();
+    for event in events.iter() {
+        let (Some(start), Some(end)) = (
+            local_time(unsafe { event.startDate() }.timeIntervalSince1970()),
+            local_time(unsafe { event.endDate() }.timeIntervalSince1970()),
+        ) else {
+            continue;
+        };
+        let all_day = unsafe { event.isAllDay() };
+        let id = unsafe { event.eventIdentifier() }.map(|id| id.to_string()).unwrap_or_default();
+        let title = unsafe { event.title() }.to_string();
+        let calendar = unsafe { event.calendar() }
+            .map(|calendar| unsafe { calendar.title() }.to_string())
+            .unwrap_or_default();
+
+        result.extend(
+            event_days(start, end, all_day)
+                .into_iter()
+                .filter(|(date, _)| date.year == year && date.month == month)
+                .map(|(date, time)| SystemEvent {
+                    id: id.clone(),
+                    title: title.clone(),
+                    calendar: calendar.clone(),
+                    date,
+                    time,
+                    all_day,
+                }),
+        );
+    }
+    result.sort_by(|a, b| (&a.date, a.time).cmp(&(&b.date, b.time)));
+    Ok(result)
+}
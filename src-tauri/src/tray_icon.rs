@@ -0,0 +1,64 @@
+//! The tray icon shown in icon mode, when `show_date_in_tray` is off.
+//!
+//! `Auto` picks the white glyph on a dark menu bar and the dark glyph on a light one,
+//! from the system appearance, and is applied again when the appearance changes.
+//! `Template` hands macOS a template image to tint itself instead, which also follows
+//! a menu bar tinted by the desktop picture.
+
+use serde::{Deserialize, Serialize};
+use tauri::image::Image;
+use tauri::{Manager, Theme};
+
+const TEMPLATE_ICON: &[u8] = include_bytes!("../icons/tray/template.png");
+const DARK_MENU_BAR_ICON: &[u8] = include_bytes!("../icons/tray/dark.png");
+const LIGHT_MENU_BAR_ICON: &[u8] = include_bytes!("../icons/tray/light.png");
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrayIconVariant {
+    /// `Dark` or `Light`, following the system appearance.
+    #[default]
+    Auto,
+    /// Tinted by macOS.
+    Template,
+    /// White glyph, for a dark menu bar.
+    Dark,
+    /// Dark glyph, for a light menu bar.
+    Light,
+}
+
+/// Appearance of the menu bar, as reported for the calendar window, which follows the system.
+fn system_theme(app: &tauri::AppHandle) -> Theme {
+    app.get_webview_window("settings")
+        .and_then(|window| window.theme().ok())
+        .unwrap_or(Theme::Light)
+}
+
+/// The PNG for `variant` and whether it is a template image.
+fn icon(variant: TrayIconVariant, theme: Theme) -> (&'static [u8], bool) {
+    match (variant, theme) {
+        (TrayIconVariant::Template, _) => (TEMPLATE_ICON, true),
+        (TrayIconVariant::Dark, _) | (TrayIconVariant::Auto, Theme::Dark) => (DARK_MENU_BAR_ICON, false),
+        _ => (LIGHT_MENU_BAR_ICON, false),
+    }
+}
+
+/// Shows the icon for `variant` in the tray.
+pub fn show(app: &tauri::AppHandle, variant: TrayIconVariant) -> Result<(), String> {
+    let Some(tray) = app.tray_by_id("main") else {
+        return Ok(());
+    };
+    let (bytes, template) = icon(variant, system_theme(app));
+    let image = Image::from_bytes(bytes).map_err(|e| format!("Failed to load tray icon: {}", e))?;
+    tray.set_icon(Some(image)).map_err(|e| format!("Failed to set tray icon: {}", e))?;
+    tray.set_icon_as_template(template)
+        .map_err(|e| format!("Failed to set tray icon: {}", e))
+}
+
+/// Removes the icon, leaving the tray to the date text.
+pub fn hide(app: &tauri::AppHandle) -> Result<(), String> {
+    match app.tray_by_id("main") {
+        Some(tray) => tray.set_icon(None).map_err(|e| format!("Failed to remove tray icon: {}", e)),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,124 @@
+//! Checks GitHub Releases for a newer version of ZemenBar.
+//!
+//! A background thread checks a little after launch and then once a day, unless
+//! `check_for_updates` is turned off in the settings; `check_for_updates` checks on
+//! demand either way. A newer release adds an "Update Available" entry to the tray menu
+//! that opens its release page. Nothing is downloaded or installed.
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/yafetgetachew/zemenbar/releases/latest";
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How long after launch the first check runs.
+const FIRST_CHECK_DELAY: Duration = Duration::from_secs(60);
+
+/// How often the background thread checks whether a check is due.
+const TICK: Duration = Duration::from_secs(60 * 60);
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The part of the GitHub release response used here.
+#[derive(Debug, Clone, Deserialize)]
+struct Release {
+    tag_name: String,
+    html_url: String,
+    #[serde(default)]
+    draft: bool,
+    #[serde(default)]
+    prerelease: bool,
+}
+
+/// The outcome of an update check.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    /// Version of the latest release, without the leading `v` of its tag.
+    pub latest_version: String,
+    /// Page of the latest release.
+    pub url: String,
+    pub update_available: bool,
+}
+
+/// The last check that found a newer release, shown in the tray menu.
+static AVAILABLE_UPDATE: Mutex<Option<UpdateInfo>> = Mutex::new(None);
+
+/// Numeric parts of a version such as `v0.6.1`; a pre-release suffix like `-beta.2` is ignored.
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    let version = version.trim().trim_start_matches('v');
+    let release = version.split(['-', '+']).next()?;
+    release.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Whether `latest` is a later version than `current`.
+fn is_newer(latest: &str, current: &str) -> bool {
+    match (version_parts(latest), version_parts(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn latest_release() -> Result<Release, String> {
+    ureq::AgentBuilder::new()
+        .timeout(REQUEST_TIMEOUT)
+        .build()
+        .get(LATEST_RELEASE_URL)
+        .set("Accept", "application/vnd.github+json")
+        .set("User-Agent", concat!("ZemenBar/", env!("CARGO_PKG_VERSION")))
+        .call()
+        .map_err(|e| format!("Failed to check for updates: {}", e))?
+        .into_json()
+        .map_err(|e| format!("Failed to read the latest release: {}", e))
+}
+
+/// The update found by the last check, if any.
+pub fn available_update() -> Option<UpdateInfo> {
+    AVAILABLE_UPDATE.lock().ok().and_then(|update| update.clone())
+}
+
+/// Asks GitHub for the latest release and updates the tray menu when that changes what it shows.
+pub fn check(app: &tauri::AppHandle) -> Result<UpdateInfo, String> {
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+    let release = latest_release()?;
+    let latest_version = release.tag_name.trim_start_matches('v').to_string();
+    let info = UpdateInfo {
+        update_available: !release.draft && !release.prerelease && is_newer(&latest_version, &current_version),
+        current_version,
+        latest_version,
+        url: release.html_url,
+    };
+
+    let found = info.update_available.then(|| info.clone());
+    let changed = {
+        let mut available = AVAILABLE_UPDATE.lock().map_err(|e| format!("Failed to lock update status: {}", e))?;
+        let changed = *available != found;
+        *available = found;
+        changed
+    };
+    if changed {
+        let settings = crate::load_settings(app.clone())?;
+        crate::refresh_tray_menu(app, &settings)?;
+    }
+    Ok(info)
+}
+
+/// Starts the background thread that checks once a day while checks are turned on.
+pub fn start(app: tauri::AppHandle) {
+    std::thread::spawn(move || {
+        std::thread::sleep(FIRST_CHECK_DELAY);
+        let mut last_check: Option<Instant> = None;
+        loop {
+            let enabled = crate::load_settings(app.clone()).map(|settings| settings.check_for_updates).unwrap_or(false);
+            if enabled && last_check.is_none_or(|check| check.elapsed() >= CHECK_INTERVAL) {
+                last_check = Some(Instant::now());
+                if let Err(e) = check(&app) {
+                    tracing::warn!("{}", e);
+                }
+            }
+            std::thread::sleep(TICK);
+        }
+    });
+}
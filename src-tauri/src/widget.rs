@@ -0,0 +1,109 @@
+//! Desktop widget: a borderless, non-focusable month view kept below normal windows.
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, WebviewUrl, WebviewWindowBuilder};
+
+pub const WIDGET_LABEL: &str = "widget";
+
+/// Logical position and size of the widget window, persisted separately from the settings.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct WidgetGeometry {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Default for WidgetGeometry {
+    fn default() -> Self {
+        Self {
+            x: 40.0,
+            y: 60.0,
+            width: 300.0,
+            height: 330.0,
+        }
+    }
+}
+
+fn get_geometry_path(app: &tauri::AppHandle) -> Result<std::path::PathBuf, String> {
+    app.path()
+        .app_data_dir()
+        .map(|dir| dir.join("widget.json"))
+        .map_err(|e| format!("Failed to get app data directory: {}", e))
+}
+
+pub fn load_geometry(app: &tauri::AppHandle) -> WidgetGeometry {
+    get_geometry_path(app)
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_geometry(app: &tauri::AppHandle, geometry: &WidgetGeometry) -> Result<(), String> {
+    let path = get_geometry_path(app)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create widget directory: {}", e))?;
+    }
+
+    let content = serde_json::to_string_pretty(geometry)
+        .map_err(|e| format!("Failed to serialize widget geometry: {}", e))?;
+
+    std::fs::write(&path, content)
+        .map_err(|e| format!("Failed to write widget geometry: {}", e))
+}
+
+/// Creates the widget window at its saved geometry, or shows it if it already exists.
+pub fn show_widget(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(WIDGET_LABEL) {
+        return window
+            .show()
+            .map_err(|e| format!("Failed to show widget: {}", e));
+    }
+
+    let geometry = load_geometry(app);
+    let window = WebviewWindowBuilder::new(app, WIDGET_LABEL, WebviewUrl::default())
+        .title("ZemenBar Widget")
+        .inner_size(geometry.width, geometry.height)
+        .position(geometry.x, geometry.y)
+        .decorations(false)
+        .transparent(true)
+        .shadow(false)
+        .resizable(false)
+        .skip_taskbar(true)
+        .focused(false)
+        .focusable(false)
+        .always_on_bottom(true)
+        .visible_on_all_workspaces(true)
+        .build()
+        .map_err(|e| format!("Failed to create widget window: {}", e))?;
+
+    let app_handle = app.clone();
+    let window_clone = window.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Moved(position) = event {
+            let scale_factor = window_clone.scale_factor().unwrap_or(1.0);
+            let moved = WidgetGeometry {
+                x: position.x as f64 / scale_factor,
+                y: position.y as f64 / scale_factor,
+                ..geometry
+            };
+            if let Err(e) = save_geometry(&app_handle, &moved) {
+                tracing::warn!("{}", e);
+            }
+        }
+    });
+
+    Ok(())
+}
+
+pub fn close_widget(app: &tauri::AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(WIDGET_LABEL) {
+        window
+            .close()
+            .map_err(|e| format!("Failed to close widget: {}", e))?;
+    }
+    Ok(())
+}
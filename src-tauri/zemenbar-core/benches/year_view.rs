@@ -0,0 +1,29 @@
+//! Times building the thirteen month grids of the year view, which must stay within a
+//! few milliseconds. Run with `cargo bench -p zemenbar-core`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use zemenbar_core::locale::Language;
+use zemenbar_core::CalendarMonth;
+
+const RUNS: u32 = 100;
+
+/// Slowest acceptable year view build.
+const BUDGET: Duration = Duration::from_millis(5);
+
+fn year_view(year: usize) -> Vec<CalendarMonth> {
+    (1..=13).map(|month| CalendarMonth::localized(year, month, 1, Language::English)).collect()
+}
+
+fn main() {
+    black_box(year_view(2017));
+
+    let start = Instant::now();
+    for run in 0..RUNS {
+        black_box(year_view(2000 + run as usize % 50));
+    }
+    let per_year = start.elapsed() / RUNS;
+
+    println!("year view: {:?} per build, over {} builds", per_year, RUNS);
+    assert!(per_year < BUDGET, "Year view took {:?}, over the {:?} budget", per_year, BUDGET);
+}
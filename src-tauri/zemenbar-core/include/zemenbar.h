@@ -0,0 +1,78 @@
+/* Generated by cbindgen from src/ffi.rs; do not edit. */
+
+#ifndef ZEMENBAR_H
+#define ZEMENBAR_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * A year, month and day, in whichever calendar the function names.
+ */
+typedef struct ZemenbarDate {
+  int32_t year;
+  uint32_t month;
+  uint32_t day;
+} ZemenbarDate;
+
+/**
+ * Today's Ethiopian date in the configured timezone.
+ *
+ * # Safety
+ *
+ * `out` must be null or point to writable memory for a `ZemenbarDate`.
+ */
+bool zemenbar_today(ZemenbarDate *out);
+
+/**
+ * Converts a Gregorian date to Ethiopian; `false` when the date does not exist.
+ *
+ * # Safety
+ *
+ * `out` must be null or point to writable memory for a `ZemenbarDate`.
+ */
+bool zemenbar_to_ethiopian(ZemenbarDate gregorian, ZemenbarDate *out);
+
+/**
+ * Converts an Ethiopian date to Gregorian; `false` when the date does not exist.
+ *
+ * # Safety
+ *
+ * `out` must be null or point to writable memory for a `ZemenbarDate`.
+ */
+bool zemenbar_to_gregorian(ZemenbarDate ethiopian_date, ZemenbarDate *out);
+
+/**
+ * Renders an Ethiopian date with a `%` token template, e.g. `"%EB %e %Y %EC"`; see
+ * `format` for the tokens. Null when the date or template is invalid.
+ *
+ * # Safety
+ *
+ * `template` must be null or a NUL-terminated string.
+ */
+char *zemenbar_format(ZemenbarDate ethiopian_date, const char *template);
+
+/**
+ * The name of the holiday on an Ethiopian date, in Amharic or English, or null when
+ * the day is not a holiday. Several holidays on one day are joined with " / ".
+ */
+char *zemenbar_holiday_name(ZemenbarDate ethiopian_date, bool amharic);
+
+/**
+ * Every holiday and observance of an Ethiopian year as a JSON array of `Holiday`s,
+ * in date order.
+ */
+char *zemenbar_holidays_json(uint32_t year);
+
+/**
+ * Releases a string returned by this library.
+ *
+ * # Safety
+ *
+ * `text` must be null or a pointer returned by this library that was not freed yet.
+ */
+void zemenbar_string_free(char *text);
+
+#endif  /* ZEMENBAR_H */
@@ -0,0 +1,163 @@
+//! Optional academic calendar presets: school semesters, university semesters and the
+//! national exam periods.
+//!
+//! The dates are the usual ones of the Ministry of Education calendar, kept on the same
+//! Ethiopian dates every year; schools and universities announce their own each year,
+//! so these are a starting point rather than an official schedule. Enabled presets show
+//! up among the holidays, as a day marking the start and one marking the end of each
+//! period, in the `Academic` category.
+
+use crate::holidays::{Holiday, HolidayCategory};
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use AcademicPreset::{NationalExams, SchoolTerms, UniversitySemesters};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcademicPreset {
+    SchoolTerms,
+    UniversitySemesters,
+    NationalExams,
+}
+
+/// Which presets are turned on, from `AppSettings.academic_presets`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AcademicPresets {
+    pub school_terms: bool,
+    pub university_semesters: bool,
+    pub national_exams: bool,
+}
+
+impl AcademicPresets {
+    pub fn includes(&self, preset: AcademicPreset) -> bool {
+        match preset {
+            SchoolTerms => self.school_terms,
+            UniversitySemesters => self.university_semesters,
+            NationalExams => self.national_exams,
+        }
+    }
+}
+
+static ENABLED_PRESETS: Mutex<AcademicPresets> = Mutex::new(AcademicPresets {
+    school_terms: false,
+    university_semesters: false,
+    national_exams: false,
+});
+
+pub fn enabled_presets() -> AcademicPresets {
+    ENABLED_PRESETS.lock().map(|presets| *presets).unwrap_or_default()
+}
+
+pub fn set_enabled_presets(presets: AcademicPresets) {
+    if let Ok(mut current) = ENABLED_PRESETS.lock() {
+        *current = presets;
+    }
+}
+
+/// Ethiopian month and day.
+type MonthDay = (usize, usize);
+
+/// Periods of the year: (preset, id, first day, last day, Amharic, English).
+const PERIODS: [(AcademicPreset, &str, MonthDay, MonthDay, &str, &str); 8] = [
+    (SchoolTerms, "school_semester_1", (1, 14), (5, 10), "የትምህርት ቤት አንደኛ መንፈቀ ዓመት", "School first semester"),
+    (SchoolTerms, "school_semester_2", (5, 26), (10, 30), "የትምህርት ቤት ሁለተኛ መንፈቀ ዓመት", "School second semester"),
+    (UniversitySemesters, "university_semester_1", (2, 1), (5, 30), "የዩኒቨርሲቲ አንደኛ ሴሚስተር", "University first semester"),
+    (UniversitySemesters, "university_semester_2", (6, 15), (10, 30), "የዩኒቨርሲቲ ሁለተኛ ሴሚስተር", "University second semester"),
+    (UniversitySemesters, "university_summer", (11, 1), (12, 30), "የክረምት ትምህርት", "University summer term"),
+    (NationalExams, "grade_6_exam", (10, 10), (10, 12), "የ፮ኛ ክፍል ክልላዊ ፈተና", "Grade 6 regional exam"),
+    (NationalExams, "grade_8_exam", (10, 16), (10, 18), "የ፰ኛ ክፍል ክልላዊ ፈተና", "Grade 8 regional exam"),
+    (NationalExams, "grade_12_exam", (11, 3), (11, 19), "የ፲፪ኛ ክፍል ብሔራዊ ፈተና", "Grade 12 national exam"),
+];
+
+/// A term, semester or exam period of one Ethiopian year.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AcademicPeriod {
+    pub id: String,
+    pub preset: AcademicPreset,
+    pub name_amharic: String,
+    pub name_english: String,
+    /// First day, inclusive.
+    pub start: EthiopianDate,
+    /// Last day, inclusive.
+    pub end: EthiopianDate,
+}
+
+/// The periods of `year` from the `presets` turned on, in date order.
+pub fn periods_for_year(year: usize, presets: AcademicPresets) -> Vec<AcademicPeriod> {
+    let mut periods: Vec<AcademicPeriod> = PERIODS
+        .iter()
+        .filter(|(preset, ..)| presets.includes(*preset))
+        .map(|&(preset, id, (start_month, start_day), (end_month, end_day), name_amharic, name_english)| {
+            AcademicPeriod {
+                id: id.to_string(),
+                preset,
+                name_amharic: name_amharic.to_string(),
+                name_english: name_english.to_string(),
+                start: EthiopianDate::new(year, start_month, start_day),
+                end: EthiopianDate::new(year, end_month, end_day),
+            }
+        })
+        .collect();
+    periods.sort_by(|a, b| a.start.cmp(&b.start));
+    periods
+}
+
+/// A period under way on some day, and how far into it that day is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrentPeriod {
+    pub period: AcademicPeriod,
+    /// Week of the period, counting from 1 on its first day.
+    pub week: usize,
+    /// Weeks the period lasts, the last one possibly shorter.
+    pub total_weeks: usize,
+    /// Days left after that day, 0 on the last day.
+    pub days_remaining: i64,
+}
+
+/// Every enabled period `date` falls in, e.g. the second semester in its 6th week.
+pub fn current_periods(date: &EthiopianDate) -> Vec<CurrentPeriod> {
+    periods_for_year(date.year, enabled_presets())
+        .into_iter()
+        .filter(|period| period.start <= *date && *date <= period.end)
+        .filter_map(|period| {
+            let elapsed = period.start.days_between(date)?;
+            let length = period.start.days_between(&period.end)? + 1;
+            Some(CurrentPeriod {
+                week: elapsed as usize / 7 + 1,
+                total_weeks: (length as usize).div_ceil(7),
+                days_remaining: date.days_between(&period.end)?,
+                period,
+            })
+        })
+        .collect()
+}
+
+/// Start and end markers of the enabled periods of `year`, for the holiday list.
+pub fn academic_holidays(year: usize) -> Vec<Holiday> {
+    periods_for_year(year, enabled_presets())
+        .into_iter()
+        .flat_map(|period| {
+            let start = Holiday {
+                id: format!("{}_start", period.id),
+                name_amharic: format!("{} ይጀምራል", period.name_amharic),
+                name_english: format!("{} begins", period.name_english),
+                date: period.start.clone(),
+                is_public: false,
+                category: HolidayCategory::Academic,
+                is_approximate: false,
+            };
+            let end = Holiday {
+                id: format!("{}_end", period.id),
+                name_amharic: format!("{} ያበቃል", period.name_amharic),
+                name_english: format!("{} ends", period.name_english),
+                date: period.end,
+                is_public: false,
+                category: HolidayCategory::Academic,
+                is_approximate: false,
+            };
+            [start, end]
+        })
+        .collect()
+}
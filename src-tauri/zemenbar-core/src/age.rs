@@ -0,0 +1,143 @@
+//! Ages and spans between dates in years, months and days, in both calendars.
+//!
+//! Spans borrow from the month before the end date when the days do not reach, as
+//! people count ages by hand: from Tir 25 to Yekatit 10 is 15 days once a 30-day month
+//! is borrowed. Borrowing from Meskerem takes Pagumē's five or six days instead, and
+//! the month before Pagumē as well when that is not enough.
+
+use crate::holidays::{self, Holiday};
+use crate::EthiopianDate;
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YearsMonthsDays {
+    pub years: usize,
+    pub months: usize,
+    pub days: usize,
+}
+
+/// The span between two dates, counted in both calendars.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateDuration {
+    pub from: EthiopianDate,
+    pub to: EthiopianDate,
+    /// Signed number of days from `from` to `to`.
+    pub total_days: i64,
+    /// Ethiopian years (of 13 months), months and days between the two dates.
+    pub ethiopian: YearsMonthsDays,
+    pub gregorian: YearsMonthsDays,
+    /// `from` and `to` as Gregorian `YYYY-MM-DD`.
+    pub from_gregorian: String,
+    pub to_gregorian: String,
+}
+
+/// Two dates side by side, as the date calculator compares them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateComparison {
+    /// Both dates in both calendars and the span between them.
+    pub duration: DateDuration,
+    /// Whole weeks between the dates, ignoring their order, and the days left over.
+    pub weeks: usize,
+    pub remaining_days: usize,
+    /// Whole months between the dates, counting 13 to the Ethiopian year.
+    pub ethiopian_months: usize,
+    pub gregorian_months: usize,
+    /// Shown holidays and observances after the earlier date and before the later one, in date order.
+    pub holidays: Vec<Holiday>,
+}
+
+fn ethiopian_span(start: &EthiopianDate, end: &EthiopianDate) -> YearsMonthsDays {
+    let mut years = end.year as i64 - start.year as i64;
+    let mut months = end.month as i64 - start.month as i64;
+    let mut days = end.day as i64 - start.day as i64;
+
+    // Pagumē is shorter than most start days, so borrowing may reach into the month before it.
+    let mut borrow_from = end.clone();
+    while days < 0 {
+        let Some(previous) = borrow_from.add_months(-1) else {
+            break;
+        };
+        months -= 1;
+        days += previous.days_in_month() as i64;
+        borrow_from = previous;
+    }
+    while months < 0 {
+        years -= 1;
+        months += 13;
+    }
+
+    YearsMonthsDays {
+        years: years as usize,
+        months: months as usize,
+        days: days as usize,
+    }
+}
+
+fn gregorian_span(start: NaiveDate, end: NaiveDate) -> YearsMonthsDays {
+    let mut years = end.year() as i64 - start.year() as i64;
+    let mut months = end.month() as i64 - start.month() as i64;
+    let mut days = end.day() as i64 - start.day() as i64;
+
+    if days < 0 {
+        months -= 1;
+        let previous_month_end = end.with_day(1).and_then(|first| first.pred_opt());
+        days += previous_month_end.map(|date| date.day() as i64).unwrap_or(30);
+    }
+    if months < 0 {
+        years -= 1;
+        months += 12;
+    }
+
+    YearsMonthsDays {
+        years: years as usize,
+        months: months as usize,
+        days: days as usize,
+    }
+}
+
+/// Span from `from` to `to`. The years, months and days are counted forward from the
+/// earlier date, whichever order the dates are given in.
+pub fn duration_between(from: &EthiopianDate, to: &EthiopianDate) -> Option<DateDuration> {
+    let (start, end) = if from <= to { (from, to) } else { (to, from) };
+    let start_gregorian = start.to_gregorian()?;
+    let end_gregorian = end.to_gregorian()?;
+
+    Some(DateDuration {
+        from: from.clone(),
+        to: to.clone(),
+        total_days: from.days_between(to)?,
+        ethiopian: ethiopian_span(start, end),
+        gregorian: gregorian_span(start_gregorian, end_gregorian),
+        from_gregorian: from.to_gregorian()?.format("%Y-%m-%d").to_string(),
+        to_gregorian: to.to_gregorian()?.format("%Y-%m-%d").to_string(),
+    })
+}
+
+/// Age on `today` of someone born on `birth_date`; `None` for a birth date in the future.
+pub fn calculate_age(birth_date: &EthiopianDate, today: &EthiopianDate) -> Option<DateDuration> {
+    if birth_date > today {
+        return None;
+    }
+    duration_between(birth_date, today)
+}
+
+/// Compares `a` and `b`, which may be given in either order.
+pub fn compare_dates(a: &EthiopianDate, b: &EthiopianDate) -> Option<DateComparison> {
+    let duration = duration_between(a, b)?;
+    let (start, end) = if a <= b { (a, b) } else { (b, a) };
+    let days = duration.total_days.unsigned_abs() as usize;
+    let holidays = holidays::holidays_between(start, end)
+        .into_iter()
+        .filter(|holiday| holiday.date != *start && holiday.date != *end && holiday.is_shown())
+        .collect();
+
+    Some(DateComparison {
+        weeks: days / 7,
+        remaining_days: days % 7,
+        ethiopian_months: duration.ethiopian.years * 13 + duration.ethiopian.months,
+        gregorian_months: duration.gregorian.years * 12 + duration.gregorian.months,
+        holidays,
+        duration,
+    })
+}
@@ -0,0 +1,138 @@
+//! Solar calculations used for daylight length and sunrise, and the phase of the moon.
+//!
+//! Uses the standard declination / hour-angle approximation, which is accurate to
+//! a minute or two at Ethiopian latitudes.
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+
+/// Latitude of Addis Ababa, used when no location is configured.
+pub const ADDIS_ABABA_LATITUDE: f64 = 9.03;
+
+/// Longitude of Addis Ababa, east positive.
+pub const ADDIS_ABABA_LONGITUDE: f64 = 38.74;
+
+/// Sun altitude at sunrise/sunset, accounting for refraction and the solar disc.
+const SUNRISE_ALTITUDE_DEG: f64 = -0.833;
+
+/// Mean length of a lunation in days.
+const SYNODIC_MONTH_DAYS: f64 = 29.530588853;
+
+/// Fraction of 2000-01-06 that had passed at the new moon of 18:14 UTC.
+const REFERENCE_NEW_MOON_TIME: f64 = 0.7597;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DaylightTrend {
+    Lengthening,
+    Shortening,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Daylight {
+    /// Hours between sunrise and sunset.
+    pub hours: f64,
+    /// Change in minutes compared to the previous day.
+    pub change_minutes: f64,
+    pub trend: DaylightTrend,
+}
+
+/// Solar declination in degrees for a Gregorian date.
+pub fn solar_declination(date: NaiveDate) -> f64 {
+    let day_of_year = date.ordinal() as f64;
+    -23.44 * (2.0 * std::f64::consts::PI / 365.0 * (day_of_year + 10.0)).cos()
+}
+
+/// Cosine of the sun's hour angle at sunrise; outside [-1, 1] during polar day or night.
+fn cos_sunrise_hour_angle(date: NaiveDate, latitude: f64) -> f64 {
+    let latitude = latitude.to_radians();
+    let declination = solar_declination(date).to_radians();
+    (SUNRISE_ALTITUDE_DEG.to_radians().sin() - latitude.sin() * declination.sin()) / (latitude.cos() * declination.cos())
+}
+
+/// Minutes the true solar time is ahead of mean solar time.
+fn equation_of_time_minutes(date: NaiveDate) -> f64 {
+    let b = 2.0 * std::f64::consts::PI / 364.0 * (date.ordinal() as f64 - 81.0);
+    9.87 * (2.0 * b).sin() - 7.53 * b.cos() - 1.5 * b.sin()
+}
+
+/// Length of the day in hours at the given latitude.
+pub fn day_length_hours(date: NaiveDate, latitude: f64) -> f64 {
+    // Polar day/night outside [-1, 1].
+    let hour_angle = cos_sunrise_hour_angle(date, latitude).clamp(-1.0, 1.0).acos().to_degrees();
+    2.0 * hour_angle / 15.0
+}
+
+/// Sunrise in UTC on `date` at the given latitude and longitude (east positive), or
+/// `None` when the sun does not rise or set that day.
+pub fn sunrise_utc(date: NaiveDate, latitude: f64, longitude: f64) -> Option<NaiveDateTime> {
+    let cos_hour_angle = cos_sunrise_hour_angle(date, latitude);
+    if !(-1.0..=1.0).contains(&cos_hour_angle) {
+        return None;
+    }
+    let hour_angle = cos_hour_angle.acos().to_degrees();
+    let solar_noon_minutes = 720.0 - 4.0 * longitude - equation_of_time_minutes(date);
+    let sunrise_minutes = solar_noon_minutes - 4.0 * hour_angle;
+    Some(date.and_hms_opt(0, 0, 0)? + Duration::seconds((sunrise_minutes * 60.0).round() as i64))
+}
+
+/// Daylight length for a date along with its day-over-day trend.
+pub fn daylight(date: NaiveDate, latitude: f64) -> Daylight {
+    let hours = day_length_hours(date, latitude);
+    let previous = day_length_hours(date - Duration::days(1), latitude);
+    let change_minutes = (hours - previous) * 60.0;
+
+    Daylight {
+        hours,
+        change_minutes,
+        trend: if change_minutes >= 0.0 {
+            DaylightTrend::Lengthening
+        } else {
+            DaylightTrend::Shortening
+        },
+    }
+}
+
+/// Phase of the moon on a day. The four principal phases are given only to the day
+/// they fall on; the days between get the waxing or waning phase they are in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    NewMoon,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    FullMoon,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+/// Phase of the moon on `date` (UTC), from the mean lunation; principal phases can be
+/// a day off, as the true moon runs up to about half a day ahead of or behind the mean.
+pub fn moon_phase(date: NaiveDate) -> MoonPhase {
+    let reference = NaiveDate::from_ymd_opt(2000, 1, 6).expect("2000-01-06 is a valid date");
+    let days = (date - reference).num_days() as f64 - REFERENCE_NEW_MOON_TIME;
+    // Fraction of the lunation at the start and the end of the day, 0 being new moon
+    let start = (days / SYNODIC_MONTH_DAYS).rem_euclid(1.0);
+    let end = start + 1.0 / SYNODIC_MONTH_DAYS;
+    let falls_on = |phase: f64| (start..end).contains(&phase);
+
+    if falls_on(0.0) || falls_on(1.0) {
+        MoonPhase::NewMoon
+    } else if falls_on(0.25) {
+        MoonPhase::FirstQuarter
+    } else if falls_on(0.5) {
+        MoonPhase::FullMoon
+    } else if falls_on(0.75) {
+        MoonPhase::LastQuarter
+    } else if start < 0.25 {
+        MoonPhase::WaxingCrescent
+    } else if start < 0.5 {
+        MoonPhase::WaxingGibbous
+    } else if start < 0.75 {
+        MoonPhase::WaningGibbous
+    } else {
+        MoonPhase::WaningCrescent
+    }
+}
@@ -0,0 +1,198 @@
+//! Bahire Hasab (ባሕረ ሃሳብ) computation of the movable feasts and fasts.
+//!
+//! Every movable observance is a fixed number of days after Nineveh (ጾመ ነነዌ),
+//! which is itself derived from the year's Metqe (መጥቅዕ).
+
+use crate::cache::YearTable;
+use crate::{EthiopianDate, AMETE_ALEM_OFFSET};
+use chrono::{Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Days from Metqe to Tir/Yekatit of the same day number.
+const METQE_TO_NINEVEH_MONTHS: i64 = 120;
+
+/// Movable feasts and fasts of the Ethiopian Orthodox calendar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MovableFeast {
+    Nineveh,
+    AbiyTsom,
+    DebreZeit,
+    Hosanna,
+    Siklet,
+    Fasika,
+    RikbeKahnat,
+    Erget,
+    Paraclete,
+    TsomeHawaryat,
+    TsomeDihnet,
+}
+
+impl MovableFeast {
+    pub const ALL: [MovableFeast; 11] = [
+        MovableFeast::Nineveh,
+        MovableFeast::AbiyTsom,
+        MovableFeast::DebreZeit,
+        MovableFeast::Hosanna,
+        MovableFeast::Siklet,
+        MovableFeast::Fasika,
+        MovableFeast::RikbeKahnat,
+        MovableFeast::Erget,
+        MovableFeast::Paraclete,
+        MovableFeast::TsomeHawaryat,
+        MovableFeast::TsomeDihnet,
+    ];
+
+    /// Number of days after Nineveh on which the feast falls.
+    pub fn days_after_nineveh(&self) -> i64 {
+        match self {
+            MovableFeast::Nineveh => 0,
+            MovableFeast::AbiyTsom => 14,
+            MovableFeast::DebreZeit => 41,
+            MovableFeast::Hosanna => 62,
+            MovableFeast::Siklet => 67,
+            MovableFeast::Fasika => 69,
+            MovableFeast::RikbeKahnat => 93,
+            MovableFeast::Erget => 108,
+            MovableFeast::Paraclete => 118,
+            MovableFeast::TsomeHawaryat => 119,
+            MovableFeast::TsomeDihnet => 121,
+        }
+    }
+
+    /// Stable snake_case identifier of the feast.
+    pub fn id(&self) -> &'static str {
+        match self {
+            MovableFeast::Nineveh => "nineveh",
+            MovableFeast::AbiyTsom => "abiy_tsom",
+            MovableFeast::DebreZeit => "debre_zeit",
+            MovableFeast::Hosanna => "hosanna",
+            MovableFeast::Siklet => "siklet",
+            MovableFeast::Fasika => "fasika",
+            MovableFeast::RikbeKahnat => "rikbe_kahnat",
+            MovableFeast::Erget => "erget",
+            MovableFeast::Paraclete => "paraclete",
+            MovableFeast::TsomeHawaryat => "tsome_hawaryat",
+            MovableFeast::TsomeDihnet => "tsome_dihnet",
+        }
+    }
+
+    pub fn amharic_name(&self) -> &'static str {
+        match self {
+            MovableFeast::Nineveh => "ጾመ ነነዌ",
+            MovableFeast::AbiyTsom => "ዐቢይ ጾም",
+            MovableFeast::DebreZeit => "ደብረ ዘይት",
+            MovableFeast::Hosanna => "ሆሳዕና",
+            MovableFeast::Siklet => "ስቅለት",
+            MovableFeast::Fasika => "ፋሲካ",
+            MovableFeast::RikbeKahnat => "ርክበ ካህናት",
+            MovableFeast::Erget => "ዕርገት",
+            MovableFeast::Paraclete => "ጰራቅሊጦስ",
+            MovableFeast::TsomeHawaryat => "ጾመ ሐዋርያት",
+            MovableFeast::TsomeDihnet => "ጾመ ድኅነት",
+        }
+    }
+
+    pub fn english_name(&self) -> &'static str {
+        match self {
+            MovableFeast::Nineveh => "Fast of Nineveh",
+            MovableFeast::AbiyTsom => "Great Lent",
+            MovableFeast::DebreZeit => "Debre Zeit",
+            MovableFeast::Hosanna => "Palm Sunday",
+            MovableFeast::Siklet => "Good Friday",
+            MovableFeast::Fasika => "Easter",
+            MovableFeast::RikbeKahnat => "Rikbe Kahnat",
+            MovableFeast::Erget => "Ascension",
+            MovableFeast::Paraclete => "Pentecost",
+            MovableFeast::TsomeHawaryat => "Fast of the Apostles",
+            MovableFeast::TsomeDihnet => "Fast of Salvation",
+        }
+    }
+}
+
+/// A movable feast resolved for a specific year, with both calendar representations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovableFeastDate {
+    pub feast: MovableFeast,
+    pub name_amharic: String,
+    pub name_english: String,
+    pub ethiopian: EthiopianDate,
+    pub gregorian: NaiveDate,
+}
+
+/// Returns the Metqe (መጥቅዕ) of an Ethiopian year as a date in Meskerem or Tikimt.
+pub fn metqe(year: usize) -> EthiopianDate {
+    let wenber = (year + AMETE_ALEM_OFFSET - 1) % 19;
+    let metqe = match (wenber * 19) % 30 {
+        0 => 30,
+        m => m,
+    };
+
+    if metqe > 14 {
+        EthiopianDate::new(year, 1, metqe)
+    } else {
+        EthiopianDate::new(year, 2, metqe)
+    }
+}
+
+/// Returns the Gregorian date of Nineveh for an Ethiopian year.
+fn nineveh_gregorian(year: usize) -> Option<NaiveDate> {
+    let metqe = metqe(year);
+    let metqe_gregorian = metqe.to_gregorian()?;
+
+    // Tewsak (ተውሳክ) pushes Nineveh onto the Monday following the Metqe day.
+    let tewsak = match metqe.weekday() {
+        0 => 7,
+        1 => 6,
+        2 => 5,
+        3 => 4,
+        4 => 3,
+        5 => 2,
+        _ => 8,
+    };
+
+    Some(metqe_gregorian + Duration::days(METQE_TO_NINEVEH_MONTHS + tewsak))
+}
+
+/// Returns the Gregorian date of a movable feast in the given Ethiopian year.
+pub fn movable_feast_gregorian(year: usize, feast: MovableFeast) -> Option<NaiveDate> {
+    nineveh_gregorian(year).map(|nineveh| nineveh + Duration::days(feast.days_after_nineveh()))
+}
+
+/// Returns the Ethiopian date of a movable feast in the given Ethiopian year.
+pub fn movable_feast(year: usize, feast: MovableFeast) -> Option<EthiopianDate> {
+    movable_feast_gregorian(year, feast).and_then(EthiopianDate::from_naive_date)
+}
+
+/// Returns the Gregorian date of Fasika (Ethiopian Easter) in the given Ethiopian year.
+pub fn fasika_gregorian(year: usize) -> Option<NaiveDate> {
+    movable_feast_gregorian(year, MovableFeast::Fasika)
+}
+
+/// `movable_feasts` of recent years.
+static MOVABLE_FEASTS: YearTable<Vec<MovableFeastDate>> = YearTable::new();
+
+pub(crate) fn clear_cache() {
+    MOVABLE_FEASTS.clear();
+}
+
+/// Returns every movable feast of an Ethiopian year in chronological order.
+pub fn movable_feasts(year: usize) -> Vec<MovableFeastDate> {
+    MOVABLE_FEASTS.get(year, compute_movable_feasts)
+}
+
+fn compute_movable_feasts(year: usize) -> Vec<MovableFeastDate> {
+    MovableFeast::ALL
+        .iter()
+        .filter_map(|feast| {
+            let gregorian = movable_feast_gregorian(year, *feast)?;
+            Some(MovableFeastDate {
+                feast: *feast,
+                name_amharic: feast.amharic_name().to_string(),
+                name_english: feast.english_name().to_string(),
+                ethiopian: EthiopianDate::from_naive_date(gregorian)?,
+                gregorian,
+            })
+        })
+        .collect()
+}
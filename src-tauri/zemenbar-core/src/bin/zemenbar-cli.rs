@@ -0,0 +1,217 @@
+//! `zemenbar-cli`: conversions and lookups from the terminal, for scripts and shell users.
+//!
+//! Shares the calendar code of the app, but reads no app data: holidays are the
+//! built-in ones, and "today" is taken in Addis Ababa time. Install it with
+//! `cargo install --path src-tauri/zemenbar-core`; the binary is not called `zemenbar`
+//! so it cannot overwrite the app's in the workspace target directory, but the README
+//! shows how to link it under that name.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::process::ExitCode;
+use zemenbar_core::locale::Language;
+use zemenbar_core::{holidays, launcher, CalendarMonth, EthiopianDate};
+
+const USAGE: &str = "Usage: zemenbar <command> [--json] [--geez] [--amharic]
+       zemenbar --alfred [query] [--amharic]
+
+Commands:
+  today                   Today's Ethiopian date
+  convert YYYY-MM-DD      Gregorian date to Ethiopian
+  convert-ec YYYY-MM-DD   Ethiopian date to Gregorian
+  month YEAR MONTH        Ethiopian month grid
+  holidays YEAR           Holidays of an Ethiopian year
+
+Options:
+  --json      Print JSON instead of text
+  --geez      Write numbers in Geez numerals
+  --amharic   Use Amharic names
+  --alfred    Print Alfred/Raycast Script Filter JSON: today and the upcoming
+              holidays, or conversions and holidays matching the query";
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Options {
+    json: bool,
+    geez: bool,
+    amharic: bool,
+    alfred: bool,
+}
+
+impl Options {
+    fn language(&self) -> Language {
+        if self.amharic { Language::Amharic } else { Language::English }
+    }
+
+    fn number(&self, number: usize) -> String {
+        if self.geez { EthiopianDate::to_geez_number(number) } else { number.to_string() }
+    }
+
+    /// "Tuesday, Tahsas 14, 2018 E.C." in the chosen language and numerals.
+    fn date_text(&self, date: &EthiopianDate) -> String {
+        let template = match (self.amharic, self.geez) {
+            (false, false) => "%A, %B %e, %Y %C",
+            (false, true) => "%A, %B %Od, %OY %C",
+            (true, false) => "%EA፣ %EB %e %Y %EC",
+            (true, true) => "%EA፣ %EB %Od %OY %EC",
+        };
+        date.format(template)
+    }
+}
+
+/// A date in both calendars, as printed by `today`, `convert` and `convert-ec`.
+#[derive(Serialize)]
+struct DateOutput {
+    ethiopian: EthiopianDate,
+    /// `YYYY-MM-DD`.
+    gregorian: String,
+    text: String,
+}
+
+impl DateOutput {
+    fn new(date: EthiopianDate, options: &Options) -> Result<Self, String> {
+        let gregorian = date
+            .to_gregorian()
+            .ok_or_else(|| format!("Failed to convert {} to Gregorian", date))?;
+        Ok(Self {
+            text: options.date_text(&date),
+            gregorian: gregorian.format("%Y-%m-%d").to_string(),
+            ethiopian: date,
+        })
+    }
+}
+
+fn print_json(value: &impl Serialize) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value).map_err(|e| format!("Failed to serialize output: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+fn print_date(output: DateOutput, options: &Options) -> Result<(), String> {
+    if options.json {
+        return print_json(&output);
+    }
+    println!("{} ({})", output.text, output.gregorian);
+    Ok(())
+}
+
+fn parse_number(text: Option<&String>, what: &str) -> Result<usize, String> {
+    let text = text.ok_or_else(|| format!("Missing {}", what))?;
+    text.parse().map_err(|_| format!("Invalid {}: {}", what, text))
+}
+
+fn print_month(year: usize, month: usize, options: &Options) -> Result<(), String> {
+    if year == 0 || !(1..=13).contains(&month) {
+        return Err(format!("Invalid Ethiopian month: {}-{}", year, month));
+    }
+    let calendar = CalendarMonth::localized(year, month, 0, options.language());
+    if options.json {
+        return print_json(&calendar);
+    }
+
+    let era = if options.amharic { "ዓ.ም." } else { "E.C." };
+    println!("{} {} {}", calendar.month_name, options.number(year), era);
+    let headers: Vec<String> = calendar
+        .weekday_names
+        .iter()
+        .map(|name| format!("{:>3} ", name.chars().take(if options.amharic { 2 } else { 3 }).collect::<String>()))
+        .collect();
+    println!("{}", headers.concat().trim_end());
+
+    let mut line = "    ".repeat(calendar.first_day_weekday);
+    for day in &calendar.days {
+        let marker = if day.holidays.is_empty() { ' ' } else { '*' };
+        line.push_str(&format!("{:>3}{}", options.number(day.day), marker));
+        if day.weekday == 6 {
+            println!("{}", line.trim_end());
+            line.clear();
+        }
+    }
+    if !line.is_empty() {
+        println!("{}", line.trim_end());
+    }
+
+    for day in calendar.days.iter().filter(|day| !day.holidays.is_empty()) {
+        for holiday in &day.holidays {
+            let name = if options.amharic { &holiday.name_amharic } else { &holiday.name_english };
+            println!("* {:>2} {}", options.number(day.day), name);
+        }
+    }
+    Ok(())
+}
+
+fn print_holidays(year: usize, options: &Options) -> Result<(), String> {
+    if year == 0 {
+        return Err("Invalid year: 0".to_string());
+    }
+    let holidays: Vec<holidays::Holiday> = holidays::holidays_for_year(year).into_iter().filter(|holiday| holiday.is_shown()).collect();
+    if options.json {
+        return print_json(&holidays);
+    }
+
+    for holiday in &holidays {
+        let date = if options.amharic {
+            format!("{} {}", holiday.date.amharic_month(), options.number(holiday.date.day))
+        } else {
+            format!("{} {}", holiday.date.english_month(), options.number(holiday.date.day))
+        };
+        let gregorian = holiday.date.to_gregorian().map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default();
+        let name = if options.amharic { &holiday.name_amharic } else { &holiday.name_english };
+        let approximate = if holiday.is_approximate { " (approximate)" } else { "" };
+        println!("{:<12} {}  {}{}", date, gregorian, name, approximate);
+    }
+    Ok(())
+}
+
+fn run(args: Vec<String>) -> Result<(), String> {
+    let mut options = Options::default();
+    let mut positional = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--geez" => options.geez = true,
+            "--amharic" => options.amharic = true,
+            "--alfred" => options.alfred = true,
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return Ok(());
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option: {}", flag)),
+            _ => positional.push(arg),
+        }
+    }
+
+    if options.alfred {
+        let query = positional.join(" ");
+        return print_json(&launcher::script_filter(Some(&query), options.language()));
+    }
+
+    let Some((command, rest)) = positional.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    match command.as_str() {
+        "today" => print_date(DateOutput::new(EthiopianDate::today(), &options)?, &options),
+        "convert" => {
+            let text = rest.first().ok_or_else(|| "Missing Gregorian date (YYYY-MM-DD)".to_string())?;
+            let gregorian = NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(|e| format!("Invalid date {}: {}", text, e))?;
+            let date = EthiopianDate::from_naive_date(gregorian).ok_or_else(|| format!("Failed to convert {}", text))?;
+            print_date(DateOutput::new(date, &options)?, &options)
+        }
+        "convert-ec" => {
+            let text = rest.first().ok_or_else(|| "Missing Ethiopian date (YYYY-MM-DD)".to_string())?;
+            print_date(DateOutput::new(text.parse()?, &options)?, &options)
+        }
+        "month" => print_month(parse_number(rest.first(), "year")?, parse_number(rest.get(1), "month")?, &options),
+        "holidays" => print_holidays(parse_number(rest.first(), "year")?, &options),
+        other => Err(format!("Unknown command: {}\n\n{}", other, USAGE)),
+    }
+}
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Birthdays and anniversaries, stored in `birthdays.json` in the app data directory.
+//!
+//! Entries recur every year on their Ethiopian date, with Pagumē 6 birthdays following
+//! the `Pagume6Policy` from the settings, and count their age in Ethiopian years. Like events, they are kept in memory once
+//! loaded and written back on every change.
+
+use crate::locale::Language;
+use crate::{strings, EthiopianDate, EthiopianDateInput, Pagume6Policy};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BirthdayKind {
+    #[default]
+    Birthday,
+    Anniversary,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Birthday {
+    pub id: String,
+    pub name: String,
+    /// Date of birth, or of the wedding or other occasion for anniversaries.
+    #[serde(with = "crate::date_serde::compact")]
+    pub date: EthiopianDate,
+    #[serde(default)]
+    pub kind: BirthdayKind,
+    /// Set on entries imported from Contacts, so that syncing again updates them instead of adding duplicates.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<String>,
+}
+
+/// Birthday fields sent by the frontend when adding or updating an entry.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BirthdayInput {
+    pub name: String,
+    pub date: EthiopianDateInput,
+    #[serde(default)]
+    pub kind: BirthdayKind,
+}
+
+/// A birthday or anniversary falling on a given day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Celebration {
+    pub id: String,
+    pub name: String,
+    pub kind: BirthdayKind,
+    /// Ethiopian years completed on that day.
+    pub years: usize,
+}
+
+impl Celebration {
+    /// E.g. "Abebe turns ፴ today", in `language`.
+    pub fn message(&self, language: Language, use_geez_numbers: bool) -> String {
+        let key = match self.kind {
+            BirthdayKind::Birthday => "birthday_turns",
+            BirthdayKind::Anniversary => "anniversary_years",
+        };
+        let years = if use_geez_numbers { EthiopianDate::to_geez_number(self.years) } else { self.years.to_string() };
+        strings::label(language, key).replace("{name}", &self.name).replace("{years}", &years)
+    }
+}
+
+static BIRTHDAYS: Mutex<Vec<Birthday>> = Mutex::new(Vec::new());
+
+impl BirthdayInput {
+    fn into_birthday(self, id: String) -> Result<Birthday, String> {
+        let name = self.name.trim().to_string();
+        if name.is_empty() {
+            return Err("Name cannot be empty".to_string());
+        }
+        Ok(Birthday {
+            id,
+            name,
+            date: self.date.to_date()?,
+            kind: self.kind,
+            contact_id: None,
+        })
+    }
+}
+
+fn new_birthday_id() -> String {
+    let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    format!("bday-{:x}", nanos)
+}
+
+/// Reads the birthdays store into memory. A missing file simply means no entries yet.
+pub fn load_birthdays(path: &Path) -> Result<(), String> {
+    let birthdays = if path.exists() {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read birthdays file: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse birthdays: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let mut current = BIRTHDAYS.lock().map_err(|e| format!("Failed to lock birthdays: {}", e))?;
+    *current = birthdays;
+    Ok(())
+}
+
+/// Applies `change` to the in-memory entries and persists the result.
+fn modify_birthdays<T>(path: &Path, change: impl FnOnce(&mut Vec<Birthday>) -> Result<T, String>) -> Result<T, String> {
+    let mut current = BIRTHDAYS.lock().map_err(|e| format!("Failed to lock birthdays: {}", e))?;
+    let mut birthdays = current.clone();
+    let result = change(&mut birthdays)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create birthdays directory: {}", e))?;
+    }
+    let content =
+        serde_json::to_string_pretty(&birthdays).map_err(|e| format!("Failed to serialize birthdays: {}", e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write birthdays file: {}", e))?;
+
+    *current = birthdays;
+    Ok(result)
+}
+
+pub fn list_birthdays() -> Vec<Birthday> {
+    let mut birthdays = BIRTHDAYS.lock().map(|birthdays| birthdays.clone()).unwrap_or_default();
+    birthdays.sort_by_key(|birthday| (birthday.date.month, birthday.date.day));
+    birthdays
+}
+
+pub fn add_birthday(path: &Path, input: BirthdayInput) -> Result<Birthday, String> {
+    let birthday = input.into_birthday(new_birthday_id())?;
+    modify_birthdays(path, |birthdays| {
+        birthdays.push(birthday.clone());
+        Ok(birthday)
+    })
+}
+
+pub fn update_birthday(path: &Path, id: &str, input: BirthdayInput) -> Result<Birthday, String> {
+    let mut birthday = input.into_birthday(id.to_string())?;
+    modify_birthdays(path, |birthdays| {
+        let existing = birthdays
+            .iter_mut()
+            .find(|existing| existing.id == id)
+            .ok_or_else(|| format!("No birthday with id {}", id))?;
+        birthday.contact_id = existing.contact_id.take();
+        *existing = birthday.clone();
+        Ok(birthday)
+    })
+}
+
+/// A contact's Gregorian birthday, as read from Contacts.
+#[derive(Debug, Clone)]
+pub struct ContactBirthday {
+    /// Contacts identifier, kept on the imported entry so later syncs update it.
+    pub contact_id: String,
+    pub name: String,
+    pub birthday: NaiveDate,
+}
+
+/// Birthdays found in Contacts, and how many contacts had a birthday without a year.
+#[derive(Debug, Clone, Default)]
+pub struct ContactBirthdays {
+    pub birthdays: Vec<ContactBirthday>,
+    pub without_year: usize,
+}
+
+/// Outcome of a Contacts sync.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContactsSyncResult {
+    pub added: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Contacts whose birthday has no year, which cannot be converted.
+    pub skipped_without_year: usize,
+}
+
+/// Merges birthdays from Contacts into the store.
+///
+/// An entry already imported from the same contact is updated in place. Otherwise a
+/// manually added birthday with the same name and date is linked to the contact
+/// rather than duplicated. Entries are never deleted, even when the contact is gone.
+pub fn sync_contact_birthdays(path: &Path, contacts: ContactBirthdays) -> Result<ContactsSyncResult, String> {
+    modify_birthdays(path, |birthdays| {
+        let mut result = ContactsSyncResult {
+            skipped_without_year: contacts.without_year,
+            ..Default::default()
+        };
+
+        for contact in contacts.birthdays {
+            let Some(date) = EthiopianDate::from_naive_date(contact.birthday) else {
+                continue;
+            };
+            let same_contact = birthdays
+                .iter()
+                .position(|birthday| birthday.contact_id.as_deref() == Some(contact.contact_id.as_str()));
+            let same_person = || {
+                birthdays.iter().position(|birthday| {
+                    birthday.contact_id.is_none()
+                        && birthday.date == date
+                        && birthday.name.trim().to_lowercase() == contact.name.to_lowercase()
+                })
+            };
+
+            match same_contact.or_else(same_person) {
+                Some(index) => {
+                    let existing = &mut birthdays[index];
+                    let linked = existing.contact_id.is_none();
+                    if linked || existing.name != contact.name || existing.date != date {
+                        existing.contact_id = Some(contact.contact_id);
+                        existing.name = contact.name;
+                        existing.date = date;
+                        result.updated += 1;
+                    } else {
+                        result.unchanged += 1;
+                    }
+                }
+                None => {
+                    birthdays.push(Birthday {
+                        // Several entries get created within the same clock tick.
+                        id: format!("{}-{}", new_birthday_id(), result.added),
+                        name: contact.name,
+                        date,
+                        kind: BirthdayKind::Birthday,
+                        contact_id: Some(contact.contact_id),
+                    });
+                    result.added += 1;
+                }
+            }
+        }
+        Ok(result)
+    })
+}
+
+pub fn delete_birthday(path: &Path, id: &str) -> Result<(), String> {
+    modify_birthdays(path, |birthdays| {
+        let count = birthdays.len();
+        birthdays.retain(|birthday| birthday.id != id);
+        if birthdays.len() == count {
+            return Err(format!("No birthday with id {}", id));
+        }
+        Ok(())
+    })
+}
+
+impl Birthday {
+    /// The celebration on `date`, if `date` is one of this entry's anniversaries.
+    pub fn celebration_on(&self, date: &EthiopianDate, policy: Pagume6Policy) -> Option<Celebration> {
+        if *date <= self.date || !self.date.is_anniversary(date, policy) {
+            return None;
+        }
+        Some(Celebration {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            kind: self.kind,
+            years: self.date.age_on(date, policy)?,
+        })
+    }
+}
+
+/// Birthdays and anniversaries celebrated on `date`.
+pub fn celebrations_on(date: &EthiopianDate) -> Vec<Celebration> {
+    let policy = crate::pagume_6_policy();
+    BIRTHDAYS
+        .lock()
+        .map(|birthdays| birthdays.iter().filter_map(|birthday| birthday.celebration_on(date, policy)).collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,79 @@
+//! Working days: Monday to Friday, less the public holidays whose category is shown.
+//!
+//! Observances that are not days off (`is_public == false`) and hidden categories do
+//! not count, so turning off Muslim holidays also makes Eid a working day here. Moon
+//! sighted holidays are taken on their expected date.
+
+use crate::{holidays, EthiopianDate};
+use std::collections::{HashMap, HashSet};
+
+/// Public holidays of the years looked at so far, as (month, day), fetched a year at a time.
+#[derive(Default)]
+struct DaysOff {
+    years: HashMap<usize, HashSet<(usize, usize)>>,
+}
+
+impl DaysOff {
+    fn is_working_day(&mut self, date: &EthiopianDate) -> bool {
+        if matches!(date.weekday(), 0 | 6) {
+            return false;
+        }
+        let holidays = self.years.entry(date.year).or_insert_with(|| {
+            holidays::holidays_for_year(date.year)
+                .into_iter()
+                .filter(|holiday| holiday.is_public && holiday.is_shown())
+                .map(|holiday| (holiday.date.month, holiday.date.day))
+                .collect()
+        });
+        !holidays.contains(&(date.month, date.day))
+    }
+}
+
+/// Whether `date` is a weekday that is not a shown public holiday.
+pub fn is_working_day(date: &EthiopianDate) -> bool {
+    DaysOff::default().is_working_day(date)
+}
+
+/// `date` when it is a working day, otherwise the closest working day before it.
+pub fn working_day_on_or_before(date: &EthiopianDate) -> Option<EthiopianDate> {
+    step_to_working_day(date, -1)
+}
+
+/// `date` when it is a working day, otherwise the closest working day after it.
+pub fn working_day_on_or_after(date: &EthiopianDate) -> Option<EthiopianDate> {
+    step_to_working_day(date, 1)
+}
+
+fn step_to_working_day(date: &EthiopianDate, step: i64) -> Option<EthiopianDate> {
+    let mut days_off = DaysOff::default();
+    let mut current = date.clone();
+    while !days_off.is_working_day(&current) {
+        current = current.add_days(step)?;
+    }
+    Some(current)
+}
+
+/// Working days after `from` up to and including `to`; negative when `to` is earlier.
+pub fn working_days_between(from: &EthiopianDate, to: &EthiopianDate) -> i64 {
+    let (start, end, sign) = if from <= to { (from, to, 1) } else { (to, from, -1) };
+    let mut days_off = DaysOff::default();
+    let count = EthiopianDate::iter_days(start.succ(), end.clone())
+        .filter(|date| days_off.is_working_day(date))
+        .count() as i64;
+    count * sign
+}
+
+/// The date `days` working days after `date` (before it when negative); `date` itself
+/// when `days` is 0, even on a weekend or holiday.
+pub fn add_working_days(date: &EthiopianDate, days: i64) -> Option<EthiopianDate> {
+    let mut days_off = DaysOff::default();
+    let mut current = date.clone();
+    let mut remaining = days.unsigned_abs();
+    while remaining > 0 {
+        current = current.add_days(days.signum())?;
+        if days_off.is_working_day(&current) {
+            remaining -= 1;
+        }
+    }
+    Some(current)
+}
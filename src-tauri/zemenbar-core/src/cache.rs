@@ -0,0 +1,55 @@
+//! Memoized per-year tables, so navigating months and refreshing the tray and tooltips
+//! does not rerun Bahire Hasab and the Hijri calendar for the same year again and again.
+//!
+//! Only results that depend on nothing but the year are kept; holiday packs and custom
+//! holidays, which the user can change, are added on top on every call. Date conversions
+//! are plain Julian Day Number arithmetic and are not worth caching.
+
+use std::sync::Mutex;
+
+/// Number of years each table keeps; the month grid, tray and feed touch only a few.
+const YEARS_KEPT: usize = 8;
+
+/// Results of a per-year computation, most recently used first.
+pub(crate) struct YearTable<T> {
+    years: Mutex<Vec<(usize, T)>>,
+}
+
+impl<T: Clone> YearTable<T> {
+    pub(crate) const fn new() -> Self {
+        Self { years: Mutex::new(Vec::new()) }
+    }
+
+    /// The cached value for `year`, computing and keeping it on a miss.
+    pub(crate) fn get(&self, year: usize, compute: impl FnOnce(usize) -> T) -> T {
+        if let Ok(mut years) = self.years.lock() {
+            if let Some(index) = years.iter().position(|(cached, _)| *cached == year) {
+                let entry = years.remove(index);
+                let value = entry.1.clone();
+                years.insert(0, entry);
+                return value;
+            }
+        }
+
+        // Computed without the lock held, as one table's computation may read another.
+        let value = compute(year);
+        if let Ok(mut years) = self.years.lock() {
+            years.retain(|(cached, _)| *cached != year);
+            years.insert(0, (year, value.clone()));
+            years.truncate(YEARS_KEPT);
+        }
+        value
+    }
+
+    pub(crate) fn clear(&self) {
+        if let Ok(mut years) = self.years.lock() {
+            years.clear();
+        }
+    }
+}
+
+/// Drops every cached table; they are rebuilt on next use.
+pub fn clear_caches() {
+    crate::bahire_hasab::clear_cache();
+    crate::holidays::clear_cache();
+}
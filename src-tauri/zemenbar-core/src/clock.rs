@@ -0,0 +1,98 @@
+//! Ethiopian time of day, counted in two runs of twelve hours from daybreak.
+//!
+//! Daybreak is 12 o'clock, so an hour after it is 1 o'clock in the day and twelve
+//! hours after it 12 o'clock at night. By default daybreak is fixed at 6:00 local time;
+//! the solar mode puts it at the actual sunrise for a configured location instead,
+//! as the traditional reckoning does, falling back to 6:00 on days without a sunrise.
+
+use crate::astronomy;
+use chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const MINUTES_PER_DAY: i64 = 24 * 60;
+
+/// Where the count of hours starts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockMode {
+    /// 6:00 local time.
+    #[default]
+    Fixed,
+    /// Sunrise at `ClockSettings.latitude` and `longitude`.
+    Solar,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ClockSettings {
+    pub mode: ClockMode,
+    /// Degrees north, used by the solar mode.
+    pub latitude: f64,
+    /// Degrees east, used by the solar mode.
+    pub longitude: f64,
+}
+
+impl Default for ClockSettings {
+    fn default() -> Self {
+        Self {
+            mode: ClockMode::Fixed,
+            latitude: astronomy::ADDIS_ABABA_LATITUDE,
+            longitude: astronomy::ADDIS_ABABA_LONGITUDE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClockPeriod {
+    /// The twelve hours from daybreak (ቀን).
+    Day,
+    /// The twelve hours after those (ሌሊት).
+    Night,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EthiopianTime {
+    /// 1 to 12, with 12 at daybreak and at nightfall.
+    pub hour: u32,
+    pub minute: u32,
+    pub period: ClockPeriod,
+    /// Local time the hours are counted from.
+    pub daybreak: NaiveTime,
+}
+
+impl fmt::Display for EthiopianTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// Local time of daybreak on `date` in `timezone`.
+pub fn daybreak(settings: &ClockSettings, date: NaiveDate, timezone: Tz) -> NaiveTime {
+    let fixed = NaiveTime::from_hms_opt(6, 0, 0).expect("6:00 is a valid time");
+    match settings.mode {
+        ClockMode::Fixed => fixed,
+        ClockMode::Solar => astronomy::sunrise_utc(date, settings.latitude, settings.longitude)
+            .map(|sunrise| Utc.from_utc_datetime(&sunrise).with_timezone(&timezone).time())
+            .unwrap_or(fixed),
+    }
+}
+
+/// Ethiopian time at `now`.
+pub fn ethiopian_time(now: DateTime<Tz>, settings: &ClockSettings) -> EthiopianTime {
+    let local = now.naive_local();
+    let daybreak = daybreak(settings, local.date(), now.timezone());
+    // Before daybreak it is still the night that followed the previous day
+    let seconds = local.time().signed_duration_since(daybreak).num_seconds();
+    let minutes = seconds.div_euclid(60).rem_euclid(MINUTES_PER_DAY);
+    let hour = (minutes / 60 % 12) as u32;
+
+    EthiopianTime {
+        hour: if hour == 0 { 12 } else { hour },
+        minute: (minutes % 60) as u32,
+        period: if minutes < MINUTES_PER_DAY / 2 { ClockPeriod::Day } else { ClockPeriod::Night },
+        daybreak,
+    }
+}
@@ -0,0 +1,148 @@
+//! User-defined holidays and observances from `custom_holidays.json` in the app data directory.
+//!
+//! Each entry has a name and either an Ethiopian `month`/`day` or a `rule`:
+//!
+//! ```json
+//! [
+//!   { "name": "Company Day", "name_amharic": "የድርጅቱ ቀን", "month": 3, "day": 5, "is_public": true, "category": "national" },
+//!   { "name": "Christmas", "rule": { "type": "gregorian", "month": 12, "day": 25 } },
+//!   { "name": "Day after Fasika", "rule": { "type": "feast", "feast": "fasika", "offset_days": 1 } },
+//!   { "name": "Staff Picnic", "rule": { "type": "nth_weekday", "month": 10, "weekday": 6, "week": -1 } }
+//! ]
+//! ```
+//!
+//! Entries are in the cultural category unless they give another `category`.
+//! The file is read at startup and on `reload_custom_holidays`, and its entries are
+//! merged into every holiday query alongside the built-in holidays and packs.
+
+use crate::bahire_hasab::{self, MovableFeast};
+use crate::holidays::HolidayCategory;
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// How a custom holiday's date is found in each Ethiopian year.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CustomHolidayRule {
+    /// The same Gregorian month and day every year, within the Ethiopian year.
+    Gregorian { month: u32, day: u32 },
+    /// Days after (or, when negative, before) a movable feast.
+    Feast { feast: MovableFeast, offset_days: i64 },
+    /// The `week`-th `weekday` (0 = Sunday) of an Ethiopian month; `week` -1 is the last one.
+    NthWeekday { month: usize, weekday: usize, week: i32 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomHoliday {
+    /// Stable identifier; derived from the entry's position in the file when missing.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    /// Shown in Ethiopic-script languages; `name` is used when missing.
+    #[serde(default)]
+    pub name_amharic: Option<String>,
+    #[serde(default)]
+    pub month: Option<usize>,
+    #[serde(default)]
+    pub day: Option<usize>,
+    #[serde(default)]
+    pub rule: Option<CustomHolidayRule>,
+    #[serde(default)]
+    pub is_public: bool,
+    #[serde(default)]
+    pub category: HolidayCategory,
+}
+
+static CUSTOM_HOLIDAYS: Mutex<Vec<CustomHoliday>> = Mutex::new(Vec::new());
+
+impl CustomHoliday {
+    /// Checks the name and that the entry has exactly one valid date or rule.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Custom holiday name cannot be empty".to_string());
+        }
+        match (self.month, self.day, &self.rule) {
+            // 2015 is a leap year, so Pagumē 6 passes this check.
+            (Some(month), Some(day), None) if EthiopianDate::new(2015, month, day).is_valid() => Ok(()),
+            (Some(month), Some(day), None) => Err(format!("Invalid month and day for {}: {}/{}", self.name, month, day)),
+            (None, None, Some(CustomHolidayRule::Gregorian { month, day })) => {
+                // 2024 is a leap year, so February 29 passes this check.
+                chrono::NaiveDate::from_ymd_opt(2024, *month, *day)
+                    .map(|_| ())
+                    .ok_or_else(|| format!("Invalid Gregorian month and day for {}: {}/{}", self.name, month, day))
+            }
+            (None, None, Some(CustomHolidayRule::Feast { .. })) => Ok(()),
+            (None, None, Some(CustomHolidayRule::NthWeekday { month, weekday, week })) => {
+                if !(1..=13).contains(month) || *weekday > 6 || !(week == &-1 || (1..=5).contains(week)) {
+                    return Err(format!("Invalid weekday rule for {}", self.name));
+                }
+                Ok(())
+            }
+            _ => Err(format!("{} needs either a month and day or a rule", self.name)),
+        }
+    }
+
+    /// The entry's date in the Ethiopian `year`, if it has one that year.
+    pub fn date_in(&self, year: usize) -> Option<EthiopianDate> {
+        match (self.month, self.day, &self.rule) {
+            (Some(month), Some(day), None) => Some(EthiopianDate::new(year, month, day)).filter(|date| date.is_valid()),
+            (_, _, Some(CustomHolidayRule::Gregorian { month, day })) => {
+                // The Ethiopian year spans September of Gregorian year + 7 to September of year + 8.
+                (year as i32 + 7..=year as i32 + 8)
+                    .filter_map(|gregorian_year| EthiopianDate::from_gregorian(gregorian_year, *month, *day))
+                    .find(|date| date.year == year)
+            }
+            (_, _, Some(CustomHolidayRule::Feast { feast, offset_days })) => {
+                bahire_hasab::movable_feast(year, *feast)?.add_days(*offset_days)
+            }
+            (_, _, Some(CustomHolidayRule::NthWeekday { month, weekday, week })) => {
+                nth_weekday(year, *month, *weekday, *week)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn nth_weekday(year: usize, month: usize, weekday: usize, week: i32) -> Option<EthiopianDate> {
+    let first = EthiopianDate::new(year, month, 1);
+    let days_in_month = first.days_in_month();
+    let day = if week < 0 {
+        let last = EthiopianDate::new(year, month, days_in_month);
+        days_in_month - (last.weekday() + 7 - weekday) % 7
+    } else {
+        1 + (weekday + 7 - first.weekday()) % 7 + (week as usize - 1) * 7
+    };
+    (day >= 1 && day <= days_in_month).then(|| EthiopianDate::new(year, month, day))
+}
+
+fn read_custom_holidays(path: &Path) -> Result<Vec<CustomHoliday>, String> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read custom holidays file: {}", e))?;
+    let mut holidays: Vec<CustomHoliday> =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse custom holidays: {}", e))?;
+    for (index, holiday) in holidays.iter_mut().enumerate() {
+        holiday.validate()?;
+        if holiday.id.as_deref().is_none_or(|id| id.trim().is_empty()) {
+            holiday.id = Some(format!("custom_{}", index + 1));
+        }
+    }
+    Ok(holidays)
+}
+
+/// Reads `custom_holidays.json` into memory. A missing file simply means no custom holidays.
+///
+/// An invalid file is reported and leaves the previously loaded entries in place.
+pub fn load_custom_holidays(path: &Path) -> Result<Vec<CustomHoliday>, String> {
+    let holidays = read_custom_holidays(path)?;
+    let mut current = CUSTOM_HOLIDAYS.lock().map_err(|e| format!("Failed to lock custom holidays: {}", e))?;
+    *current = holidays.clone();
+    Ok(holidays)
+}
+
+pub fn custom_holidays() -> Vec<CustomHoliday> {
+    CUSTOM_HOLIDAYS.lock().map(|holidays| holidays.clone()).unwrap_or_default()
+}
@@ -0,0 +1,265 @@
+//! Validation and completion of typed Ethiopian dates.
+//!
+//! Every date input in the frontend goes through here on each keystroke, so that
+//! Arabic digits, Geez numerals and month names are all understood the same way.
+//! Inputs are split on spaces and the usual separators (`/`, `-`, `.`, `,`, `፣`, `፡`).
+//! Numeric-only input is read as day/month/year, or year-month-day when the first
+//! number has four digits, including while the month and day are still being typed,
+//! so `2017` and `2017-0` are partial dates on the way to `2017-04-14`.
+
+use crate::{months, EthiopianDate};
+use serde::{Deserialize, Serialize};
+
+/// Where the user is in typing a date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputState {
+    Empty,
+    /// Valid so far, but the year, month or day is still missing.
+    Partial,
+    Complete,
+    Invalid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateInputResult {
+    pub state: InputState,
+    pub year: Option<usize>,
+    pub month: Option<usize>,
+    pub day: Option<usize>,
+    /// The date, once all parts are present and valid.
+    pub date: Option<EthiopianDate>,
+    /// Canonical `YYYY-MM-DD` form of a complete date.
+    pub normalized: Option<String>,
+    pub error: Option<String>,
+}
+
+impl DateInputResult {
+    fn invalid(error: String) -> Self {
+        Self {
+            state: InputState::Invalid,
+            year: None,
+            month: None,
+            day: None,
+            date: None,
+            normalized: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Why a string could not be read as an Ethiopian date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateParseError {
+    Empty,
+    /// A word that is neither a number nor a month name.
+    UnrecognizedToken(String),
+    MultipleMonths,
+    TooManyNumbers,
+    MonthOutOfRange(usize),
+    InvalidYear,
+    DayOutOfRange { day: usize, max: usize },
+    /// Valid so far, but the year, month or day is missing.
+    Incomplete,
+}
+
+impl std::fmt::Display for DateParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DateParseError::Empty => write!(f, "No date given"),
+            DateParseError::UnrecognizedToken(token) => write!(f, "Unrecognized input: {}", token),
+            DateParseError::MultipleMonths => write!(f, "More than one month given"),
+            DateParseError::TooManyNumbers => write!(f, "Too many numbers"),
+            DateParseError::MonthOutOfRange(month) => write!(f, "Month must be between 1 and 13, got {}", month),
+            DateParseError::InvalidYear => write!(f, "Year must be positive"),
+            DateParseError::DayOutOfRange { day, max } => write!(f, "Day must be between 1 and {}, got {}", max, day),
+            DateParseError::Incomplete => write!(f, "Year, month and day are all needed"),
+        }
+    }
+}
+
+impl std::error::Error for DateParseError {}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct PartialDate {
+    year: Option<usize>,
+    month: Option<usize>,
+    day: Option<usize>,
+}
+
+fn parse_number(token: &str) -> Option<usize> {
+    token.parse().ok().or_else(|| EthiopianDate::from_geez_number(token).ok())
+}
+
+fn tokenize(input: &str) -> Vec<&str> {
+    input
+        .split(|c: char| c.is_whitespace() || matches!(c, '/' | '-' | '.' | ',' | '፣' | '፡' | '።'))
+        .filter(|token| !token.is_empty() && *token != "ቀን" && *token != "ዓ" && *token != "ም")
+        .collect()
+}
+
+fn parse_partial(input: &str) -> Result<PartialDate, DateParseError> {
+    let mut parsed = PartialDate::default();
+    let mut numbers = Vec::new();
+
+    for token in tokenize(input) {
+        if let Some(number) = parse_number(token) {
+            numbers.push(number);
+        } else if let Some(month) = months::resolve_month(token) {
+            if parsed.month.replace(month).is_some() {
+                return Err(DateParseError::MultipleMonths);
+            }
+        } else {
+            return Err(DateParseError::UnrecognizedToken(token.to_string()));
+        }
+    }
+
+    if parsed.month.is_some() {
+        // With a month name, small numbers are the day and large ones the year.
+        for number in numbers {
+            if number <= 30 && parsed.day.is_none() {
+                parsed.day = Some(number);
+            } else if parsed.year.is_none() {
+                parsed.year = Some(number);
+            } else {
+                return Err(DateParseError::TooManyNumbers);
+            }
+        }
+    } else {
+        match numbers.as_slice() {
+            [] => {}
+            [year] if *year > 31 => parsed.year = Some(*year),
+            [day] => parsed.day = Some(*day),
+            // A trailing 0 is the first digit of a month or day still being typed
+            [year, month] if *year > 31 => {
+                parsed.year = Some(*year);
+                parsed.month = Some(*month).filter(|month| *month != 0);
+            }
+            [year, month, day] if *year > 31 => {
+                parsed.year = Some(*year);
+                parsed.month = Some(*month);
+                parsed.day = Some(*day).filter(|day| *day != 0);
+            }
+            [day, month] => {
+                parsed.day = Some(*day);
+                parsed.month = Some(*month);
+            }
+            [day, month, year] => {
+                parsed.day = Some(*day);
+                parsed.month = Some(*month);
+                parsed.year = Some(*year);
+            }
+            _ => return Err(DateParseError::TooManyNumbers),
+        }
+    }
+
+    if let Some(month) = parsed.month {
+        if !(1..=13).contains(&month) {
+            return Err(DateParseError::MonthOutOfRange(month));
+        }
+    }
+    if parsed.year == Some(0) {
+        return Err(DateParseError::InvalidYear);
+    }
+    if let Some(day) = parsed.day {
+        // Without a known year, allow Pagumē 6 until the year settles it.
+        let max_day = match (parsed.year, parsed.month) {
+            (Some(year), Some(month)) => EthiopianDate::new(year, month, 1).days_in_month(),
+            (None, Some(13)) => 6,
+            _ => 30,
+        };
+        if day == 0 || day > max_day {
+            return Err(DateParseError::DayOutOfRange { day, max: max_day });
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// Reads a complete date such as `መስከረም ፲፬ ፳፻፲፯`, `Meskerem 14 2017` or `14/01/2017`.
+pub fn parse_date(input: &str) -> Result<EthiopianDate, DateParseError> {
+    let parsed = parse_partial(input)?;
+    match (parsed.year, parsed.month, parsed.day) {
+        (Some(year), Some(month), Some(day)) => Ok(EthiopianDate::new(year, month, day)),
+        (None, None, None) => Err(DateParseError::Empty),
+        _ => Err(DateParseError::Incomplete),
+    }
+}
+
+/// Validates typed input and reports how much of a date it describes.
+pub fn normalize_date_input(input: &str) -> DateInputResult {
+    let parsed = match parse_partial(input) {
+        Ok(parsed) => parsed,
+        Err(e) => return DateInputResult::invalid(e.to_string()),
+    };
+
+    let date = match (parsed.year, parsed.month, parsed.day) {
+        (Some(year), Some(month), Some(day)) => Some(EthiopianDate::new(year, month, day)),
+        _ => None,
+    };
+    let state = if date.is_some() {
+        InputState::Complete
+    } else if parsed.year.is_none() && parsed.month.is_none() && parsed.day.is_none() {
+        InputState::Empty
+    } else {
+        InputState::Partial
+    };
+
+    DateInputResult {
+        state,
+        year: parsed.year,
+        month: parsed.month,
+        day: parsed.day,
+        normalized: date.as_ref().map(|d| d.to_string()),
+        date,
+        error: None,
+    }
+}
+
+/// Fills in whatever the input leaves out from `today` (year, then month; the day defaults to 1).
+pub fn complete_partial_date(input: &str, today: &EthiopianDate) -> Result<EthiopianDate, String> {
+    let parsed = parse_partial(input).map_err(|e| e.to_string())?;
+    let year = parsed.year.unwrap_or(today.year);
+    let month = parsed.month.unwrap_or(today.month);
+    let day = parsed.day.unwrap_or(1);
+
+    let date = EthiopianDate::new(year, month, day);
+    if date.is_valid() {
+        Ok(date)
+    } else {
+        Err(format!("{} {} has only {} days", date.english_month(), year, date.days_in_month()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn year_first_input_is_partial_while_typed() {
+        let steps = [
+            ("2", InputState::Partial),
+            ("20", InputState::Partial),
+            ("2017", InputState::Partial),
+            ("2017-0", InputState::Partial),
+            ("2017-04", InputState::Partial),
+            ("2017-04-1", InputState::Complete),
+            ("2017-04-14", InputState::Complete),
+        ];
+        for (input, state) in steps {
+            let result = normalize_date_input(input);
+            assert_eq!(result.state, state, "{}: {:?}", input, result.error);
+        }
+        assert_eq!(normalize_date_input("2017").year, Some(2017));
+        assert_eq!(normalize_date_input("2017-04").month, Some(4));
+        assert_eq!(normalize_date_input("2017-04-14").normalized.as_deref(), Some("2017-04-14"));
+    }
+
+    #[test]
+    fn day_first_input_is_unchanged() {
+        assert_eq!(normalize_date_input("14").day, Some(14));
+        assert_eq!(normalize_date_input("14/04").month, Some(4));
+        assert_eq!(parse_date("14/04/2017"), Ok(EthiopianDate::new(2017, 4, 14)));
+        assert_eq!(normalize_date_input("14/0").state, InputState::Invalid);
+    }
+}
@@ -0,0 +1,114 @@
+//! Alternative serde representations of `EthiopianDate`, for use with `#[serde(with = "...")]`.
+//!
+//! By default an `EthiopianDate` serializes field by field (`year`, `month`, `day`,
+//! `day_geez`), which is what the frontend reads. Stored data should use one of these
+//! stable forms instead:
+//!
+//! - [`compact`]: a string such as `"2017-04-14#EC"`. The `#EC` suffix marks the
+//!   Ethiopian calendar so the value is never mistaken for a Gregorian ISO date.
+//! - [`named`]: an object with the month name and Gregorian date alongside the numbers,
+//!   meant for files people read. Only `year`, `month` and `day` are read back.
+//!
+//! ```ignore
+//! #[derive(Serialize, Deserialize)]
+//! struct Event {
+//!     #[serde(with = "crate::date_serde::compact")]
+//!     date: EthiopianDate,
+//! }
+//! ```
+
+use crate::EthiopianDate;
+
+/// Suffix marking a compact string as an Ethiopian calendar date.
+pub const COMPACT_SUFFIX: &str = "#EC";
+
+/// Writes the compact form, e.g. `2017-04-14#EC`.
+pub fn to_compact_string(date: &EthiopianDate) -> String {
+    format!("{}{}", date, COMPACT_SUFFIX)
+}
+
+/// Reads the compact form; the `#EC` suffix is required.
+pub fn from_compact_str(text: &str) -> Result<EthiopianDate, String> {
+    let date = text
+        .trim()
+        .strip_suffix(COMPACT_SUFFIX)
+        .ok_or_else(|| format!("Expected an Ethiopian date like 2017-04-14{}, got {}", COMPACT_SUFFIX, text))?;
+    date.parse()
+}
+
+/// `"2017-04-14#EC"`.
+pub mod compact {
+    use crate::EthiopianDate;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(date: &EthiopianDate, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::to_compact_string(date))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<EthiopianDate, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        super::from_compact_str(&text).map_err(serde::de::Error::custom)
+    }
+
+    /// The compact form for `Option<EthiopianDate>`, with `None` as `null`.
+    pub mod option {
+        use crate::EthiopianDate;
+        use serde::{Deserialize, Deserializer, Serializer};
+
+        pub fn serialize<S: Serializer>(date: &Option<EthiopianDate>, serializer: S) -> Result<S::Ok, S::Error> {
+            match date {
+                Some(date) => serializer.serialize_some(&super::super::to_compact_string(date)),
+                None => serializer.serialize_none(),
+            }
+        }
+
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<EthiopianDate>, D::Error> {
+            Option::<String>::deserialize(deserializer)?
+                .map(|text| super::super::from_compact_str(&text).map_err(serde::de::Error::custom))
+                .transpose()
+        }
+    }
+}
+
+/// `{ "year": 2017, "month": 4, "day": 14, "month_name_english": "Tahsas", ... }`.
+pub mod named {
+    use crate::{EthiopianDate, EthiopianDateInput};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct NamedDate {
+        year: usize,
+        month: usize,
+        day: usize,
+        #[serde(default, skip_deserializing)]
+        month_name_amharic: String,
+        #[serde(default, skip_deserializing)]
+        month_name_english: String,
+        /// Gregorian equivalent as `YYYY-MM-DD`.
+        #[serde(default, skip_deserializing)]
+        gregorian: Option<String>,
+    }
+
+    pub fn serialize<S: Serializer>(date: &EthiopianDate, serializer: S) -> Result<S::Ok, S::Error> {
+        NamedDate {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            month_name_amharic: date.amharic_month().to_string(),
+            month_name_english: date.english_month().to_string(),
+            gregorian: date.to_gregorian().map(|g| g.format("%Y-%m-%d").to_string()),
+        }
+        .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<EthiopianDate, D::Error> {
+        let named = NamedDate::deserialize(deserializer)?;
+        EthiopianDateInput {
+            year: named.year,
+            month: named.month,
+            day: named.day,
+        }
+        .to_date()
+        .map_err(serde::de::Error::custom)
+    }
+}
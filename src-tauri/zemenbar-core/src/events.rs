@@ -0,0 +1,305 @@
+//! Personal events kept on Ethiopian dates, stored in `events.json` in the app data directory.
+//!
+//! Events are loaded once at startup and kept in memory, so the month grid can mark
+//! days with events without touching the disk. Every change is written back right away.
+//! Dates are stored in the compact `2017-04-14#EC` form (see `date_serde`).
+//!
+//! A recurring event repeats from its date onwards, in Ethiopian terms:
+//!
+//! - yearly on the same month and day. In years where Pagumē has only five days,
+//!   Pagumē 6 anniversaries follow the `Pagume6Policy` chosen in the settings;
+//! - monthly on the same day number, skipping months that are too short (Pagumē
+//!   for days 7-30, and Pagumē of common years for day 6);
+//! - weekly on the same weekday.
+//!
+//! Each event can ask for reminders some minutes before it starts; an event without
+//! a time starts at midnight. Events also remember when they were last changed, which
+//! CalDAV sync uses to settle edits made on both sides.
+
+use crate::{EthiopianDate, EthiopianDateInput, Pagume6Policy};
+use chrono::{DateTime, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// How an event repeats, anchored on the event's own date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Recurrence {
+    Yearly,
+    Monthly,
+    Weekly,
+}
+
+impl Recurrence {
+    /// Whether a series anchored on `anchor` repeats on `date`; dates before the anchor
+    /// are not checked here.
+    pub fn matches(self, anchor: &EthiopianDate, date: &EthiopianDate, policy: Pagume6Policy) -> bool {
+        match self {
+            Recurrence::Yearly => anchor.is_anniversary(date, policy),
+            Recurrence::Monthly => date.day == anchor.day,
+            Recurrence::Weekly => date.weekday() == anchor.weekday(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Event {
+    pub id: String,
+    #[serde(with = "crate::date_serde::compact")]
+    pub date: EthiopianDate,
+    pub title: String,
+    #[serde(default)]
+    pub notes: String,
+    /// CSS hex color such as `"#3478f6"`.
+    #[serde(default)]
+    pub color: Option<String>,
+    /// `None` for a one-off event.
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    /// Start time in the "today" timezone; `None` for an all-day event.
+    #[serde(default)]
+    pub time: Option<NaiveTime>,
+    /// Minutes before the start at which to remind, e.g. `[1440, 60]` for a day and
+    /// an hour before. Empty means a single reminder when the event starts.
+    #[serde(default)]
+    pub reminder_minutes: Vec<u32>,
+    /// When the event was added or last edited; `None` for events saved before this was recorded.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl Event {
+    /// Whether the event, or one of its repetitions, falls on `date`.
+    pub fn occurs_on(&self, date: &EthiopianDate, policy: Pagume6Policy) -> bool {
+        if *date < self.date {
+            return false;
+        }
+        match self.recurrence {
+            None => *date == self.date,
+            Some(recurrence) => recurrence.matches(&self.date, date, policy),
+        }
+    }
+
+    /// Dates in a month on which the event occurs.
+    pub fn occurrences_in_month(&self, year: usize, month: usize, policy: Pagume6Policy) -> Vec<EthiopianDate> {
+        EthiopianDate::iter_month(year, month).filter(|date| self.occurs_on(date, policy)).collect()
+    }
+}
+
+/// Event fields sent by the frontend when adding or updating an event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventInput {
+    pub date: EthiopianDateInput,
+    pub title: String,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub recurrence: Option<Recurrence>,
+    #[serde(default)]
+    pub time: Option<NaiveTime>,
+    #[serde(default)]
+    pub reminder_minutes: Vec<u32>,
+}
+
+/// Longest reminder lead time accepted, in minutes.
+pub const MAX_REMINDER_MINUTES: u32 = 30 * 24 * 60;
+
+impl EventInput {
+    pub fn into_event(self, id: String) -> Result<Event, String> {
+        let title = self.title.trim().to_string();
+        if title.is_empty() {
+            return Err("Event title cannot be empty".to_string());
+        }
+        let color = self.color.filter(|color| !color.trim().is_empty());
+        if let Some(color) = &color {
+            if !is_hex_color(color) {
+                return Err(format!("Invalid event color: {}", color));
+            }
+        }
+
+        if let Some(minutes) = self.reminder_minutes.iter().find(|minutes| **minutes > MAX_REMINDER_MINUTES) {
+            return Err(format!(
+                "Reminders can be at most {} days ahead, got {} minutes",
+                MAX_REMINDER_MINUTES / (24 * 60),
+                minutes
+            ));
+        }
+        let mut reminder_minutes = self.reminder_minutes;
+        reminder_minutes.sort_unstable_by(|a, b| b.cmp(a));
+        reminder_minutes.dedup();
+
+        Ok(Event {
+            id,
+            date: self.date.to_date()?,
+            title,
+            notes: self.notes,
+            color,
+            recurrence: self.recurrence,
+            time: self.time,
+            reminder_minutes,
+            updated_at: Some(Utc::now()),
+        })
+    }
+}
+
+static EVENTS: Mutex<Vec<Event>> = Mutex::new(Vec::new());
+
+fn is_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .is_some_and(|hex| matches!(hex.len(), 3 | 6) && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub fn new_event_id() -> String {
+    let nanos = Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    format!("evt-{:x}", nanos)
+}
+
+fn write_events(path: &Path, events: &[Event]) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create events directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(events).map_err(|e| format!("Failed to serialize events: {}", e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write events file: {}", e))
+}
+
+/// Reads the event store into memory. A missing file simply means no events yet.
+pub fn load_events(path: &Path) -> Result<(), String> {
+    let events = if path.exists() {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read events file: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse events: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let mut current = EVENTS.lock().map_err(|e| format!("Failed to lock events: {}", e))?;
+    *current = events;
+    Ok(())
+}
+
+/// Applies `change` to the in-memory events and persists the result.
+///
+/// The in-memory list is only replaced once the file is written.
+pub fn modify_events<T>(path: &Path, change: impl FnOnce(&mut Vec<Event>) -> Result<T, String>) -> Result<T, String> {
+    let mut current = EVENTS.lock().map_err(|e| format!("Failed to lock events: {}", e))?;
+    let mut events = current.clone();
+    let result = change(&mut events)?;
+    write_events(path, &events)?;
+    *current = events;
+    Ok(result)
+}
+
+pub fn add_event(path: &Path, input: EventInput) -> Result<Event, String> {
+    let event = input.into_event(new_event_id())?;
+    modify_events(path, |events| {
+        events.push(event.clone());
+        Ok(event)
+    })
+}
+
+/// Adds events brought in from elsewhere, such as an `.ics` file, and returns how many were added.
+///
+/// Events with the same date, time and title as an existing one are left out, so
+/// importing the same file twice adds nothing the second time.
+pub fn import_events(path: &Path, inputs: Vec<EventInput>) -> Result<usize, String> {
+    let id = new_event_id();
+    let imported = inputs
+        .into_iter()
+        .enumerate()
+        .map(|(index, input)| input.into_event(format!("{}-{}", id, index)))
+        .collect::<Result<Vec<_>, _>>()?;
+    modify_events(path, |events| {
+        let count = events.len();
+        for event in imported {
+            let duplicate = events
+                .iter()
+                .any(|existing| existing.date == event.date && existing.time == event.time && existing.title == event.title);
+            if !duplicate {
+                events.push(event);
+            }
+        }
+        Ok(events.len() - count)
+    })
+}
+
+pub fn update_event(path: &Path, id: &str, input: EventInput) -> Result<Event, String> {
+    let event = input.into_event(id.to_string())?;
+    modify_events(path, |events| {
+        let existing = events
+            .iter_mut()
+            .find(|existing| existing.id == id)
+            .ok_or_else(|| format!("No event with id {}", id))?;
+        *existing = event.clone();
+        Ok(event)
+    })
+}
+
+pub fn delete_event(path: &Path, id: &str) -> Result<(), String> {
+    modify_events(path, |events| {
+        let count = events.len();
+        events.retain(|event| event.id != id);
+        if events.len() == count {
+            return Err(format!("No event with id {}", id));
+        }
+        Ok(())
+    })
+}
+
+/// Every stored event as saved, with recurring events listed once.
+pub fn all_events() -> Vec<Event> {
+    EVENTS.lock().map(|events| events.clone()).unwrap_or_default()
+}
+
+/// Occurrences of every event in one month, in date order.
+///
+/// Each occurrence is a copy of its event with `date` set to the day it falls on;
+/// the `id` stays that of the event, so updates and deletes apply to the whole series.
+pub fn events_for_month(year: usize, month: usize) -> Vec<Event> {
+    let policy = crate::pagume_6_policy();
+    let mut occurrences: Vec<Event> = EVENTS
+        .lock()
+        .map(|events| {
+            events
+                .iter()
+                .flat_map(|event| {
+                    event.occurrences_in_month(year, month, policy).into_iter().map(|date| Event {
+                        date,
+                        ..event.clone()
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    occurrences.sort_by_key(|event| event.date.day);
+    occurrences
+}
+
+/// Occurrences of every event on one day.
+pub fn events_on(date: &EthiopianDate) -> Vec<Event> {
+    let policy = crate::pagume_6_policy();
+    EVENTS
+        .lock()
+        .map(|events| {
+            events
+                .iter()
+                .filter(|event| event.occurs_on(date, policy))
+                .map(|event| Event {
+                    date: date.clone(),
+                    ..event.clone()
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether any event, or a repetition of one, falls on `date`.
+pub fn has_event(date: &EthiopianDate) -> bool {
+    let policy = crate::pagume_6_policy();
+    EVENTS
+        .lock()
+        .map(|events| events.iter().any(|event| event.occurs_on(date, policy)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,196 @@
+//! File exports of calendar data.
+
+use crate::format::NumericFormat;
+use crate::locale::Language;
+use crate::{events, holidays, strings, EthiopianDate};
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Upper bound on the number of years in one conversion table (~36k rows per century).
+const MAX_TABLE_YEARS: usize = 1000;
+
+/// Writes a CSV mapping every Ethiopian date in `start_year..=end_year` to its Gregorian date,
+/// both written in `date_format`.
+///
+/// Returns the number of rows written, excluding the header.
+pub fn write_conversion_table(
+    start_year: usize,
+    end_year: usize,
+    path: &Path,
+    date_format: NumericFormat,
+) -> Result<usize, String> {
+    if start_year == 0 || end_year < start_year {
+        return Err(format!("Invalid year range: {}-{}", start_year, end_year));
+    }
+    if end_year - start_year + 1 > MAX_TABLE_YEARS {
+        return Err(format!("Year range is limited to {} years", MAX_TABLE_YEARS));
+    }
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create export file: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    let write_err = |e: std::io::Error| format!("Failed to write export file: {}", e);
+
+    writeln!(writer, "ethiopian_date,ethiopian_year,ethiopian_month,ethiopian_day,month_name,gregorian_date,weekday")
+        .map_err(write_err)?;
+
+    let template = date_format.template(false);
+    let first = EthiopianDate::new(start_year, 1, 1);
+    let last = EthiopianDate::new(end_year, 13, EthiopianDate::pagume_length(end_year));
+    let mut rows = 0;
+    for date in EthiopianDate::iter_days(first, last) {
+        let gregorian = date
+            .to_gregorian()
+            .ok_or_else(|| format!("Failed to convert {}-{}-{}", date.year, date.month, date.day))?;
+
+        writeln!(
+            writer,
+            "{},{},{},{},{},{},{}",
+            date.format(&template),
+            date.year,
+            date.month,
+            date.day,
+            date.english_month(),
+            gregorian.format(&template),
+            date.english_weekday()
+        )
+        .map_err(write_err)?;
+        rows += 1;
+    }
+
+    writer.flush().map_err(write_err)?;
+    Ok(rows)
+}
+
+/// File format of `write_calendar`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+/// One day of an exported month or year.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportedDay {
+    /// Ethiopian date in the export's `NumericFormat`, e.g. `21/05/2017`.
+    pub ethiopian_date: String,
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+    pub month_name: String,
+    /// Gregorian date in the same format.
+    pub gregorian_date: String,
+    pub weekday: String,
+    /// Names of the holidays shown in the grid on this day.
+    pub holidays: Vec<String>,
+    /// Titles of the user's events on this day, repetitions included.
+    pub events: Vec<String>,
+}
+
+/// The days of an Ethiopian month, or of the whole year when `month` is `None`, with
+/// names in `language` and dates in `date_format`.
+pub fn calendar_days(
+    year: usize,
+    month: Option<usize>,
+    language: Language,
+    date_format: NumericFormat,
+) -> Result<Vec<ExportedDay>, String> {
+    if year == 0 {
+        return Err("Invalid year: 0".to_string());
+    }
+    let (first, last) = match month {
+        Some(month @ 1..=13) => {
+            let first = EthiopianDate::new(year, month, 1);
+            let last = EthiopianDate::new(year, month, first.days_in_month());
+            (first, last)
+        }
+        Some(month) => return Err(format!("Invalid month: {}", month)),
+        None => (EthiopianDate::new(year, 1, 1), EthiopianDate::new(year, 13, EthiopianDate::pagume_length(year))),
+    };
+    let template = date_format.template(false);
+    let year_holidays: Vec<holidays::Holiday> =
+        holidays::holidays_for_year(year).into_iter().filter(|h| h.is_shown()).collect();
+
+    EthiopianDate::iter_days(first, last)
+        .map(|date| {
+            let gregorian = date
+                .to_gregorian()
+                .ok_or_else(|| format!("Failed to convert {}-{}-{}", date.year, date.month, date.day))?;
+            Ok(ExportedDay {
+                ethiopian_date: date.format(&template),
+                year: date.year,
+                month: date.month,
+                day: date.day,
+                month_name: date.month_name(language).to_string(),
+                gregorian_date: gregorian.format(&template).to_string(),
+                weekday: date.weekday_name(language).to_string(),
+                holidays: year_holidays
+                    .iter()
+                    .filter(|holiday| holiday.date == date)
+                    .map(|holiday| strings::holiday_name(holiday, language))
+                    .collect(),
+                events: events::events_on(&date).into_iter().map(|event| event.title).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+/// Writes an Ethiopian month, or the whole year when `month` is `None`, as CSV or JSON;
+/// several holidays or events on one day are joined with "; " in CSV.
+///
+/// Returns the number of days written.
+pub fn write_calendar(
+    path: &Path,
+    year: usize,
+    month: Option<usize>,
+    format: ExportFormat,
+    language: Language,
+    date_format: NumericFormat,
+) -> Result<usize, String> {
+    let days = calendar_days(year, month, language, date_format)?;
+
+    let file = std::fs::File::create(path).map_err(|e| format!("Failed to create export file: {}", e))?;
+    let mut writer = BufWriter::new(file);
+    let write_err = |e: std::io::Error| format!("Failed to write export file: {}", e);
+
+    match format {
+        ExportFormat::Json => {
+            let content =
+                serde_json::to_string_pretty(&days).map_err(|e| format!("Failed to serialize export: {}", e))?;
+            writer.write_all(content.as_bytes()).map_err(write_err)?;
+        }
+        ExportFormat::Csv => {
+            writeln!(writer, "ethiopian_date,year,month,day,month_name,gregorian_date,weekday,holidays,events")
+                .map_err(write_err)?;
+            for day in &days {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{},{},{},{},{}",
+                    day.ethiopian_date,
+                    day.year,
+                    day.month,
+                    day.day,
+                    csv_field(&day.month_name),
+                    day.gregorian_date,
+                    csv_field(&day.weekday),
+                    csv_field(&day.holidays.join("; ")),
+                    csv_field(&day.events.join("; "))
+                )
+                .map_err(write_err)?;
+            }
+        }
+    }
+
+    writer.flush().map_err(write_err)?;
+    Ok(days.len())
+}
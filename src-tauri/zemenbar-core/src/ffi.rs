@@ -0,0 +1,160 @@
+//! C bindings for the date code, built with `--features ffi`, for Swift and Objective-C
+//! widgets or other native apps that want the same conversions as the app.
+//!
+//! The matching header is committed as `include/zemenbar.h`; after changing this
+//! file, regenerate it by running `cbindgen --config cbindgen.toml --output
+//! include/zemenbar.h` in the crate directory. Dates cross the
+//! boundary as plain `ZemenbarDate` structs; strings come back as NUL-terminated UTF-8
+//! that the caller owns and releases with `zemenbar_string_free`.
+//! Functions that can fail return `false` or a null pointer instead of panicking.
+
+use crate::{holidays, EthiopianDate, EthiopianDateInput};
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+/// A year, month and day, in whichever calendar the function names.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ZemenbarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl From<&EthiopianDate> for ZemenbarDate {
+    fn from(date: &EthiopianDate) -> Self {
+        Self {
+            year: date.year as i32,
+            month: date.month as u32,
+            day: date.day as u32,
+        }
+    }
+}
+
+fn ethiopian(date: ZemenbarDate) -> Option<EthiopianDate> {
+    EthiopianDateInput {
+        year: usize::try_from(date.year).ok()?,
+        month: date.month as usize,
+        day: date.day as usize,
+    }
+    .to_date()
+    .ok()
+}
+
+/// Hands `text` to the caller, or null when it contains a NUL byte.
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text).map(CString::into_raw).unwrap_or(ptr::null_mut())
+}
+
+/// Writes `date` through `out` when both are present, and reports whether it did.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a `ZemenbarDate`.
+unsafe fn write_date(out: *mut ZemenbarDate, date: Option<ZemenbarDate>) -> bool {
+    match (out.is_null(), date) {
+        (false, Some(date)) => {
+            *out = date;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Today's Ethiopian date in the configured timezone.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a `ZemenbarDate`.
+#[no_mangle]
+pub unsafe extern "C" fn zemenbar_today(out: *mut ZemenbarDate) -> bool {
+    write_date(out, Some((&EthiopianDate::today()).into()))
+}
+
+/// Converts a Gregorian date to Ethiopian; `false` when the date does not exist.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a `ZemenbarDate`.
+#[no_mangle]
+pub unsafe extern "C" fn zemenbar_to_ethiopian(gregorian: ZemenbarDate, out: *mut ZemenbarDate) -> bool {
+    let date = EthiopianDate::from_gregorian(gregorian.year, gregorian.month, gregorian.day);
+    write_date(out, date.as_ref().map(ZemenbarDate::from))
+}
+
+/// Converts an Ethiopian date to Gregorian; `false` when the date does not exist.
+///
+/// # Safety
+///
+/// `out` must be null or point to writable memory for a `ZemenbarDate`.
+#[no_mangle]
+pub unsafe extern "C" fn zemenbar_to_gregorian(ethiopian_date: ZemenbarDate, out: *mut ZemenbarDate) -> bool {
+    use chrono::Datelike;
+
+    let date = ethiopian(ethiopian_date).and_then(|date| date.to_gregorian());
+    write_date(
+        out,
+        date.map(|date| ZemenbarDate {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+        }),
+    )
+}
+
+/// Renders an Ethiopian date with a `%` token template, e.g. `"%EB %e %Y %EC"`; see
+/// `format` for the tokens. Null when the date or template is invalid.
+///
+/// # Safety
+///
+/// `template` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn zemenbar_format(ethiopian_date: ZemenbarDate, template: *const c_char) -> *mut c_char {
+    if template.is_null() {
+        return ptr::null_mut();
+    }
+    let (Some(date), Ok(template)) = (ethiopian(ethiopian_date), CStr::from_ptr(template).to_str()) else {
+        return ptr::null_mut();
+    };
+    into_c_string(date.format(template))
+}
+
+/// The name of the holiday on an Ethiopian date, in Amharic or English, or null when
+/// the day is not a holiday. Several holidays on one day are joined with " / ".
+#[no_mangle]
+pub extern "C" fn zemenbar_holiday_name(ethiopian_date: ZemenbarDate, amharic: bool) -> *mut c_char {
+    let Some(date) = ethiopian(ethiopian_date) else {
+        return ptr::null_mut();
+    };
+    let holidays = holidays::holidays_for_month(date.year, date.month);
+    let names: Vec<&str> = holidays
+        .iter()
+        .filter(|holiday| holiday.date.day == date.day)
+        .map(|holiday| if amharic { holiday.name_amharic.as_str() } else { holiday.name_english.as_str() })
+        .collect();
+    if names.is_empty() {
+        return ptr::null_mut();
+    }
+    into_c_string(names.join(" / "))
+}
+
+/// Every holiday and observance of an Ethiopian year as a JSON array of `Holiday`s,
+/// in date order.
+#[no_mangle]
+pub extern "C" fn zemenbar_holidays_json(year: u32) -> *mut c_char {
+    serde_json::to_string(&holidays::holidays_for_year(year as usize))
+        .map(into_c_string)
+        .unwrap_or(ptr::null_mut())
+}
+
+/// Releases a string returned by this library.
+///
+/// # Safety
+///
+/// `text` must be null or a pointer returned by this library that was not freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn zemenbar_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
@@ -0,0 +1,404 @@
+//! Template rendering for Ethiopian dates.
+//!
+//! Templates use `%` tokens in the spirit of strftime:
+//!
+//! | Token | Output                                  | Example   |
+//! |-------|-----------------------------------------|-----------|
+//! | `%d`  | day, zero padded                        | `05`      |
+//! | `%e`  | day                                     | `5`       |
+//! | `%m`  | month number                            | `05`      |
+//! | `%Y`  | year in the chosen era                  | `2017`    |
+//! | `%Od` | day in Geez numerals                    | `፳፩`      |
+//! | `%Om` | month in Geez numerals                  | `፭`       |
+//! | `%OY` | year in Geez numerals                   | `፳፻፲፯`    |
+//! | `%EY` | year in Amete Alem                      | `7517`    |
+//! | `%B`  | English month name                      | `Tir`     |
+//! | `%EB` | Amharic month name                      | `ጥር`      |
+//! | `%b`  | short English month name                | `Tir`     |
+//! | `%Eb` | short Amharic month name                | `ጥር`      |
+//! | `%LB` | month name in the chosen language       | `ጥሪ`      |
+//! | `%Lb` | short month name in the chosen language | `ጥሪ`      |
+//! | `%A`  | English weekday name                    | `Tuesday` |
+//! | `%EA` | Amharic weekday name                    | `ማክሰኞ`    |
+//! | `%LA` | weekday name in the chosen language     | `ሰሉስ`     |
+//! | `%C`  | English era suffix                      | `E.C.`    |
+//! | `%EC` | Amharic era suffix                      | `ዓ.ም.`    |
+//! | `%q`  | the word ቀን ("day")                     | `ቀን`      |
+//! | `%%`  | literal `%`                             | `%`       |
+//!
+//! `%Y`, `%OY`, the era suffixes and the `%L` tokens follow the `FormatOptions` passed to
+//! `render_template_with`; `render_template` uses Amete Mihret and Amharic. Unknown tokens
+//! are copied through unchanged.
+
+use crate::locale::Language;
+use crate::{Era, EthiopianDate};
+use serde::{Deserialize, Serialize};
+
+/// A user-named template used by the copy menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyTemplate {
+    pub name: String,
+    pub template: String,
+}
+
+impl CopyTemplate {
+    fn new(name: &str, template: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            template: template.to_string(),
+        }
+    }
+}
+
+/// Templates offered before the user defines their own. Numeric dates come from
+/// `CopyFormat::Numeric`, which follows the `numeric_format` setting.
+pub fn default_copy_templates() -> Vec<CopyTemplate> {
+    vec![
+        CopyTemplate::new("Formal letter", "%EB %Od ቀን %OY ዓ.ም."),
+        CopyTemplate::new("Amharic", "%EA፣ %EB %e %Y"),
+        CopyTemplate::new("English", "%A, %B %e, %Y"),
+    ]
+}
+
+/// Built-in ways of copying a date, offered next to the user's own templates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CopyFormat {
+    /// `ረቡዕ፣ ጥር 21 2017 ዓ.ም.`
+    AmharicLong,
+    /// `Wednesday, Tir 21, 2017 E.C.`
+    EnglishLong,
+    /// `21/05/2017`, in the order and with the separator of `FormatOptions::numeric`.
+    Numeric,
+    /// `ጥር ፳፩ ቀን ፳፻፲፯ ዓ.ም.`
+    Geez,
+    /// `Wednesday, January 29, 2025`
+    Gregorian,
+    /// `2025-01-29`, the Gregorian date.
+    Iso,
+    /// `ጥር 21 2017 ዓ.ም. (January 29, 2025)`
+    Dual,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 7] = [
+        CopyFormat::AmharicLong,
+        CopyFormat::EnglishLong,
+        CopyFormat::Numeric,
+        CopyFormat::Geez,
+        CopyFormat::Gregorian,
+        CopyFormat::Iso,
+        CopyFormat::Dual,
+    ];
+
+    /// Renders `date`; `None` only when a Gregorian date is needed and out of range.
+    pub fn render(self, date: &EthiopianDate, options: FormatOptions) -> Option<String> {
+        let ethiopian = |template: &str| render_template_with(date, template, options);
+        let gregorian = |template: &str| date.to_gregorian().map(|g| g.format(template).to_string());
+        Some(match self {
+            CopyFormat::AmharicLong => ethiopian("%EA፣ %EB %e %Y %EC"),
+            CopyFormat::EnglishLong => ethiopian("%A, %B %e, %Y %C"),
+            CopyFormat::Numeric => ethiopian(&options.numeric.template(false)),
+            CopyFormat::Geez => ethiopian("%EB %Od ቀን %OY %EC"),
+            CopyFormat::Gregorian => gregorian("%A, %B %-d, %Y")?,
+            CopyFormat::Iso => gregorian("%Y-%m-%d")?,
+            CopyFormat::Dual => format!("{} ({})", ethiopian("%EB %e %Y %EC"), gregorian("%B %-d, %Y")?),
+        })
+    }
+}
+
+/// A `CopyFormat` with the text it produces, for showing the choices.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CopyFormatText {
+    pub format: CopyFormat,
+    pub text: String,
+}
+
+/// Every `CopyFormat` rendered for `date`, in menu order.
+pub fn copy_format_texts(date: &EthiopianDate, options: FormatOptions) -> Vec<CopyFormatText> {
+    CopyFormat::ALL
+        .iter()
+        .filter_map(|format| Some(CopyFormatText { format: *format, text: format.render(date, options)? }))
+        .collect()
+}
+
+/// Order of the day, month and year in a numeric date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateOrder {
+    /// `21/05/2017`
+    #[default]
+    DayMonthYear,
+    /// `2017/05/21`
+    YearMonthDay,
+    /// `05/21/2017`
+    MonthDayYear,
+}
+
+/// What goes between the parts of a numeric date.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DateSeparator {
+    #[default]
+    Slash,
+    Dash,
+    Dot,
+    Space,
+}
+
+impl DateSeparator {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DateSeparator::Slash => "/",
+            DateSeparator::Dash => "-",
+            DateSeparator::Dot => ".",
+            DateSeparator::Space => " ",
+        }
+    }
+}
+
+/// How numeric dates are written, e.g. `21/05/2017` or `2017-05-21`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NumericFormat {
+    pub order: DateOrder,
+    pub separator: DateSeparator,
+}
+
+impl NumericFormat {
+    /// Template for the date, with Geez numerals when `geez` is set.
+    ///
+    /// Without Geez numerals the tokens are also chrono's, so the template formats a
+    /// Gregorian date the same way.
+    pub fn template(self, geez: bool) -> String {
+        let (day, month, year) = if geez { ("%Od", "%Om", "%OY") } else { ("%d", "%m", "%Y") };
+        let parts = match self.order {
+            DateOrder::DayMonthYear => [day, month, year],
+            DateOrder::YearMonthDay => [year, month, day],
+            DateOrder::MonthDayYear => [month, day, year],
+        };
+        parts.join(self.separator.as_str())
+    }
+}
+
+/// Era, language and numeric date format for the tokens and formats that depend on them.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FormatOptions {
+    pub era: Era,
+    pub language: Language,
+    pub numeric: NumericFormat,
+}
+
+/// Renders a date using a `%` token template, in Amete Mihret and with Amharic `%L` names.
+pub fn render_template(date: &EthiopianDate, template: &str) -> String {
+    render_template_with(date, template, FormatOptions::default())
+}
+
+/// Renders a date using a `%` token template, with the era and language taken from `options`.
+pub fn render_template_with(date: &EthiopianDate, template: &str, options: FormatOptions) -> String {
+    let FormatOptions { era, language, .. } = options;
+    let mut output = String::with_capacity(template.len());
+    let mut chars = template.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            output.push(c);
+            continue;
+        }
+
+        let token = match chars.next() {
+            Some(modifier @ ('O' | 'E' | 'L')) => match chars.next() {
+                Some(spec) => format!("{}{}", modifier, spec),
+                None => modifier.to_string(),
+            },
+            Some(spec) => spec.to_string(),
+            None => String::new(),
+        };
+
+        match token.as_str() {
+            "d" => output.push_str(&format!("{:02}", date.day)),
+            "e" => output.push_str(&date.day.to_string()),
+            "m" => output.push_str(&format!("{:02}", date.month)),
+            "Y" => output.push_str(&date.year_in_era(era).to_string()),
+            "Od" => output.push_str(&date.day_geez()),
+            "Om" => output.push_str(&EthiopianDate::to_geez_number(date.month)),
+            "OY" => output.push_str(&EthiopianDate::to_geez_number(date.year_in_era(era))),
+            "EY" => output.push_str(&date.year_amete_alem().to_string()),
+            "B" => output.push_str(date.english_month()),
+            "EB" => output.push_str(date.amharic_month()),
+            "b" => output.extend(date.english_month().chars().take(3)),
+            "Eb" => output.extend(date.amharic_month().chars().take(2)),
+            "LB" => output.push_str(date.month_name(language)),
+            "Lb" => {
+                let length = if language.uses_ethiopic_script() { 2 } else { 3 };
+                output.extend(date.month_name(language).chars().take(length));
+            }
+            "LA" => output.push_str(date.weekday_name(language)),
+            "A" => output.push_str(date.english_weekday()),
+            "EA" => output.push_str(date.amharic_weekday()),
+            "C" => output.push_str(era.english_abbreviation()),
+            "EC" => output.push_str(era.amharic_abbreviation()),
+            "q" => output.push_str("ቀን"),
+            "%" => output.push('%'),
+            other => {
+                output.push('%');
+                output.push_str(other);
+            }
+        }
+    }
+
+    output
+}
+
+/// Layout of the date line at the top of official correspondence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LetterStampLayout {
+    /// `ቁጥር፡-` (reference number) line above the `ቀን፡-` line.
+    NumberAndDate,
+    /// Only the `ቀን፡-` line.
+    DateOnly,
+}
+
+/// How the date itself is written on the `ቀን፡-` line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LetterDateStyle {
+    /// `21/05/2017 ዓ.ም.`
+    Numeric,
+    /// `ጥር 21 ቀን 2017 ዓ.ም.`
+    Written,
+}
+
+/// Placeholder printed when no reference number is given, to be filled in by hand.
+const LETTER_NUMBER_BLANK: &str = "______________";
+
+/// Renders the standard date stamp for Ethiopian official letters.
+pub fn letter_date_stamp(
+    date: &EthiopianDate,
+    layout: LetterStampLayout,
+    style: LetterDateStyle,
+    use_geez_numbers: bool,
+    reference_number: Option<&str>,
+) -> String {
+    let template = match (style, use_geez_numbers) {
+        (LetterDateStyle::Numeric, false) => "%d/%m/%Y ዓ.ም.",
+        (LetterDateStyle::Numeric, true) => "%Od/%Om/%OY ዓ.ም.",
+        (LetterDateStyle::Written, false) => "%EB %e ቀን %Y ዓ.ም.",
+        (LetterDateStyle::Written, true) => "%EB %Od ቀን %OY ዓ.ም.",
+    };
+    let date_line = format!("ቀን፡- {}", render_template(date, template));
+
+    match layout {
+        LetterStampLayout::DateOnly => date_line,
+        LetterStampLayout::NumberAndDate => {
+            let number = reference_number
+                .map(str::trim)
+                .filter(|n| !n.is_empty())
+                .unwrap_or(LETTER_NUMBER_BLANK);
+            format!("ቁጥር፡- {}\n{}", number, date_line)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Yekatit 21, 2017, a Friday (February 28, 2025).
+    fn date() -> EthiopianDate {
+        EthiopianDate::new(2017, 6, 21)
+    }
+
+    fn options(era: Era, language: Language) -> FormatOptions {
+        FormatOptions { era, language, numeric: NumericFormat::default() }
+    }
+
+    #[test]
+    fn each_token() {
+        let tokens = [
+            ("%d", "21"),
+            ("%e", "21"),
+            ("%m", "06"),
+            ("%Y", "2017"),
+            ("%Od", "፳፩"),
+            ("%Om", "፮"),
+            ("%OY", "፳፻፲፯"),
+            ("%EY", "7517"),
+            ("%B", "Yekatit"),
+            ("%EB", "የካቲት"),
+            ("%b", "Yek"),
+            ("%Eb", "የካ"),
+            ("%LB", "የካቲት"),
+            ("%Lb", "የካ"),
+            ("%A", "Friday"),
+            ("%EA", "ዓርብ"),
+            ("%LA", "ዓርብ"),
+            ("%C", "E.C."),
+            ("%EC", "ዓ.ም."),
+            ("%q", "ቀን"),
+            ("%%", "%"),
+        ];
+        for (template, expected) in tokens {
+            assert_eq!(render_template(&date(), template), expected, "{}", template);
+        }
+    }
+
+    #[test]
+    fn day_padding_and_geez_day() {
+        let date = EthiopianDate::new(2017, 6, 5);
+        assert_eq!(render_template(&date, "%d|%e|%Od"), "05|5|፭");
+    }
+
+    #[test]
+    fn amete_alem_years_and_era_suffixes() {
+        let options = options(Era::AmeteAlem, Language::Amharic);
+        let tokens = [("%Y", "7517"), ("%OY", "፸፭፻፲፯"), ("%EY", "7517"), ("%C", "A.A."), ("%EC", "ዓ.ዓ.")];
+        for (template, expected) in tokens {
+            assert_eq!(render_template_with(&date(), template, options), expected, "{}", template);
+        }
+    }
+
+    #[test]
+    fn language_tokens_follow_the_script() {
+        let names = [
+            (Language::Amharic, "የካቲት|የካ|ዓርብ"),
+            (Language::Tigrinya, "ለካቲት|ለካ|ዓርቢ"),
+            (Language::English, "Yekatit|Yek|Friday"),
+            (Language::Oromo, "Guraandhala|Gur|Jimaata"),
+        ];
+        for (language, expected) in names {
+            let options = options(Era::AmeteMihret, language);
+            assert_eq!(render_template_with(&date(), "%LB|%Lb|%LA", options), expected, "{:?}", language);
+        }
+    }
+
+    #[test]
+    fn unknown_tokens_and_trailing_percent_are_kept() {
+        let cases = [("%x", "%x"), ("%Ez", "%Ez"), ("%Ox %d", "%Ox 21"), ("%", "%"), ("%d%", "21%"), ("%E", "%E")];
+        for (template, expected) in cases {
+            assert_eq!(render_template(&date(), template), expected, "{}", template);
+        }
+    }
+
+    #[test]
+    fn copy_formats() {
+        let options = FormatOptions {
+            era: Era::AmeteMihret,
+            language: Language::Amharic,
+            numeric: NumericFormat { order: DateOrder::YearMonthDay, separator: DateSeparator::Dash },
+        };
+        let formats = [
+            (CopyFormat::AmharicLong, "ዓርብ፣ የካቲት 21 2017 ዓ.ም."),
+            (CopyFormat::EnglishLong, "Friday, Yekatit 21, 2017 E.C."),
+            (CopyFormat::Numeric, "2017-06-21"),
+            (CopyFormat::Geez, "የካቲት ፳፩ ቀን ፳፻፲፯ ዓ.ም."),
+            (CopyFormat::Gregorian, "Friday, February 28, 2025"),
+            (CopyFormat::Iso, "2025-02-28"),
+            (CopyFormat::Dual, "የካቲት 21 2017 ዓ.ም. (February 28, 2025)"),
+        ];
+        for (format, expected) in formats {
+            assert_eq!(format.render(&date(), options).as_deref(), Some(expected), "{:?}", format);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Hijri (Islamic) calendar conversion and the Muslim holidays observed in Ethiopia.
+//!
+//! Uses the arithmetical (tabular) Islamic calendar with the civil epoch, in which
+//! 11 of every 30 years are leap years. Eid al-Fitr, Eid al-Adha and Mawlid are
+//! announced from the sighting of the new moon, so the actual day may differ from
+//! the computed one by a day; those holidays are flagged as approximate.
+
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+
+/// JDN of 1 Muharram 1 AH (July 16, 622 Julian) in the civil epoch.
+const HIJRI_EPOCH_JDN: i64 = 1_948_440;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct HijriDate {
+    pub year: i64,
+    pub month: i64,
+    pub day: i64,
+}
+
+impl HijriDate {
+    fn year_start_jdn(year: i64) -> i64 {
+        HIJRI_EPOCH_JDN + (year - 1) * 354 + (3 + 11 * year).div_euclid(30)
+    }
+
+    pub fn to_jdn(&self) -> i64 {
+        // Months alternate between 30 and 29 days, starting with 30.
+        Self::year_start_jdn(self.year) + (59 * (self.month - 1) + 1) / 2 + self.day - 1
+    }
+
+    pub fn from_jdn(jdn: i64) -> Self {
+        let mut year = (30 * (jdn - HIJRI_EPOCH_JDN) + 10646).div_euclid(10631);
+        while Self::year_start_jdn(year) > jdn {
+            year -= 1;
+        }
+        while Self::year_start_jdn(year + 1) <= jdn {
+            year += 1;
+        }
+        let day_of_year = jdn - Self::year_start_jdn(year);
+        let month = (1..=12)
+            .rev()
+            .find(|month| (59 * (month - 1) + 1) / 2 <= day_of_year)
+            .unwrap_or(1);
+        let day = day_of_year - (59 * (month - 1) + 1) / 2 + 1;
+        Self { year, month, day }
+    }
+
+    pub fn from_ethiopian(date: &EthiopianDate) -> Self {
+        Self::from_jdn(date.to_jdn())
+    }
+
+    pub fn to_ethiopian(&self) -> Option<EthiopianDate> {
+        EthiopianDate::from_jdn(self.to_jdn())
+    }
+}
+
+/// Muslim holidays that are public holidays in Ethiopia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IslamicFeast {
+    /// 1 Shawwal, the end of Ramadan.
+    EidAlFitr,
+    /// 10 Dhu al-Hijjah, also known as Arafa.
+    EidAlAdha,
+    /// 12 Rabi' al-Awwal, the Prophet's birthday.
+    Mawlid,
+}
+
+impl IslamicFeast {
+    pub const ALL: [IslamicFeast; 3] = [IslamicFeast::EidAlFitr, IslamicFeast::EidAlAdha, IslamicFeast::Mawlid];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            IslamicFeast::EidAlFitr => "eid_al_fitr",
+            IslamicFeast::EidAlAdha => "eid_al_adha",
+            IslamicFeast::Mawlid => "mawlid",
+        }
+    }
+
+    pub fn amharic_name(&self) -> &'static str {
+        match self {
+            IslamicFeast::EidAlFitr => "ኢድ አል ፈጥር",
+            IslamicFeast::EidAlAdha => "ኢድ አል አድሃ (አረፋ)",
+            IslamicFeast::Mawlid => "መውሊድ",
+        }
+    }
+
+    pub fn english_name(&self) -> &'static str {
+        match self {
+            IslamicFeast::EidAlFitr => "Eid al-Fitr",
+            IslamicFeast::EidAlAdha => "Eid al-Adha",
+            IslamicFeast::Mawlid => "Mawlid",
+        }
+    }
+
+    /// Hijri (month, day) of the feast.
+    pub fn hijri_month_day(&self) -> (i64, i64) {
+        match self {
+            IslamicFeast::EidAlFitr => (10, 1),
+            IslamicFeast::EidAlAdha => (12, 10),
+            IslamicFeast::Mawlid => (3, 12),
+        }
+    }
+}
+
+/// Every occurrence of `feast` within an Ethiopian year, in date order.
+///
+/// The Hijri year is about 11 days shorter, so a feast occasionally falls twice in
+/// one Ethiopian year.
+pub fn feast_dates(year: usize, feast: IslamicFeast) -> Vec<EthiopianDate> {
+    let first = HijriDate::from_ethiopian(&EthiopianDate::new(year, 1, 1));
+    let last = HijriDate::from_ethiopian(&EthiopianDate::new(year + 1, 1, 1));
+    let (month, day) = feast.hijri_month_day();
+
+    (first.year..=last.year)
+        .filter_map(|hijri_year| HijriDate { year: hijri_year, month, day }.to_ethiopian())
+        .filter(|date| date.year == year)
+        .collect()
+}
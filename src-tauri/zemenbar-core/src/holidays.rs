@@ -0,0 +1,375 @@
+//! Ethiopian holidays and observances for a given year.
+//!
+//! Fixed holidays are kept on their Ethiopian date, except the few that follow the
+//! Gregorian calendar (Genna on January 7, Labour Day on May 1). Movable feasts come
+//! from Bahire Hasab and Muslim holidays from the Hijri calendar; installed holiday
+//! packs add their own fixed dates, the academic presets their terms and exams, and
+//! `custom_holidays.json` adds the user's own.
+
+use crate::academic;
+use crate::bahire_hasab::{self, MovableFeast};
+use crate::cache::YearTable;
+use crate::locale::Language;
+use crate::hijri::{self, IslamicFeast};
+use crate::{custom_holidays, packs, strings, EthiopianDate};
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Kind of holiday, which the settings can hide from the grid, tray and notifications.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HolidayCategory {
+    /// Secular national days, such as Adwa Victory Day.
+    National,
+    /// Ethiopian Orthodox feasts and fasts.
+    Orthodox,
+    Muslim,
+    /// Regional and cultural days; the default for packs and custom holidays.
+    #[default]
+    Cultural,
+    /// Terms and exam periods from the academic presets.
+    Academic,
+}
+
+/// Which holiday categories are shown, from `AppSettings.holiday_categories`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HolidayCategories {
+    pub national: bool,
+    pub orthodox: bool,
+    pub muslim: bool,
+    pub cultural: bool,
+    pub academic: bool,
+}
+
+impl Default for HolidayCategories {
+    fn default() -> Self {
+        Self {
+            national: true,
+            orthodox: true,
+            muslim: true,
+            cultural: true,
+            academic: true,
+        }
+    }
+}
+
+impl HolidayCategories {
+    pub fn includes(&self, category: HolidayCategory) -> bool {
+        match category {
+            HolidayCategory::National => self.national,
+            HolidayCategory::Orthodox => self.orthodox,
+            HolidayCategory::Muslim => self.muslim,
+            HolidayCategory::Cultural => self.cultural,
+            HolidayCategory::Academic => self.academic,
+        }
+    }
+}
+
+static SHOWN_CATEGORIES: Mutex<HolidayCategories> = Mutex::new(HolidayCategories {
+    national: true,
+    orthodox: true,
+    muslim: true,
+    cultural: true,
+    academic: true,
+});
+
+pub fn shown_categories() -> HolidayCategories {
+    SHOWN_CATEGORIES.lock().map(|categories| *categories).unwrap_or_default()
+}
+
+pub fn set_shown_categories(categories: HolidayCategories) {
+    if let Ok(mut current) = SHOWN_CATEGORIES.lock() {
+        *current = categories;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holiday {
+    /// Stable identifier, e.g. `"enkutatash"` or `"fasika"`.
+    pub id: String,
+    pub name_amharic: String,
+    pub name_english: String,
+    pub date: EthiopianDate,
+    /// Whether the day is a public (non-working) holiday.
+    pub is_public: bool,
+    pub category: HolidayCategory,
+    /// Set for holidays announced from the moon sighting, which may fall a day either side of `date`.
+    pub is_approximate: bool,
+}
+
+impl Holiday {
+    fn new(
+        id: &str,
+        name_amharic: &str,
+        name_english: &str,
+        date: EthiopianDate,
+        is_public: bool,
+        category: HolidayCategory,
+    ) -> Self {
+        Self {
+            id: id.to_string(),
+            name_amharic: name_amharic.to_string(),
+            name_english: name_english.to_string(),
+            date,
+            is_public,
+            category,
+            is_approximate: false,
+        }
+    }
+
+    /// Whether the holiday's category is shown in the grid, tray and notifications.
+    pub fn is_shown(&self) -> bool {
+        shown_categories().includes(self.category)
+    }
+}
+
+/// Fixed holidays kept on their Ethiopian date: (id, Amharic, English, month, day, category).
+const FIXED_HOLIDAYS: [(&str, &str, &str, usize, usize, HolidayCategory); 6] = [
+    ("enkutatash", "እንቁጣጣሽ", "Ethiopian New Year", 1, 1, HolidayCategory::National),
+    ("meskel", "መስቀል", "Finding of the True Cross", 1, 17, HolidayCategory::Orthodox),
+    ("timket", "ጥምቀት", "Epiphany", 5, 11, HolidayCategory::Orthodox),
+    ("adwa", "የዓድዋ ድል በዓል", "Adwa Victory Day", 6, 23, HolidayCategory::National),
+    ("arbegnoch", "የአርበኞች ቀን", "Patriots' Victory Day", 8, 27, HolidayCategory::National),
+    ("ginbot_20", "ግንቦት ፳", "Derg Downfall Day", 9, 20, HolidayCategory::National),
+];
+
+/// Holidays kept on a Gregorian date (month, day) within the Ethiopian year.
+const GREGORIAN_HOLIDAYS: [(&str, &str, &str, u32, u32, HolidayCategory); 2] = [
+    ("genna", "ገና", "Ethiopian Christmas", 1, 7, HolidayCategory::Orthodox),
+    ("labour_day", "የሠራተኞች ቀን", "International Labour Day", 5, 1, HolidayCategory::National),
+];
+
+/// Movable feasts that are also public holidays.
+const PUBLIC_FEASTS: [MovableFeast; 2] = [MovableFeast::Siklet, MovableFeast::Fasika];
+
+/// `built_in_holidays` of recent years.
+static BUILT_IN_HOLIDAYS: YearTable<Vec<Holiday>> = YearTable::new();
+
+pub(crate) fn clear_cache() {
+    BUILT_IN_HOLIDAYS.clear();
+}
+
+/// The holidays of a year that do not depend on installed packs or custom holidays.
+fn built_in_holidays(year: usize) -> Vec<Holiday> {
+    let mut holidays: Vec<Holiday> = FIXED_HOLIDAYS
+        .iter()
+        .map(|(id, amharic, english, month, day, category)| {
+            Holiday::new(id, amharic, english, EthiopianDate::new(year, *month, *day), true, *category)
+        })
+        .collect();
+
+    // January to August of the Ethiopian year fall in Gregorian year + 8.
+    let gregorian_year = year as i32 + 8;
+    holidays.extend(GREGORIAN_HOLIDAYS.iter().filter_map(|(id, amharic, english, month, day, category)| {
+        let date = EthiopianDate::from_gregorian(gregorian_year, *month, *day)?;
+        Some(Holiday::new(id, amharic, english, date, true, *category))
+    }));
+
+    holidays.extend(bahire_hasab::movable_feasts(year).into_iter().map(|feast| Holiday {
+        id: feast.feast.id().to_string(),
+        is_public: PUBLIC_FEASTS.contains(&feast.feast),
+        name_amharic: feast.name_amharic,
+        name_english: feast.name_english,
+        date: feast.ethiopian,
+        category: HolidayCategory::Orthodox,
+        is_approximate: false,
+    }));
+
+    holidays.extend(IslamicFeast::ALL.iter().flat_map(|feast| {
+        hijri::feast_dates(year, *feast).into_iter().map(|date| Holiday {
+            is_approximate: true,
+            ..Holiday::new(feast.id(), feast.amharic_name(), feast.english_name(), date, true, HolidayCategory::Muslim)
+        })
+    }));
+    holidays
+}
+
+/// Returns every holiday and observance of an Ethiopian year, in date order.
+pub fn holidays_for_year(year: usize) -> Vec<Holiday> {
+    let mut holidays = BUILT_IN_HOLIDAYS.get(year, built_in_holidays);
+
+    holidays.extend(packs::pack_holidays().into_iter().filter_map(|holiday| {
+        let date = EthiopianDate::new(year, holiday.month, holiday.day);
+        date.is_valid().then_some(Holiday {
+            id: holiday.id,
+            name_amharic: holiday.name_amharic,
+            name_english: holiday.name_english,
+            date,
+            is_public: holiday.is_public,
+            category: holiday.category,
+            is_approximate: false,
+        })
+    }));
+
+    holidays.extend(academic::academic_holidays(year));
+
+    holidays.extend(custom_holidays::custom_holidays().into_iter().filter_map(|holiday| {
+        Some(Holiday {
+            date: holiday.date_in(year)?,
+            id: holiday.id.unwrap_or_default(),
+            name_amharic: holiday.name_amharic.unwrap_or_else(|| holiday.name.clone()),
+            name_english: holiday.name,
+            is_public: holiday.is_public,
+            category: holiday.category,
+            is_approximate: false,
+        })
+    }));
+
+    holidays.sort_by(|a, b| a.date.cmp(&b.date));
+    holidays
+}
+
+/// Returns the holidays of a single month.
+pub fn holidays_for_month(year: usize, month: usize) -> Vec<Holiday> {
+    holidays_for_year(year)
+        .into_iter()
+        .filter(|holiday| holiday.date.year == year && holiday.date.month == month)
+        .collect()
+}
+
+/// Every holiday and observance from `start` to `end` inclusive, in date order.
+pub fn holidays_between(start: &EthiopianDate, end: &EthiopianDate) -> Vec<Holiday> {
+    (start.year..=end.year)
+        .flat_map(holidays_for_year)
+        .filter(|holiday| holiday.date >= *start && holiday.date <= *end)
+        .collect()
+}
+
+/// A holiday still to come, as listed in the "Upcoming" sections of the popup and tray menu.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpcomingHoliday {
+    pub id: String,
+    /// Name in the requested language.
+    pub name: String,
+    pub date: EthiopianDate,
+    /// Gregorian `YYYY-MM-DD`.
+    pub gregorian_date: String,
+    pub is_public: bool,
+    pub category: HolidayCategory,
+    pub is_approximate: bool,
+    /// Days from the starting date; 0 for a holiday on that day.
+    pub days_remaining: i64,
+    pub days_remaining_geez: String,
+}
+
+/// Holidays are looked for this many years after the starting date's year.
+const UPCOMING_YEARS: usize = 2;
+
+/// The next `count` shown holidays and observances on or after `from`, across year boundaries.
+/// Fewer are returned when only a few categories are shown, or none when no category is.
+pub fn upcoming_holidays(from: &EthiopianDate, count: usize, language: Language) -> Vec<UpcomingHoliday> {
+    (from.year..=from.year + UPCOMING_YEARS)
+        .flat_map(holidays_for_year)
+        .filter(|holiday| holiday.date >= *from && holiday.is_shown())
+        .filter_map(|holiday| {
+            let days_remaining = from.days_between(&holiday.date)?;
+            Some(UpcomingHoliday {
+                name: strings::holiday_name(&holiday, language),
+                gregorian_date: holiday.date.to_gregorian()?.format("%Y-%m-%d").to_string(),
+                days_remaining,
+                days_remaining_geez: EthiopianDate::to_geez_number(days_remaining as usize),
+                id: holiday.id,
+                date: holiday.date,
+                is_public: holiday.is_public,
+                category: holiday.category,
+                is_approximate: holiday.is_approximate,
+            })
+        })
+        .take(count)
+        .collect()
+}
+
+/// A holiday found by [`find_holiday`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HolidayMatch {
+    pub holiday: Holiday,
+    /// Gregorian `YYYY-MM-DD`.
+    pub gregorian_date: String,
+}
+
+/// Lowercases and turns everything but letters and digits (including Ethiopic wordspace) into single spaces.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_lowercase().next().unwrap_or(c) } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// How well `name` matches the normalized `query`, lower being better; `None` for no match.
+///
+/// Exact names beat prefixes, prefixes beat substrings, and a name or word within a
+/// typo or two of the query (one per four letters) matches last.
+fn match_score(query: &str, name: &str) -> Option<usize> {
+    let name = normalize(name);
+    if name == query {
+        return Some(0);
+    }
+    if name.starts_with(query) || name.split(' ').any(|word| word.starts_with(query)) {
+        return Some(1);
+    }
+    if name.contains(query) {
+        return Some(2);
+    }
+
+    let query_chars: Vec<char> = query.chars().collect();
+    let allowed = query_chars.len() / 4;
+    std::iter::once(name.as_str())
+        .chain(name.split(' '))
+        .map(|candidate| edit_distance(&query_chars, &candidate.chars().collect::<Vec<_>>()))
+        .min()
+        .filter(|distance| *distance <= allowed)
+        .map(|distance| 3 + distance)
+}
+
+/// Holidays of `year` whose id or name in any language matches `query`, best matches first.
+///
+/// Matching ignores case and punctuation and tolerates small typos, so "fasika",
+/// "ፋሲካ", "Easter" and "eastr" all find Fasika.
+pub fn find_holiday(query: &str, year: usize) -> Vec<HolidayMatch> {
+    let query = normalize(query);
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<(usize, Holiday)> = holidays_for_year(year)
+        .into_iter()
+        .filter_map(|holiday| {
+            let names = [
+                holiday.id.replace('_', " "),
+                holiday.name_amharic.clone(),
+                holiday.name_english.clone(),
+                strings::holiday_name(&holiday, Language::Tigrinya),
+                strings::holiday_name(&holiday, Language::Oromo),
+            ];
+            let score = names.iter().filter_map(|name| match_score(&query, name)).min()?;
+            Some((score, holiday))
+        })
+        .collect();
+    matches.sort_by(|(score_a, a), (score_b, b)| score_a.cmp(score_b).then_with(|| a.date.cmp(&b.date)));
+
+    matches
+        .into_iter()
+        .filter_map(|(_, holiday)| {
+            Some(HolidayMatch {
+                gregorian_date: holiday.date.to_gregorian()?.format("%Y-%m-%d").to_string(),
+                holiday,
+            })
+        })
+        .collect()
+}
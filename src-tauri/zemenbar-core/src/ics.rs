@@ -0,0 +1,969 @@
+//! iCalendar (RFC 5545) files of holidays and events, for Google Calendar, Apple Calendar and the like.
+//!
+//! Those apps only know Gregorian dates, so every occurrence within the exported
+//! Ethiopian year becomes its own `VEVENT` on its Gregorian date; a yearly event on
+//! Meskerem 1 cannot be written as a Gregorian `RRULE`, since it moves between
+//! September 11 and 12. Summaries keep the Ethiopian names and descriptions carry
+//! the Ethiopian date.
+//!
+//! Importing goes the other way: each `VEVENT` is placed on the Ethiopian day it
+//! falls on in the "today" timezone. For the same reason, Gregorian `RRULE`s are
+//! expanded into one event per occurrence, from `RECURRENCE_LOOKBACK_DAYS` before
+//! today up to `RECURRENCE_HORIZON_DAYS` ahead; only a plain weekly rule stays a
+//! single weekly event.
+
+use crate::events::{self, EventInput, Recurrence, MAX_REMINDER_MINUTES};
+use crate::{holidays, EthiopianDate, EthiopianDateInput};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const PRODUCT_ID: &str = "-//ZemenBar//Ethiopian Calendar//EN";
+
+/// What `export_ics` writes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IcsExportOptions {
+    /// Ethiopian year to export; the current year when not given.
+    pub year: Option<usize>,
+    pub include_holidays: bool,
+    /// Leave out fasts and other observances that are not days off.
+    pub public_holidays_only: bool,
+    pub include_events: bool,
+}
+
+impl Default for IcsExportOptions {
+    fn default() -> Self {
+        Self {
+            year: None,
+            include_holidays: true,
+            public_holidays_only: false,
+            include_events: true,
+        }
+    }
+}
+
+/// Escapes commas, semicolons, backslashes and newlines in a TEXT value.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Folds a content line into lines of at most 75 octets, without splitting a UTF-8 character.
+fn push_line(output: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            output.push_str("\r\n ");
+            width = 1;
+        }
+        output.push(c);
+        width += c.len_utf8();
+    }
+    output.push_str("\r\n");
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y%m%d").to_string()
+}
+
+fn format_utc(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// "Meskerem 17, 2017 E.C. / መስከረም 17 2017 ዓ.ም."
+pub fn ethiopian_date_text(date: &EthiopianDate) -> String {
+    format!("{} / {}", date.format("%B %e, %Y %C"), date.format("%EB %e %Y %EC"))
+}
+
+struct VEvent {
+    uid: String,
+    summary: String,
+    description: String,
+    start: EventStart,
+    categories: Option<String>,
+    transparent: bool,
+    /// Further content lines, such as `RRULE` or `LAST-MODIFIED`, already formatted.
+    extra_lines: Vec<String>,
+    alarm_minutes: Vec<u32>,
+}
+
+enum EventStart {
+    Day(NaiveDate),
+    Time(DateTime<Utc>),
+}
+
+impl VEvent {
+    fn write(&self, output: &mut String, stamp: &str) {
+        push_line(output, "BEGIN:VEVENT");
+        push_line(output, &format!("UID:{}", self.uid));
+        push_line(output, &format!("DTSTAMP:{}", stamp));
+        match self.start {
+            EventStart::Day(day) => {
+                push_line(output, &format!("DTSTART;VALUE=DATE:{}", format_date(day)));
+                if let Some(next) = day.succ_opt() {
+                    push_line(output, &format!("DTEND;VALUE=DATE:{}", format_date(next)));
+                }
+            }
+            EventStart::Time(time) => push_line(output, &format!("DTSTART:{}", format_utc(time))),
+        }
+        push_line(output, &format!("SUMMARY:{}", escape_text(&self.summary)));
+        push_line(output, &format!("DESCRIPTION:{}", escape_text(&self.description)));
+        if let Some(categories) = &self.categories {
+            push_line(output, &format!("CATEGORIES:{}", escape_text(categories)));
+        }
+        if self.transparent {
+            push_line(output, "TRANSP:TRANSPARENT");
+        }
+        for line in &self.extra_lines {
+            push_line(output, line);
+        }
+        for minutes in &self.alarm_minutes {
+            push_line(output, "BEGIN:VALARM");
+            push_line(output, "ACTION:DISPLAY");
+            push_line(output, &format!("DESCRIPTION:{}", escape_text(&self.summary)));
+            push_line(output, &format!("TRIGGER:-PT{}M", minutes));
+            push_line(output, "END:VALARM");
+        }
+        push_line(output, "END:VEVENT");
+    }
+}
+
+fn holiday_events(year: usize, public_only: bool) -> Vec<VEvent> {
+    holidays::holidays_for_year(year)
+        .into_iter()
+        .filter(|holiday| holiday.is_shown() && (holiday.is_public || !public_only))
+        .filter_map(|holiday| {
+            let mut description = ethiopian_date_text(&holiday.date);
+            if holiday.is_approximate {
+                description.push_str("\nDepends on the moon sighting and may fall a day earlier or later.");
+            }
+            Some(VEvent {
+                uid: format!("holiday-{}-{}@zemenbar", holiday.id, holiday.date),
+                summary: format!("{} ({})", holiday.name_amharic, holiday.name_english),
+                description,
+                start: EventStart::Day(holiday.date.to_gregorian()?),
+                categories: Some(format!("{:?}", holiday.category)),
+                transparent: true,
+                extra_lines: Vec::new(),
+                alarm_minutes: Vec::new(),
+            })
+        })
+        .collect()
+}
+
+/// Start of an event's occurrence on `date`, with its time taken in `timezone`.
+fn event_start(event: &events::Event, date: &EthiopianDate, timezone: Tz) -> Option<EventStart> {
+    let day = date.to_gregorian()?;
+    Some(match event.time {
+        Some(time) => EventStart::Time(timezone.from_local_datetime(&day.and_time(time)).earliest()?.with_timezone(&Utc)),
+        None => EventStart::Day(day),
+    })
+}
+
+fn user_vevent(event: events::Event, uid: String, start: EventStart, extra_lines: Vec<String>) -> VEvent {
+    let mut description = ethiopian_date_text(&event.date);
+    if !event.notes.is_empty() {
+        description = format!("{}\n\n{}", description, event.notes);
+    }
+    VEvent {
+        uid,
+        summary: event.title,
+        description,
+        start,
+        categories: None,
+        transparent: false,
+        extra_lines,
+        alarm_minutes: if event.reminder_minutes.is_empty() { vec![0] } else { event.reminder_minutes },
+    }
+}
+
+fn user_events(year: usize) -> Vec<VEvent> {
+    let timezone = crate::today_timezone();
+    (1..=13)
+        .flat_map(|month| events::events_for_month(year, month))
+        .filter_map(|event| {
+            let start = event_start(&event, &event.date, timezone)?;
+            let uid = format!("{}-{}@zemenbar", event.id, event.date);
+            Some(user_vevent(event, uid, start, Vec::new()))
+        })
+        .collect()
+}
+
+/// Ethiopian recurrence kept alongside the Gregorian dates, so it survives a round trip through a server.
+const RECURRENCE_PROPERTY: &str = "X-ZEMENBAR-RECURRENCE";
+
+/// `RRULE` or `RDATE` lines for a stored event's repetitions, empty for a one-off event.
+///
+/// Weekly events get a matching `RRULE`. Yearly and monthly ones have no Gregorian
+/// rule, so their next `RECURRENCE_HORIZON_DAYS` of occurrences are listed as `RDATE`s.
+pub fn recurrence_lines(event: &events::Event, now: DateTime<Utc>) -> Vec<String> {
+    let timezone = crate::today_timezone();
+    match event.recurrence {
+        None => return Vec::new(),
+        Some(Recurrence::Weekly) => return vec!["RRULE:FREQ=WEEKLY".to_string()],
+        Some(Recurrence::Yearly | Recurrence::Monthly) => {}
+    }
+    let Some(horizon) = EthiopianDate::from_naive_date(now.date_naive() + Duration::days(RECURRENCE_HORIZON_DAYS)) else {
+        return Vec::new();
+    };
+
+    let policy = crate::pagume_6_policy();
+    let mut values = Vec::new();
+    let (mut year, mut month) = (event.date.year, event.date.month);
+    while (year, month) <= (horizon.year, horizon.month) {
+        values.extend(
+            event
+                .occurrences_in_month(year, month, policy)
+                .into_iter()
+                .filter(|date| *date != event.date && *date <= horizon)
+                .filter_map(|date| event_start(event, &date, timezone))
+                .map(|start| match start {
+                    EventStart::Day(day) => format_date(day),
+                    EventStart::Time(time) => format_utc(time),
+                }),
+        );
+        (year, month) = if month == 13 { (year + 1, 1) } else { (year, month + 1) };
+    }
+    if values.is_empty() {
+        return Vec::new();
+    }
+    let kind = if event.time.is_some() { "" } else { ";VALUE=DATE" };
+    vec![format!("RDATE{}:{}", kind, values.join(","))]
+}
+
+/// A calendar holding one stored event as a single `VEVENT`, for CalDAV.
+pub fn event_calendar(event: &events::Event, uid: &str, now: DateTime<Utc>) -> Option<String> {
+    let start = event_start(event, &event.date, crate::today_timezone())?;
+    let mut extra_lines = vec![format!("LAST-MODIFIED:{}", format_utc(event.updated_at.unwrap_or(now)))];
+    extra_lines.extend(recurrence_lines(event, now));
+    match event.recurrence {
+        Some(Recurrence::Yearly) => extra_lines.push(format!("{}:YEARLY", RECURRENCE_PROPERTY)),
+        Some(Recurrence::Monthly) => extra_lines.push(format!("{}:MONTHLY", RECURRENCE_PROPERTY)),
+        Some(Recurrence::Weekly) | None => {}
+    }
+
+    let mut output = String::new();
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, &format!("PRODID:{}", PRODUCT_ID));
+    user_vevent(event.clone(), uid.to_string(), start, extra_lines).write(&mut output, &format_utc(now));
+    push_line(&mut output, "END:VCALENDAR");
+    Some(output)
+}
+
+/// The calendar file as a string, with its number of events.
+pub fn build_calendar(options: &IcsExportOptions, now: DateTime<Utc>) -> (String, usize) {
+    let year = options.year.unwrap_or_else(|| EthiopianDate::today().year);
+    let mut vevents = Vec::new();
+    if options.include_holidays {
+        vevents.extend(holiday_events(year, options.public_holidays_only));
+    }
+    if options.include_events {
+        vevents.extend(user_events(year));
+    }
+
+    let output = write_calendar(&format!("ZemenBar {} E.C.", year), &[], &vevents, now);
+    (output, vevents.len())
+}
+
+fn write_calendar(name: &str, header_lines: &[&str], vevents: &[VEvent], now: DateTime<Utc>) -> String {
+    let stamp = format_utc(now);
+    let mut output = String::new();
+    push_line(&mut output, "BEGIN:VCALENDAR");
+    push_line(&mut output, "VERSION:2.0");
+    push_line(&mut output, &format!("PRODID:{}", PRODUCT_ID));
+    push_line(&mut output, "CALSCALE:GREGORIAN");
+    push_line(&mut output, &format!("X-WR-CALNAME:{}", escape_text(name)));
+    for line in header_lines {
+        push_line(&mut output, line);
+    }
+    for vevent in vevents {
+        vevent.write(&mut output, &stamp);
+    }
+    push_line(&mut output, "END:VCALENDAR");
+    output
+}
+
+/// How far ahead the holiday feed reaches, about two years.
+const FEED_HORIZON_DAYS: i64 = 2 * 365;
+
+/// A calendar of the holidays from today until `FEED_HORIZON_DAYS` ahead, for subscribing to.
+///
+/// Unlike an export it spans Ethiopian years, and asks subscribers to check back daily.
+pub fn holiday_feed(public_only: bool, now: DateTime<Utc>) -> (String, usize) {
+    let today = now.with_timezone(&crate::today_timezone()).date_naive();
+    let horizon = today + Duration::days(FEED_HORIZON_DAYS);
+    let first_year = EthiopianDate::from_naive_date(today).map_or(1, |date| date.year);
+    let last_year = EthiopianDate::from_naive_date(horizon).map_or(first_year, |date| date.year);
+
+    let vevents: Vec<VEvent> = (first_year..=last_year)
+        .flat_map(|year| holiday_events(year, public_only))
+        .filter(|vevent| matches!(vevent.start, EventStart::Day(day) if day >= today && day <= horizon))
+        .collect();
+    let header_lines = ["REFRESH-INTERVAL;VALUE=DURATION:P1D", "X-PUBLISHED-TTL:P1D"];
+    let output = write_calendar("Ethiopian Holidays", &header_lines, &vevents, now);
+    (output, vevents.len())
+}
+
+/// Writes an `.ics` file and returns the number of events in it.
+pub fn export_ics(path: &Path, options: &IcsExportOptions) -> Result<usize, String> {
+    if options.year == Some(0) {
+        return Err("Invalid year: 0".to_string());
+    }
+    let (content, count) = build_calendar(options, Utc::now());
+    std::fs::write(path, content).map_err(|e| format!("Failed to write calendar file: {}", e))?;
+    Ok(count)
+}
+
+/// How far ahead recurring events without an end are expanded, about five years.
+const RECURRENCE_HORIZON_DAYS: i64 = 5 * 365;
+
+/// How far back occurrences of a recurring event are imported, about a month.
+const RECURRENCE_LOOKBACK_DAYS: i64 = 30;
+
+/// Most occurrences imported for a single recurring event.
+const MAX_OCCURRENCES: usize = 1000;
+
+/// Outcome of an `.ics` import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IcsImportResult {
+    /// Events added to the store; a weekly series counts once.
+    pub imported: usize,
+    /// Events already in the store with the same date, time and title.
+    pub duplicates: usize,
+    /// Events whose `RRULE` uses parts other than `FREQ`, `INTERVAL`, `COUNT`, `UNTIL`
+    /// and `BYDAY`; only their first occurrence is imported.
+    pub unsupported_rules: usize,
+}
+
+/// A content line split into its name, parameters and value.
+struct Property {
+    name: String,
+    params: Vec<(String, String)>,
+    value: String,
+}
+
+impl Property {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(param, _)| param.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Joins folded lines back together.
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+fn parse_property(line: &str) -> Option<Property> {
+    // The value starts at the first colon outside a quoted parameter value.
+    let mut quoted = false;
+    let colon = line.char_indices().find_map(|(index, c)| match c {
+        '"' => {
+            quoted = !quoted;
+            None
+        }
+        ':' if !quoted => Some(index),
+        _ => None,
+    })?;
+    let mut parts = line[..colon].split(';');
+    let name = parts.next()?.trim().to_ascii_uppercase();
+    let params = parts
+        .filter_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            Some((key.trim().to_ascii_uppercase(), value.trim_matches('"').to_string()))
+        })
+        .collect();
+    Some(Property {
+        name,
+        params,
+        value: line[colon + 1..].to_string(),
+    })
+}
+
+fn unescape_text(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+    unescaped
+}
+
+/// When a `VEVENT` starts, in the terms its `DTSTART` was written in.
+#[derive(Debug, Clone, Copy)]
+enum IcsStart {
+    Day(NaiveDate),
+    /// Wall-clock time in the "today" timezone, for times without a zone.
+    Floating(NaiveDateTime),
+    Utc(NaiveDateTime),
+    Zoned(NaiveDateTime, Tz),
+}
+
+impl IcsStart {
+    fn parse(property: &Property) -> Option<Self> {
+        let value = property.value.trim();
+        if property.param("VALUE").is_some_and(|kind| kind.eq_ignore_ascii_case("DATE")) || value.len() == 8 {
+            return NaiveDate::parse_from_str(value, "%Y%m%d").ok().map(IcsStart::Day);
+        }
+        if let Some(utc) = value.strip_suffix('Z') {
+            return NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok().map(IcsStart::Utc);
+        }
+        let time = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+        // Zones outside the IANA database, such as Windows names, are read as floating times.
+        match property.param("TZID").and_then(|zone| zone.parse::<Tz>().ok()) {
+            Some(zone) => Some(IcsStart::Zoned(time, zone)),
+            None => Some(IcsStart::Floating(time)),
+        }
+    }
+
+    /// Date the rule repeats on, in the event's own timezone.
+    fn date(&self) -> NaiveDate {
+        match self {
+            IcsStart::Day(date) => *date,
+            IcsStart::Floating(time) | IcsStart::Utc(time) | IcsStart::Zoned(time, _) => time.date(),
+        }
+    }
+
+    /// Ethiopian date and start time, in `timezone`, of the occurrence on `date`.
+    fn on(&self, date: NaiveDate, timezone: Tz) -> Option<(EthiopianDate, Option<NaiveTime>)> {
+        let local = match self {
+            IcsStart::Day(_) => return Some((EthiopianDate::from_naive_date(date)?, None)),
+            IcsStart::Floating(time) => date.and_time(time.time()),
+            IcsStart::Utc(time) => Utc.from_utc_datetime(&date.and_time(time.time())).with_timezone(&timezone).naive_local(),
+            IcsStart::Zoned(time, zone) => zone
+                .from_local_datetime(&date.and_time(time.time()))
+                .earliest()?
+                .with_timezone(&timezone)
+                .naive_local(),
+        };
+        Some((EthiopianDate::from_naive_date(local.date())?, Some(local.time())))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The supported subset of an `RRULE`.
+#[derive(Debug, Clone)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<NaiveDate>,
+    /// Weekdays with an optional ordinal, e.g. `2TU` is `(Some(2), Tue)`.
+    by_day: Vec<(Option<i32>, Weekday)>,
+}
+
+fn parse_weekday(code: &str) -> Option<Weekday> {
+    match code {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+impl RecurrenceRule {
+    /// `None` when the rule uses parts this importer does not understand.
+    fn parse(value: &str) -> Option<Self> {
+        let mut frequency = None;
+        let mut rule = RecurrenceRule {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+        };
+        for part in value.trim().split(';').filter(|part| !part.is_empty()) {
+            let (key, value) = part.split_once('=')?;
+            match key.to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|interval| *interval > 0)?,
+                "COUNT" => rule.count = Some(value.parse().ok()?),
+                "UNTIL" => rule.until = Some(NaiveDate::parse_from_str(value.get(..8)?, "%Y%m%d").ok()?),
+                "BYDAY" => {
+                    for day in value.split(',') {
+                        let day = day.trim().to_ascii_uppercase();
+                        let (ordinal, code) = day.split_at(day.len().checked_sub(2)?);
+                        let ordinal = if ordinal.is_empty() { None } else { Some(ordinal.parse().ok()?) };
+                        rule.by_day.push((ordinal, parse_weekday(code)?));
+                    }
+                }
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+        rule.frequency = frequency?;
+        // Weekly rules take plain weekdays and monthly ones an ordinal, as in `2TU` or `-1FR`.
+        let by_day_supported = match rule.frequency {
+            Frequency::Weekly => rule.by_day.iter().all(|(ordinal, _)| ordinal.is_none()),
+            Frequency::Monthly => rule
+                .by_day
+                .iter()
+                .all(|(ordinal, _)| ordinal.is_some_and(|n| n != 0 && (-5..=5).contains(&n))),
+            Frequency::Daily | Frequency::Yearly => rule.by_day.is_empty(),
+        };
+        by_day_supported.then_some(rule)
+    }
+
+    /// Whether the rule is the same as an Ethiopian weekly event with no end.
+    fn is_plain_weekly(&self, start: NaiveDate) -> bool {
+        self.frequency == Frequency::Weekly
+            && self.interval == 1
+            && self.count.is_none()
+            && self.until.is_none()
+            && self.by_day.iter().all(|(_, weekday)| *weekday == start.weekday())
+    }
+
+    /// Dates of the `period`-th interval from `start`, before filtering by `start` and the end.
+    fn period_dates(&self, start: NaiveDate, period: u32) -> Vec<NaiveDate> {
+        let step = self.interval * period;
+        match self.frequency {
+            Frequency::Daily => start.checked_add_signed(Duration::days(step as i64)).into_iter().collect(),
+            Frequency::Weekly => {
+                let week_start = start - Duration::days(start.weekday().num_days_from_monday() as i64)
+                    + Duration::weeks(step as i64);
+                let mut weekdays: Vec<Weekday> = self.by_day.iter().map(|(_, weekday)| *weekday).collect();
+                if weekdays.is_empty() {
+                    weekdays.push(start.weekday());
+                }
+                weekdays.sort_by_key(|weekday| weekday.num_days_from_monday());
+                weekdays.dedup();
+                weekdays
+                    .into_iter()
+                    .map(|weekday| week_start + Duration::days(weekday.num_days_from_monday() as i64))
+                    .collect()
+            }
+            Frequency::Monthly => {
+                let months = start.year() * 12 + start.month0() as i32 + step as i32;
+                let (year, month) = (months.div_euclid(12), months.rem_euclid(12) as u32 + 1);
+                if self.by_day.is_empty() {
+                    return NaiveDate::from_ymd_opt(year, month, start.day()).into_iter().collect();
+                }
+                let mut dates: Vec<NaiveDate> = self
+                    .by_day
+                    .iter()
+                    .filter_map(|(ordinal, weekday)| nth_weekday_of_month(year, month, *weekday, (*ordinal)?))
+                    .collect();
+                dates.sort();
+                dates.dedup();
+                dates
+            }
+            Frequency::Yearly => NaiveDate::from_ymd_opt(start.year() + step as i32, start.month(), start.day())
+                .into_iter()
+                .collect(),
+        }
+    }
+
+    /// Occurrence dates from `from`, up to the rule's end or `horizon`, whichever is earlier.
+    ///
+    /// The series is still stepped from `start`, so it keeps its phase and `COUNT`
+    /// includes the occurrences before `from`; `MAX_OCCURRENCES` only caps the ones kept.
+    fn dates(&self, start: NaiveDate, from: NaiveDate, horizon: NaiveDate) -> Vec<NaiveDate> {
+        let last = self.until.map_or(horizon, |until| until.min(horizon));
+        let count = self.count.unwrap_or(usize::MAX);
+        let mut seen = 0;
+        let mut dates = Vec::new();
+        for period in 0.. {
+            let period_dates = self.period_dates(start, period);
+            // Periods without the day, such as a February for day 31, are empty and skipped.
+            if period_dates.first().is_some_and(|first| *first > last) || period > 50_000 {
+                break;
+            }
+            for date in period_dates.into_iter().filter(|date| *date >= start && *date <= last) {
+                if seen == count || dates.len() == MAX_OCCURRENCES {
+                    return dates;
+                }
+                seen += 1;
+                if date >= from {
+                    dates.push(date);
+                }
+            }
+        }
+        dates
+    }
+}
+
+fn nth_weekday_of_month(year: i32, month: u32, weekday: Weekday, ordinal: i32) -> Option<NaiveDate> {
+    if ordinal > 0 {
+        NaiveDate::from_weekday_of_month_opt(year, month, weekday, ordinal as u8)
+    } else {
+        let next_month = if month == 12 { NaiveDate::from_ymd_opt(year + 1, 1, 1)? } else { NaiveDate::from_ymd_opt(year, month + 1, 1)? };
+        let last = next_month.pred_opt()?;
+        let back = (last.weekday().num_days_from_monday() + 7 - weekday.num_days_from_monday()) % 7;
+        let date = last - Duration::days(back as i64 + 7 * (-ordinal - 1) as i64);
+        (date.month() == month).then_some(date)
+    }
+}
+
+/// Minutes before the start for a relative `TRIGGER` such as `-PT15M` or `-P1D`.
+fn parse_trigger(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let (before, rest) = match value.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, value.strip_prefix('+').unwrap_or(value)),
+    };
+    let mut seconds = 0u64;
+    let mut number = String::new();
+    for c in rest.strip_prefix('P')?.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            'W' | 'D' | 'H' | 'M' | 'S' => {
+                let amount: u64 = number.parse().ok()?;
+                number.clear();
+                seconds += amount
+                    * match c {
+                        'W' => 7 * 86_400,
+                        'D' => 86_400,
+                        'H' => 3_600,
+                        'M' => 60,
+                        _ => 1,
+                    };
+            }
+            _ => return None,
+        }
+    }
+    // Reminders after the start have no equivalent; a zero offset still reminds at the start.
+    if !before && seconds > 0 {
+        return None;
+    }
+    u32::try_from(seconds / 60).ok().filter(|minutes| *minutes <= MAX_REMINDER_MINUTES)
+}
+
+/// The parts of a `VEVENT` the importer uses.
+#[derive(Default)]
+struct IcsEvent {
+    uid: String,
+    start: Option<IcsStart>,
+    summary: String,
+    description: String,
+    rule: Option<String>,
+    /// Ethiopian recurrence written by `event_calendar`.
+    ethiopian_recurrence: Option<Recurrence>,
+    excluded: Vec<NaiveDate>,
+    has_extra_dates: bool,
+    reminders: Vec<u32>,
+    last_modified: Option<DateTime<Utc>>,
+    /// Set on a changed instance of a recurring event, which shares its series' `UID`.
+    is_override: bool,
+    cancelled: bool,
+}
+
+fn parse_events(content: &str) -> Vec<IcsEvent> {
+    let mut parsed = Vec::new();
+    let mut current: Option<IcsEvent> = None;
+    let mut in_alarm = false;
+
+    for property in unfold(content).iter().filter_map(|line| parse_property(line)) {
+        let value = property.value.trim().to_ascii_uppercase();
+        match (property.name.as_str(), current.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => current = Some(IcsEvent::default()),
+            ("BEGIN", Some(_)) if value == "VALARM" => in_alarm = true,
+            ("END", Some(_)) if value == "VALARM" => in_alarm = false,
+            ("END", Some(_)) if value == "VEVENT" => parsed.extend(current.take()),
+            ("TRIGGER", Some(event)) if in_alarm => event.reminders.extend(parse_trigger(&property.value)),
+            (_, Some(_)) if in_alarm => {}
+            ("DTSTART", Some(event)) => event.start = IcsStart::parse(&property),
+            ("SUMMARY", Some(event)) => event.summary = unescape_text(&property.value),
+            ("DESCRIPTION", Some(event)) => event.description = unescape_text(&property.value),
+            ("RRULE", Some(event)) => event.rule = Some(property.value.clone()),
+            ("EXDATE", Some(event)) => event.excluded.extend(
+                property
+                    .value
+                    .split(',')
+                    .filter_map(|date| NaiveDate::parse_from_str(date.trim().get(..8)?, "%Y%m%d").ok()),
+            ),
+            ("STATUS", Some(event)) => event.cancelled = value == "CANCELLED",
+            ("UID", Some(event)) => event.uid = property.value.trim().to_string(),
+            ("LAST-MODIFIED", Some(event)) => {
+                event.last_modified = value
+                    .strip_suffix('Z')
+                    .and_then(|time| NaiveDateTime::parse_from_str(time, "%Y%m%dT%H%M%S").ok())
+                    .map(|time| time.and_utc())
+            }
+            ("RDATE", Some(event)) => event.has_extra_dates = true,
+            ("RECURRENCE-ID", Some(event)) => event.is_override = true,
+            (RECURRENCE_PROPERTY, Some(event)) => {
+                event.ethiopian_recurrence = match value.as_str() {
+                    "YEARLY" => Some(Recurrence::Yearly),
+                    "MONTHLY" => Some(Recurrence::Monthly),
+                    "WEEKLY" => Some(Recurrence::Weekly),
+                    _ => None,
+                }
+            }
+            _ => {}
+        }
+    }
+    parsed
+}
+
+fn event_input(event: &IcsEvent, date: EthiopianDate, time: Option<NaiveTime>, recurrence: Option<Recurrence>) -> EventInput {
+    let title = event.summary.trim();
+    EventInput {
+        date: EthiopianDateInput {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+        },
+        title: if title.is_empty() { "Untitled event".to_string() } else { title.to_string() },
+        notes: event.description.trim().to_string(),
+        color: None,
+        recurrence,
+        time,
+        reminder_minutes: event.reminders.clone(),
+    }
+}
+
+/// Ethiopian events for every `VEVENT` in an iCalendar file, with the number of
+/// events whose recurrence rule could not be followed.
+pub fn events_from_calendar(content: &str, timezone: Tz, today: NaiveDate) -> (Vec<EventInput>, usize) {
+    let from = today - Duration::days(RECURRENCE_LOOKBACK_DAYS);
+    let horizon = today + Duration::days(RECURRENCE_HORIZON_DAYS);
+    let mut inputs = Vec::new();
+    let mut unsupported_rules = 0;
+
+    for event in parse_events(content).iter().filter(|event| !event.cancelled) {
+        let Some(start) = event.start else {
+            continue;
+        };
+        let first = start.date();
+        let rule = match event.rule.as_deref().map(RecurrenceRule::parse) {
+            Some(None) => {
+                unsupported_rules += 1;
+                None
+            }
+            Some(Some(rule)) => Some(rule),
+            None => None,
+        };
+
+        match rule {
+            Some(rule) if rule.is_plain_weekly(first) && !event.excluded.iter().any(|date| *date >= first) => {
+                inputs.extend(start.on(first, timezone).map(|(date, time)| event_input(event, date, time, Some(Recurrence::Weekly))));
+            }
+            Some(rule) => inputs.extend(
+                rule.dates(first, from, horizon)
+                    .into_iter()
+                    .filter(|date| !event.excluded.contains(date))
+                    .filter_map(|date| start.on(date, timezone))
+                    .map(|(date, time)| event_input(event, date, time, None)),
+            ),
+            None => inputs.extend(start.on(first, timezone).map(|(date, time)| event_input(event, date, time, None))),
+        }
+    }
+    (inputs, unsupported_rules)
+}
+
+/// An event read from a CalDAV calendar object.
+pub struct RemoteEvent {
+    pub uid: String,
+    pub last_modified: Option<DateTime<Utc>>,
+    pub input: EventInput,
+    /// The server copy repeats in a way a stored event cannot, such as a Gregorian
+    /// monthly rule, so only its first occurrence is kept and it is never written back.
+    pub pull_only: bool,
+}
+
+/// The main `VEVENT` of a calendar object, placed on its Ethiopian date in `timezone`.
+pub fn remote_event(content: &str, timezone: Tz) -> Option<RemoteEvent> {
+    let event = parse_events(content).into_iter().find(|event| !event.is_override)?;
+    let start = event.start?;
+    let (date, time) = start.on(start.date(), timezone)?;
+    let (recurrence, pull_only) = match (event.ethiopian_recurrence, event.rule.as_deref()) {
+        (Some(recurrence), _) => (Some(recurrence), false),
+        (None, None) => (None, event.has_extra_dates),
+        (None, Some(rule)) => match RecurrenceRule::parse(rule) {
+            Some(rule) if rule.is_plain_weekly(start.date()) && event.excluded.is_empty() && !event.has_extra_dates => {
+                (Some(Recurrence::Weekly), false)
+            }
+            _ => (None, true),
+        },
+    };
+    Some(RemoteEvent {
+        uid: event.uid.clone(),
+        last_modified: event.last_modified,
+        input: event_input(&event, date, time, recurrence),
+        pull_only,
+    })
+}
+
+/// Reads an `.ics` file into the events store at `events_path`.
+pub fn import_ics(path: &Path, events_path: &Path) -> Result<IcsImportResult, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read calendar file: {}", e))?;
+    if !content.contains("BEGIN:VCALENDAR") {
+        return Err("Not an iCalendar file".to_string());
+    }
+    let today = EthiopianDate::today()
+        .to_gregorian()
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let (inputs, unsupported_rules) = events_from_calendar(&content, crate::today_timezone(), today);
+    let total = inputs.len();
+    let imported = events::import_events(events_path, inputs)?;
+    Ok(IcsImportResult {
+        imported,
+        duplicates: total - imported,
+        unsupported_rules,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn calendar(start: &str, rule: &str) -> String {
+        format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:test\r\nDTSTART;VALUE=DATE:{}\r\nRRULE:{}\r\nSUMMARY:Test\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            start, rule
+        )
+    }
+
+    #[test]
+    fn rules_with_unsupported_parts_are_rejected() {
+        for rule in [
+            "FREQ=HOURLY",
+            "FREQ=DAILY;BYHOUR=9",
+            "FREQ=WEEKLY;BYDAY=2MO",
+            "FREQ=MONTHLY;BYDAY=MO",
+            "FREQ=DAILY;INTERVAL=0",
+            "INTERVAL=2",
+        ] {
+            assert!(RecurrenceRule::parse(rule).is_none(), "{}", rule);
+        }
+        for rule in [
+            "FREQ=WEEKLY;BYDAY=MO,WE;WKST=SU",
+            "FREQ=MONTHLY;BYDAY=-1FR",
+            "FREQ=YEARLY;UNTIL=20300101T000000Z",
+        ] {
+            assert!(RecurrenceRule::parse(rule).is_some(), "{}", rule);
+        }
+    }
+
+    #[test]
+    fn rules_expand_to_their_dates() {
+        let today = date(2026, 10, 15);
+        let from = today - Duration::days(RECURRENCE_LOOKBACK_DAYS);
+        let horizon = today + Duration::days(RECURRENCE_HORIZON_DAYS);
+        let cases = [
+            (
+                "FREQ=WEEKLY;BYDAY=MO,WE;UNTIL=20261025",
+                date(2026, 10, 12),
+                vec![
+                    date(2026, 10, 12),
+                    date(2026, 10, 14),
+                    date(2026, 10, 19),
+                    date(2026, 10, 21),
+                ],
+            ),
+            (
+                "FREQ=MONTHLY;BYDAY=-1FR;COUNT=3",
+                date(2026, 10, 30),
+                vec![date(2026, 10, 30), date(2026, 11, 27), date(2026, 12, 25)],
+            ),
+            // Leap days only come back in leap years.
+            ("FREQ=YEARLY", date(2024, 2, 29), vec![date(2028, 2, 29)]),
+            // `COUNT` includes the occurrences before the look-back window.
+            (
+                "FREQ=DAILY;COUNT=20",
+                date(2026, 9, 1),
+                (15..=20).map(|day| date(2026, 9, day)).collect(),
+            ),
+        ];
+        for (rule, start, expected) in cases {
+            assert_eq!(
+                RecurrenceRule::parse(rule).unwrap().dates(start, from, horizon),
+                expected,
+                "{}",
+                rule
+            );
+        }
+    }
+
+    #[test]
+    fn old_series_keep_their_phase_from_the_start() {
+        let today = date(2026, 10, 15);
+        let rule = RecurrenceRule::parse("FREQ=MONTHLY;INTERVAL=2").unwrap();
+        let dates = rule.dates(
+            date(2020, 1, 31),
+            today - Duration::days(RECURRENCE_LOOKBACK_DAYS),
+            today + Duration::days(RECURRENCE_HORIZON_DAYS),
+        );
+        // September and November 2026 are in phase but have no 31st.
+        assert_eq!(dates[..3], [date(2027, 1, 31), date(2027, 3, 31), date(2027, 5, 31)]);
+    }
+
+    #[test]
+    fn open_ended_series_are_imported_from_around_today() {
+        let (inputs, unsupported) = events_from_calendar(
+            &calendar("20150101", "FREQ=DAILY"),
+            crate::DEFAULT_TIMEZONE,
+            date(2026, 10, 15),
+        );
+        assert_eq!(unsupported, 0);
+        assert_eq!(inputs.len(), MAX_OCCURRENCES);
+        // Gregorian 2026-09-15, a month before today.
+        let first = &inputs[0].date;
+        assert_eq!((first.year, first.month, first.day), (2019, 1, 5));
+    }
+
+    #[test]
+    fn plain_weekly_series_stay_a_single_event() {
+        let (inputs, _) = events_from_calendar(
+            &calendar("20150105", "FREQ=WEEKLY;BYDAY=MO"),
+            crate::DEFAULT_TIMEZONE,
+            date(2026, 10, 15),
+        );
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].recurrence, Some(Recurrence::Weekly));
+    }
+}
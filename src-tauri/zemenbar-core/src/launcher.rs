@@ -0,0 +1,130 @@
+//! Script Filter JSON for launchers such as Alfred and Raycast.
+//!
+//! With no query the items are today's date and the next few holidays. A query is
+//! tried as a Gregorian `YYYY-MM-DD` date, as a written Ethiopian date (see
+//! `date_input`) and as a holiday name, and each reading that works becomes an item.
+//! Every item's `arg` is the text a launcher should copy or paste.
+
+use crate::format::FormatOptions;
+use crate::locale::Language;
+use crate::{holidays, strings, EthiopianDate};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// How many upcoming holidays are listed when there is no query.
+const UPCOMING_COUNT: usize = 5;
+
+/// Top-level Script Filter document.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScriptFilter {
+    pub items: Vec<ScriptFilterItem>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptFilterItem {
+    pub uid: String,
+    pub title: String,
+    pub subtitle: String,
+    /// Passed on when the item is chosen.
+    pub arg: String,
+    /// Text for the copy and large type actions.
+    pub text: ScriptFilterText,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScriptFilterText {
+    pub copy: String,
+    pub largetype: String,
+}
+
+impl ScriptFilterItem {
+    fn new(uid: String, title: String, subtitle: String, arg: String) -> Self {
+        Self {
+            uid,
+            text: ScriptFilterText {
+                copy: arg.clone(),
+                largetype: title.clone(),
+            },
+            title,
+            subtitle,
+            arg,
+        }
+    }
+}
+
+fn ethiopian_text(date: &EthiopianDate, language: Language) -> String {
+    let template = match language {
+        Language::Amharic | Language::Tigrinya => "%LA፣ %LB %e %Y %EC",
+        Language::English | Language::Oromo => "%LA, %LB %e, %Y %C",
+    };
+    date.format_with(template, FormatOptions { language, ..Default::default() })
+}
+
+fn gregorian_text(date: NaiveDate) -> String {
+    date.format("%A, %B %-d, %Y").to_string()
+}
+
+fn today_item(today: &EthiopianDate, language: Language) -> Option<ScriptFilterItem> {
+    let text = ethiopian_text(today, language);
+    let gregorian = gregorian_text(today.to_gregorian()?);
+    Some(ScriptFilterItem::new("today".to_string(), text.clone(), format!("Today · {}", gregorian), text))
+}
+
+fn upcoming_items(today: &EthiopianDate, language: Language) -> Vec<ScriptFilterItem> {
+    holidays::upcoming_holidays(today, UPCOMING_COUNT, language)
+        .into_iter()
+        .map(|holiday| {
+            let when = match holiday.days_remaining {
+                0 => "today".to_string(),
+                1 => "tomorrow".to_string(),
+                days => format!("in {} days", days),
+            };
+            let subtitle = format!("{} · {} · {}", ethiopian_text(&holiday.date, language), holiday.gregorian_date, when);
+            let uid = format!("holiday-{}-{}", holiday.id, holiday.date);
+            ScriptFilterItem::new(uid, holiday.name.clone(), subtitle, holiday.name)
+        })
+        .collect()
+}
+
+fn query_items(query: &str, today: &EthiopianDate, language: Language) -> Vec<ScriptFilterItem> {
+    let mut items = Vec::new();
+
+    if let Some(date) = NaiveDate::parse_from_str(query, "%Y-%m-%d")
+        .ok()
+        .and_then(EthiopianDate::from_naive_date)
+    {
+        let text = ethiopian_text(&date, language);
+        let subtitle = format!("{} in the Ethiopian calendar", query);
+        items.push(ScriptFilterItem::new(format!("to-ethiopian-{}", query), text.clone(), subtitle, text));
+    }
+
+    if let Ok(date) = EthiopianDate::parse(query) {
+        if let Some(gregorian) = date.to_gregorian() {
+            let subtitle = format!("{} in the Gregorian calendar", ethiopian_text(&date, language));
+            let iso = gregorian.format("%Y-%m-%d").to_string();
+            items.push(ScriptFilterItem::new(format!("to-gregorian-{}", date), gregorian_text(gregorian), subtitle, iso));
+        }
+    }
+
+    for found in holidays::find_holiday(query, today.year) {
+        let name = strings::holiday_name(&found.holiday, language);
+        let subtitle = format!("{} · {}", ethiopian_text(&found.holiday.date, language), found.gregorian_date);
+        let uid = format!("holiday-{}-{}", found.holiday.id, found.holiday.date);
+        items.push(ScriptFilterItem::new(uid, name, subtitle, found.gregorian_date));
+    }
+    items
+}
+
+/// Items for `query`, or today's date and the upcoming holidays when it is empty.
+pub fn script_filter(query: Option<&str>, language: Language) -> ScriptFilter {
+    let today = EthiopianDate::today();
+    let query = query.map(str::trim).filter(|query| !query.is_empty());
+    let items = match query {
+        Some(query) => query_items(query, &today, language),
+        None => today_item(&today, language)
+            .into_iter()
+            .chain(upcoming_items(&today, language))
+            .collect(),
+    };
+    ScriptFilter { items }
+}
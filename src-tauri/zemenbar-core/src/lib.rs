@@ -0,0 +1,1066 @@
+//! Ethiopian calendar core of ZemenBar: dates and their Gregorian conversion, Geez
+//! numerals, formatting, holidays, events and birthdays, and the month grid.
+//!
+//! Has no GUI dependencies, so the menu bar app, the `zemenbar-cli` binary, the WASM
+//! bindings (the `wasm` feature) and the C bindings (the `ffi` feature) all share it.
+
+pub mod academic;
+pub mod age;
+pub mod astronomy;
+pub mod bahire_hasab;
+pub mod birthdays;
+pub mod business_days;
+pub mod cache;
+pub mod clock;
+pub mod custom_holidays;
+pub mod date_input;
+pub mod date_serde;
+pub mod events;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
+pub mod hijri;
+pub mod holidays;
+pub mod ics;
+pub mod launcher;
+pub mod locale;
+pub mod months;
+pub mod packs;
+pub mod paydays;
+pub mod query;
+pub mod seasons;
+pub mod spoken;
+pub mod strings;
+pub mod synaxarium;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+use chrono::{Datelike, NaiveDate, Utc};
+use chrono_tz::Tz;
+use ethiopic_calendar::{EthiopianYear, GregorianYear};
+use locale::Language;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+/// Julian Day Number of Meskerem 1, year 1 (Amete Mihret).
+const ETHIOPIAN_EPOCH_JDN: i64 = 1_724_221;
+
+/// Offset between chrono's days-from-CE count (0001-01-01 is day 1) and the Julian Day Number.
+const GREGORIAN_CE_JDN_OFFSET: i64 = 1_721_425;
+
+/// Timezone that decides which day "today" is unless the settings choose another.
+pub const DEFAULT_TIMEZONE: Tz = Tz::Africa__Addis_Ababa;
+
+/// Timezone override chosen in the app's settings, read by `EthiopianDate::today()`.
+static TODAY_TIMEZONE: Mutex<Option<Tz>> = Mutex::new(None);
+
+/// Sets the timezone `EthiopianDate::today()` uses; `None` restores Addis Ababa time.
+pub fn set_today_timezone(timezone: Option<Tz>) {
+    if let Ok(mut current) = TODAY_TIMEZONE.lock() {
+        *current = timezone;
+    }
+}
+
+/// The timezone that currently decides "today".
+pub fn today_timezone() -> Tz {
+    TODAY_TIMEZONE.lock().ok().and_then(|timezone| *timezone).unwrap_or(DEFAULT_TIMEZONE)
+}
+
+/// Offset between Amete Mihret and Amete Alem (ዓመተ ዓለም) years.
+pub const AMETE_ALEM_OFFSET: usize = 5500;
+
+/// Era a year is counted in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Era {
+    /// ዓመተ ምሕረት, the civil era (2017 for 2024/25).
+    #[default]
+    AmeteMihret,
+    /// ዓመተ ዓለም, counted from creation (Amete Mihret + 5500), common in church documents.
+    AmeteAlem,
+}
+
+impl Era {
+    pub fn amharic_abbreviation(&self) -> &'static str {
+        match self {
+            Era::AmeteMihret => "ዓ.ም.",
+            Era::AmeteAlem => "ዓ.ዓ.",
+        }
+    }
+
+    pub fn english_abbreviation(&self) -> &'static str {
+        match self {
+            Era::AmeteMihret => "E.C.",
+            Era::AmeteAlem => "A.A.",
+        }
+    }
+}
+
+/// When yearly dates on Pagumē 6, which only leap years have, are kept in other years.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Pagume6Policy {
+    /// On Pagumē 5, the last day of the year.
+    #[default]
+    Pagume5,
+    /// On Meskerem 1, the day that follows Pagumē 5.
+    Meskerem1,
+    /// Only in leap years.
+    LeapYearsOnly,
+}
+
+/// Pagumē 6 policy chosen in the app's settings, read by the recurrence code.
+static PAGUME_6_POLICY: Mutex<Pagume6Policy> = Mutex::new(Pagume6Policy::Pagume5);
+
+pub fn pagume_6_policy() -> Pagume6Policy {
+    PAGUME_6_POLICY.lock().map(|policy| *policy).unwrap_or_default()
+}
+
+pub fn set_pagume_6_policy(policy: Pagume6Policy) {
+    if let Ok(mut current) = PAGUME_6_POLICY.lock() {
+        *current = policy;
+    }
+}
+
+/// Represents a date in the Ethiopian calendar system.
+///
+/// The Ethiopian calendar has 13 months: 12 months of 30 days each,
+/// plus Pagumē with 5 or 6 days depending on leap years.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EthiopianDate {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+    /// Day formatted in Geez numerals
+    pub day_geez: String,
+}
+
+impl EthiopianDate {
+    /// Creates an `EthiopianDate` from its year, month and day.
+    pub fn new(year: usize, month: usize, day: usize) -> Self {
+        Self {
+            year,
+            month,
+            day,
+            day_geez: Self::to_geez_number(day),
+        }
+    }
+
+    /// Creates an `EthiopianDate` representing today's date in the configured timezone
+    /// (Addis Ababa, EAT, by default).
+    pub fn today() -> Self {
+        Self::today_in_tz(today_timezone())
+    }
+
+    /// Today's date as seen in `timezone`.
+    pub fn today_in_tz(timezone: Tz) -> Self {
+        let today = Utc::now().with_timezone(&timezone).date_naive();
+        Self::from_naive_date(today).expect("today is after the Ethiopian epoch")
+    }
+
+    /// Julian Day Number of this date.
+    ///
+    /// This is the canonical conversion path: Gregorian dates and weekdays are derived from it.
+    pub fn to_jdn(&self) -> i64 {
+        let year = self.year as i64;
+        ETHIOPIAN_EPOCH_JDN + 365 * (year - 1) + year / 4 + 30 * (self.month as i64 - 1) + self.day as i64 - 1
+    }
+
+    /// Creates the Ethiopian date for a Julian Day Number; `None` before Meskerem 1, year 1.
+    pub fn from_jdn(jdn: i64) -> Option<Self> {
+        // Count from the start of the year before year 1 so each 4-year cycle ends with the leap year.
+        let days = jdn - (ETHIOPIAN_EPOCH_JDN - 365);
+        if days < 365 {
+            return None;
+        }
+
+        let cycle_day = days % 1461;
+        let day_of_year = cycle_day % 365 + 365 * (cycle_day / 1460);
+        let year = 4 * (days / 1461) + cycle_day / 365 - cycle_day / 1460;
+        Some(Self::new(year as usize, day_of_year as usize / 30 + 1, day_of_year as usize % 30 + 1))
+    }
+
+    /// Converts a Gregorian date to Ethiopian calendar.
+    ///
+    /// Returns `None` if the Gregorian date does not exist or precedes the Ethiopian epoch.
+    pub fn from_gregorian(year: i32, month: u32, day: u32) -> Option<Self> {
+        Self::from_naive_date(NaiveDate::from_ymd_opt(year, month, day)?)
+    }
+
+    /// Converts this date to its Gregorian equivalent.
+    ///
+    /// Returns `None` if the resulting Gregorian date is invalid.
+    pub fn to_gregorian(&self) -> Option<NaiveDate> {
+        let days_from_ce = i32::try_from(self.to_jdn() - GREGORIAN_CE_JDN_OFFSET).ok()?;
+        let gregorian = NaiveDate::from_num_days_from_ce_opt(days_from_ce)?;
+
+        #[cfg(debug_assertions)]
+        self.cross_check_gregorian(gregorian);
+
+        Some(gregorian)
+    }
+
+    /// Logs when the `ethiopic_calendar` crate disagrees with the JDN conversion (debug builds only).
+    #[cfg(debug_assertions)]
+    fn cross_check_gregorian(&self, gregorian: NaiveDate) {
+        if !self.is_valid() {
+            return;
+        }
+        let other: GregorianYear = EthiopianYear::new(self.year, self.month, self.day).into();
+        let other = (other.year() as i32, other.month() as u32, other.day() as u32);
+        if other != (gregorian.year(), gregorian.month(), gregorian.day()) {
+            tracing::warn!(
+                "Conversion mismatch for {}: JDN gives {}, ethiopic_calendar gives {:?}",
+                self,
+                gregorian,
+                other
+            );
+        }
+    }
+
+    /// Converts a chrono `NaiveDate` to Ethiopian calendar.
+    pub fn from_naive_date(date: NaiveDate) -> Option<Self> {
+        Self::from_jdn(date.num_days_from_ce() as i64 + GREGORIAN_CE_JDN_OFFSET)
+    }
+
+    /// Returns the date `days` days after (or before, if negative) this one.
+    pub fn add_days(&self, days: i64) -> Option<Self> {
+        Self::from_jdn(self.to_jdn() + days)
+    }
+
+    /// Returns the date `months` months later (or earlier), counting Pagumē as the 13th month.
+    ///
+    /// The day is clamped to the target month, so Nehasē 30 plus one month is Pagumē 5 (or 6).
+    pub fn add_months(&self, months: i64) -> Option<Self> {
+        let index = self.year as i64 * 13 + (self.month as i64 - 1) + months;
+        if index < 13 {
+            return None;
+        }
+
+        let year = (index / 13) as usize;
+        let month = (index % 13) as usize + 1;
+        let day = self.day.min(Self::new(year, month, 1).days_in_month());
+        Some(Self::new(year, month, day))
+    }
+
+    /// Returns the same day `years` years later (or earlier); Pagumē 6 becomes Pagumē 5 outside leap years.
+    pub fn add_years(&self, years: i64) -> Option<Self> {
+        self.add_months(years * 13)
+    }
+
+    /// Number of days from this date to `other` (negative if `other` is earlier).
+    pub fn days_between(&self, other: &EthiopianDate) -> Option<i64> {
+        Some(other.to_jdn() - self.to_jdn())
+    }
+
+    /// The following day.
+    pub fn succ(&self) -> Self {
+        if self.day < self.days_in_month() {
+            Self::new(self.year, self.month, self.day + 1)
+        } else if self.month < 13 {
+            Self::new(self.year, self.month + 1, 1)
+        } else {
+            Self::new(self.year + 1, 1, 1)
+        }
+    }
+
+    /// The preceding day.
+    pub fn pred(&self) -> Self {
+        if self.day > 1 {
+            Self::new(self.year, self.month, self.day - 1)
+        } else if self.month > 1 {
+            Self::new(self.year, self.month - 1, 30)
+        } else {
+            let year = self.year.saturating_sub(1);
+            Self::new(year, 13, Self::pagume_length(year))
+        }
+    }
+
+    /// Every date from `from` to `to`, inclusive.
+    pub fn iter_days(from: EthiopianDate, to: EthiopianDate) -> DateRange {
+        DateRange::new(from, to)
+    }
+
+    /// Every day of a month, including all of Pagumē.
+    pub fn iter_month(year: usize, month: usize) -> DateRange {
+        let last_day = Self::new(year, month, 1).days_in_month();
+        DateRange::new(Self::new(year, month, 1), Self::new(year, month, last_day))
+    }
+
+    /// (year, month, day), ordered the same way as the dates themselves.
+    fn sort_key(&self) -> (usize, usize, usize) {
+        (self.year, self.month, self.day)
+    }
+
+    /// Whether the month and day exist in that year (rejects e.g. Pagumē 6 outside leap years).
+    pub fn is_valid(&self) -> bool {
+        self.year > 0 && (1..=13).contains(&self.month) && self.day >= 1 && self.day <= self.days_in_month()
+    }
+
+    /// Week number within the Ethiopian year, counting the week containing Meskerem 1 as week 1.
+    ///
+    /// `week_start` is the weekday weeks begin on (0 = Sunday).
+    pub fn week_of_year(&self, week_start: usize) -> usize {
+        let new_year_weekday = Self::new(self.year, 1, 1).weekday();
+        let leading_days = (new_year_weekday + 7 - week_start % 7) % 7;
+        (self.day_of_year() - 1 + leading_days) / 7 + 1
+    }
+
+    pub fn amharic_month(&self) -> &'static str {
+        Language::Amharic.month_name(self.month)
+    }
+
+    pub fn english_month(&self) -> &'static str {
+        Language::English.month_name(self.month)
+    }
+
+    /// Month name in any supported language.
+    pub fn month_name(&self, language: Language) -> &'static str {
+        language.month_name(self.month)
+    }
+
+    pub fn days_in_month(&self) -> usize {
+        if self.month == 13 {
+            Self::pagume_length(self.year)
+        } else {
+            30
+        }
+    }
+
+    /// Whether `year` is a leap year (every fourth year, the one before a year divisible by 4).
+    pub fn is_leap_year(year: usize) -> bool {
+        year % 4 == 3
+    }
+
+    /// Number of days in Pagumē: 6 in leap years, otherwise 5.
+    pub fn pagume_length(year: usize) -> usize {
+        if Self::is_leap_year(year) {
+            6
+        } else {
+            5
+        }
+    }
+
+    /// The first Pagumē 6 on or after `from`.
+    pub fn next_pagume_6(from: &EthiopianDate) -> EthiopianDate {
+        let year = (from.year..).find(|year| Self::is_leap_year(*year)).unwrap_or(from.year);
+        Self::new(year, 13, 6)
+    }
+
+    /// Whether `date` is a yearly anniversary of this date (or the date itself).
+    ///
+    /// Anniversaries of Pagumē 6 in common years follow `policy`.
+    pub fn is_anniversary(&self, date: &EthiopianDate, policy: Pagume6Policy) -> bool {
+        if !(self.month == 13 && self.day == 6) {
+            return date.month == self.month && date.day == self.day;
+        }
+        match (date.month, date.day) {
+            (13, 6) => true,
+            (13, 5) => policy == Pagume6Policy::Pagume5 && !Self::is_leap_year(date.year),
+            (1, 1) => policy == Pagume6Policy::Meskerem1 && date.year > 1 && !Self::is_leap_year(date.year - 1),
+            _ => false,
+        }
+    }
+
+    /// Completed Ethiopian years from this date (e.g. a birth date) to `on`.
+    ///
+    /// A year completes on the anniversary; for Pagumē 6 in common years that is
+    /// Pagumē 5 under [`Pagume6Policy::Pagume5`] and Meskerem 1 otherwise.
+    /// Returns `None` when `on` is before this date.
+    pub fn age_on(&self, on: &EthiopianDate, policy: Pagume6Policy) -> Option<usize> {
+        if on < self {
+            return None;
+        }
+        let years = on.year - self.year;
+        let reached = (on.month, on.day) >= (self.month, self.day)
+            || (policy == Pagume6Policy::Pagume5 && self.is_anniversary(on, policy));
+        if reached {
+            Some(years)
+        } else {
+            Some(years - 1)
+        }
+    }
+
+    /// The first date on or after `from` that falls on `month`/`day`, rolling over to the
+    /// next year once this year's has passed.
+    ///
+    /// Pagumē 6 in common years follows `policy`, so under [`Pagume6Policy::LeapYearsOnly`]
+    /// it may be up to four years away. Returns `None` for a month/day that never exists.
+    pub fn next_occurrence(month: usize, day: usize, from: &EthiopianDate, policy: Pagume6Policy) -> Option<Self> {
+        // 2015 is a leap year, so Pagumē 6 passes this check.
+        if !Self::new(2015, month, day).is_valid() {
+            return None;
+        }
+        // Starts a year early: under the Meskerem 1 policy, last year's Pagumē 6 may fall on `from`.
+        (from.year.saturating_sub(1).max(1)..=from.year + 4)
+            .filter_map(|year| {
+                if (month, day) != (13, 6) || Self::is_leap_year(year) {
+                    return Some(Self::new(year, month, day));
+                }
+                match policy {
+                    Pagume6Policy::Pagume5 => Some(Self::new(year, 13, 5)),
+                    Pagume6Policy::Meskerem1 => Some(Self::new(year + 1, 1, 1)),
+                    Pagume6Policy::LeapYearsOnly => None,
+                }
+            })
+            .find(|date| date >= from)
+    }
+
+    /// 1-based day within the Ethiopian year (Meskerem 1 is day 1, Pagumē 6 is day 366).
+    pub fn day_of_year(&self) -> usize {
+        (self.month - 1) * 30 + self.day
+    }
+
+    pub fn weekday(&self) -> usize {
+        // JDN 0 was a Monday.
+        (self.to_jdn() + 1).rem_euclid(7) as usize
+    }
+
+    pub fn amharic_weekday(&self) -> &'static str {
+        Language::Amharic.weekday_name(self.weekday())
+    }
+
+    pub fn english_weekday(&self) -> &'static str {
+        Language::English.weekday_name(self.weekday())
+    }
+
+    /// Weekday name in any supported language.
+    pub fn weekday_name(&self, language: Language) -> &'static str {
+        language.weekday_name(self.weekday())
+    }
+
+    /// Converts Arabic numerals to Geez numerals.
+    ///
+    /// Digits are read in groups of four: each group is written as two pairs joined by
+    /// ፻ (hundred), and groups are multiplied by one ፼ (ten thousand) per position, so
+    /// 100 is ፻, 10,000 is ፼, 1,000,000 is ፻፼ and 100,000,000 is ፼፼. A 1 is not written
+    /// before ፻, nor before the ፼ of the leading group. Zero has no Geez numeral and returns an empty string.
+    pub fn to_geez_number(num: usize) -> String {
+        const ONES: [&str; 10] = ["", "፩", "፪", "፫", "፬", "፭", "፮", "፯", "፰", "፱"];
+        const TENS: [&str; 10] = ["", "፲", "፳", "፴", "፵", "፶", "፷", "፸", "፹", "፺"];
+
+        let pair = |value: usize| format!("{}{}", TENS[value / 10], ONES[value % 10]);
+
+        let mut groups = Vec::new();
+        let mut rest = num;
+        while rest > 0 {
+            groups.push(rest % 10_000);
+            rest /= 10_000;
+        }
+
+        let mut output = String::new();
+        for (position, group) in groups.iter().enumerate().rev() {
+            if *group == 0 {
+                continue;
+            }
+
+            let (hundreds, units) = (group / 100, group % 100);
+            if hundreds > 0 {
+                if hundreds > 1 {
+                    output.push_str(&pair(hundreds));
+                }
+                output.push('፻');
+            }
+            if units > 1 || (units == 1 && (position == 0 || hundreds > 0 || !output.is_empty())) {
+                output.push_str(&pair(units));
+            }
+            output.push_str(&"፼".repeat(position));
+        }
+
+        output
+    }
+
+    /// Parses a written date with month names in Amharic or English and either numeral system.
+    ///
+    /// See `date_input` for the accepted forms; unlike `FromStr` this is meant for human input.
+    pub fn parse(input: &str) -> Result<Self, date_input::DateParseError> {
+        date_input::parse_date(input)
+    }
+
+    /// Reads a Geez numeral back into a number; the inverse of `to_geez_number`.
+    ///
+    /// An explicit leading ፩ before ፻ or ፼ is accepted as well.
+    pub fn from_geez_number(text: &str) -> Result<usize, GeezParseError> {
+        let mut total: usize = 0;
+        let mut block: usize = 0;
+        let mut tens: Option<usize> = None;
+        let mut ones: Option<usize> = None;
+        // Value of the group closed by the current ፼ run, scaled again by each further ፼.
+        let mut group_value: usize = 0;
+        let mut run_length = 0;
+        let mut previous_run_length: Option<usize> = None;
+
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(GeezParseError::Empty);
+        }
+
+        for c in text.chars() {
+            if c != '፼' && run_length > 0 {
+                previous_run_length = Some(run_length);
+                run_length = 0;
+            }
+
+            match c {
+                '፩'..='፱' => {
+                    if ones.is_some() {
+                        return Err(GeezParseError::Malformed);
+                    }
+                    ones = Some(c as usize - '፩' as usize + 1);
+                }
+                '፲'..='፺' => {
+                    if tens.is_some() || ones.is_some() {
+                        return Err(GeezParseError::Malformed);
+                    }
+                    tens = Some((c as usize - '፲' as usize + 1) * 10);
+                }
+                '፻' => {
+                    if block > 0 {
+                        return Err(GeezParseError::Malformed);
+                    }
+                    let pair = tens.unwrap_or(0) + ones.unwrap_or(0);
+                    block = pair.max(1) * 100;
+                    tens = None;
+                    ones = None;
+                }
+                '፼' => {
+                    if run_length == 0 {
+                        group_value = (block + tens.unwrap_or(0) + ones.unwrap_or(0)).max(1);
+                        total = total.checked_add(group_value).ok_or(GeezParseError::Overflow)?;
+                        block = 0;
+                        tens = None;
+                        ones = None;
+                    }
+                    run_length += 1;
+                    if previous_run_length.is_some_and(|previous| run_length >= previous) {
+                        return Err(GeezParseError::Malformed);
+                    }
+
+                    let scaled = group_value.checked_mul(10_000).ok_or(GeezParseError::Overflow)?;
+                    total = total - group_value + scaled;
+                    group_value = scaled;
+                }
+                other => return Err(GeezParseError::InvalidCharacter(other)),
+            }
+        }
+
+        total
+            .checked_add(block + tens.unwrap_or(0) + ones.unwrap_or(0))
+            .ok_or(GeezParseError::Overflow)
+    }
+
+    /// Formats the date with a `%` token template; see `format` for the tokens.
+    pub fn format(&self, template: &str) -> String {
+        format::render_template(self, template)
+    }
+
+    /// Like `format`, with the era and language of `%Y`, `%LB` and friends taken from `options`.
+    pub fn format_with(&self, template: &str, options: format::FormatOptions) -> String {
+        format::render_template_with(self, template, options)
+    }
+
+    pub fn day_geez(&self) -> String {
+        Self::to_geez_number(self.day)
+    }
+
+    pub fn year_geez(&self) -> String {
+        Self::to_geez_number(self.year)
+    }
+
+    /// The year counted in Amete Alem (ዓመተ ዓለም).
+    pub fn year_amete_alem(&self) -> usize {
+        self.year + AMETE_ALEM_OFFSET
+    }
+
+    /// The year counted in the given era.
+    pub fn year_in_era(&self, era: Era) -> usize {
+        match era {
+            Era::AmeteMihret => self.year,
+            Era::AmeteAlem => self.year_amete_alem(),
+        }
+    }
+}
+
+/// Why a string could not be read as a Geez numeral.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GeezParseError {
+    Empty,
+    /// A character that is not a Geez number sign.
+    InvalidCharacter(char),
+    /// Number signs in an impossible order, e.g. `፩፲` or `፻፻`.
+    Malformed,
+    /// The value does not fit in a `usize`.
+    Overflow,
+}
+
+impl std::fmt::Display for GeezParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GeezParseError::Empty => write!(f, "Empty Geez numeral"),
+            GeezParseError::InvalidCharacter(c) => write!(f, "'{}' is not a Geez numeral", c),
+            GeezParseError::Malformed => write!(f, "Malformed Geez numeral"),
+            GeezParseError::Overflow => write!(f, "Geez numeral is too large"),
+        }
+    }
+}
+
+impl std::error::Error for GeezParseError {}
+
+/// An Ethiopian date as sent by the frontend: `{ year, month, day }`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EthiopianDateInput {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+}
+
+impl EthiopianDateInput {
+    /// Converts to an `EthiopianDate`, rejecting days that do not exist.
+    pub fn to_date(self) -> Result<EthiopianDate, String> {
+        let date = EthiopianDate::new(self.year, self.month, self.day);
+        if date.is_valid() {
+            Ok(date)
+        } else {
+            Err(format!("Invalid Ethiopian date: {}-{}-{}", self.year, self.month, self.day))
+        }
+    }
+}
+
+/// A date in either calendar as sent by the frontend, e.g.
+/// `{ calendar: "gregorian", year: 2025, month: 1, day: 7 }`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(tag = "calendar", rename_all = "snake_case")]
+pub enum CalendarDateInput {
+    Ethiopian { year: usize, month: usize, day: usize },
+    Gregorian { year: i32, month: u32, day: u32 },
+}
+
+impl CalendarDateInput {
+    /// Converts to an `EthiopianDate`, rejecting days that do not exist in their calendar.
+    pub fn to_date(self) -> Result<EthiopianDate, String> {
+        match self {
+            Self::Ethiopian { year, month, day } => EthiopianDateInput { year, month, day }.to_date(),
+            Self::Gregorian { year, month, day } => EthiopianDate::from_gregorian(year, month, day)
+                .ok_or_else(|| format!("Invalid Gregorian date: {}-{}-{}", year, month, day)),
+        }
+    }
+}
+
+// Equality, ordering and hashing go by (year, month, day); `day_geez` is derived from `day`.
+impl PartialEq for EthiopianDate {
+    fn eq(&self, other: &Self) -> bool {
+        self.sort_key() == other.sort_key()
+    }
+}
+
+impl Eq for EthiopianDate {}
+
+impl PartialOrd for EthiopianDate {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for EthiopianDate {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key())
+    }
+}
+
+impl std::hash::Hash for EthiopianDate {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.sort_key().hash(state);
+    }
+}
+
+/// Canonical `YYYY-MM-DD` form, e.g. `2017-05-21`.
+impl std::fmt::Display for EthiopianDate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Parses the canonical `YYYY-MM-DD` form written by `Display`.
+impl std::str::FromStr for EthiopianDate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split('-').collect();
+        let [year, month, day] = parts.as_slice() else {
+            return Err(format!("Expected YYYY-MM-DD, got {}", s));
+        };
+        let parse = |part: &str| part.parse::<usize>().map_err(|e| format!("Invalid date {}: {}", s, e));
+
+        EthiopianDateInput {
+            year: parse(year)?,
+            month: parse(month)?,
+            day: parse(day)?,
+        }
+        .to_date()
+    }
+}
+
+/// Inclusive range of consecutive Ethiopian dates, walked one day at a time.
+///
+/// Stepping uses `succ()`, so no Gregorian conversion happens along the way.
+#[derive(Debug, Clone)]
+pub struct DateRange {
+    next: Option<EthiopianDate>,
+    end: EthiopianDate,
+}
+
+impl DateRange {
+    /// An empty range if `to` is before `from`.
+    pub fn new(from: EthiopianDate, to: EthiopianDate) -> Self {
+        let next = (from <= to).then_some(from);
+        Self { next, end: to }
+    }
+}
+
+impl Iterator for DateRange {
+    type Item = EthiopianDate;
+
+    fn next(&mut self) -> Option<EthiopianDate> {
+        let current = self.next.take()?;
+        if current < self.end {
+            self.next = Some(current.succ());
+        }
+        Some(current)
+    }
+}
+
+/// Represents a complete month view for the Ethiopian calendar.
+#[derive(Serialize, Deserialize)]
+pub struct CalendarMonth {
+    pub year: usize,
+    pub year_geez: String,
+    pub year_amete_alem: usize,
+    pub year_amete_alem_geez: String,
+    pub month: usize,
+    pub month_name_amharic: String,
+    pub month_name_english: String,
+    /// Month name in `language`.
+    pub month_name: String,
+    /// Column headers in `language`, starting from `week_start`.
+    pub weekday_names: Vec<String>,
+    pub language: Language,
+    pub days: Vec<CalendarDay>,
+    /// Number of grid cells before day 1, counted from `week_start`.
+    pub first_day_weekday: usize,
+    /// Weekday the grid's first column represents (0 = Sunday).
+    pub week_start: usize,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CalendarDay {
+    pub year: usize,
+    pub month: usize,
+    pub day: usize,
+    pub day_geez: String,
+    pub is_today: bool,
+    /// Grid column of the day, relative to the week start.
+    pub weekday: usize,
+    pub weekday_name_amharic: String,
+    pub weekday_name_english: String,
+    /// Weekday name in the calendar's display language.
+    pub weekday_name: String,
+    pub holidays: Vec<holidays::Holiday>,
+    /// Whether the user has an event on this day.
+    pub has_event: bool,
+    pub celebrations: Vec<birthdays::Celebration>,
+    pub gregorian_year: i32,
+    pub gregorian_month: u32,
+    pub gregorian_day: u32,
+    /// Gregorian date as `YYYY-MM-DD`.
+    pub gregorian_iso: String,
+    pub moon_phase: Option<astronomy::MoonPhase>,
+}
+
+impl CalendarMonth {
+    pub fn new(year: usize, month: usize) -> Self {
+        Self::with_week_start(year, month, 0)
+    }
+
+    /// Builds the month grid with weeks starting on `week_start` (0 = Sunday, 1 = Monday, ...).
+    pub fn with_week_start(year: usize, month: usize, week_start: usize) -> Self {
+        Self::localized(year, month, week_start, Language::default())
+    }
+
+    /// Builds the month grid with names in `language`.
+    pub fn localized(year: usize, month: usize, week_start: usize, language: Language) -> Self {
+        let week_start = week_start % 7;
+        let first_day = EthiopianDate { year, month, day: 1, day_geez: EthiopianDate::to_geez_number(1) };
+        let first_day_weekday = (first_day.weekday() + 7 - week_start) % 7;
+        let today = EthiopianDate::today();
+        let month_holidays: Vec<holidays::Holiday> =
+            holidays::holidays_for_month(year, month).into_iter().filter(|h| h.is_shown()).collect();
+
+        // Days of a month are consecutive, so the first day's Gregorian date and weekday give all the others.
+        let first_gregorian = first_day.to_gregorian();
+        let days = EthiopianDate::iter_month(year, month)
+            .enumerate()
+            .map(|(offset, date)| {
+                let is_today = date == today;
+                let day_holidays = month_holidays.iter().filter(|h| h.date.day == date.day).cloned().collect();
+                let gregorian = first_gregorian.map(|g| g + chrono::Duration::days(offset as i64));
+                let weekday = (first_day.weekday() + offset) % 7;
+                CalendarDay::with_gregorian(&date, gregorian, weekday, is_today, day_holidays, week_start, language)
+            })
+            .collect();
+
+        Self {
+            year,
+            year_geez: EthiopianDate::to_geez_number(year),
+            year_amete_alem: first_day.year_amete_alem(),
+            year_amete_alem_geez: EthiopianDate::to_geez_number(first_day.year_amete_alem()),
+            month,
+            month_name_amharic: first_day.amharic_month().to_string(),
+            month_name_english: first_day.english_month().to_string(),
+            month_name: first_day.month_name(language).to_string(),
+            weekday_names: (0..7).map(|i| language.weekday_name((i + week_start) % 7).to_string()).collect(),
+            language,
+            days,
+            first_day_weekday,
+            week_start,
+        }
+    }
+}
+
+impl CalendarDay {
+    pub fn new(
+        date: &EthiopianDate,
+        is_today: bool,
+        holidays: Vec<holidays::Holiday>,
+        week_start: usize,
+        language: Language,
+    ) -> Self {
+        Self::with_gregorian(date, date.to_gregorian(), date.weekday(), is_today, holidays, week_start, language)
+    }
+
+    /// Builds a day whose Gregorian date and weekday (0 = Sunday) the caller already knows.
+    fn with_gregorian(
+        date: &EthiopianDate,
+        gregorian: Option<NaiveDate>,
+        weekday: usize,
+        is_today: bool,
+        holidays: Vec<holidays::Holiday>,
+        week_start: usize,
+        language: Language,
+    ) -> Self {
+        Self {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            day_geez: date.day_geez(),
+            is_today,
+            weekday: (weekday + 7 - week_start % 7) % 7,
+            weekday_name_amharic: Language::Amharic.weekday_name(weekday).to_string(),
+            weekday_name_english: Language::English.weekday_name(weekday).to_string(),
+            weekday_name: language.weekday_name(weekday).to_string(),
+            holidays,
+            has_event: events::has_event(date),
+            celebrations: birthdays::celebrations_on(date),
+            gregorian_year: gregorian.map(|g| g.year()).unwrap_or_default(),
+            gregorian_month: gregorian.map(|g| g.month()).unwrap_or_default(),
+            gregorian_day: gregorian.map(|g| g.day()).unwrap_or_default(),
+            gregorian_iso: gregorian.map(|g| g.format("%Y-%m-%d").to_string()).unwrap_or_default(),
+            moon_phase: gregorian.map(astronomy::moon_phase),
+        }
+    }
+}
+
+/// The seven days of the week containing a date, for the compact week strip.
+#[derive(Serialize, Deserialize)]
+pub struct CalendarWeek {
+    pub week_of_year: usize,
+    /// Weekday the week starts on (0 = Sunday).
+    pub week_start: usize,
+    pub days: Vec<CalendarDay>,
+}
+
+impl CalendarWeek {
+    pub fn new(date: &EthiopianDate, week_start: usize, language: Language) -> Self {
+        let week_start = week_start % 7;
+        let today = EthiopianDate::today();
+        let days_since_start = (date.weekday() + 7 - week_start) % 7;
+
+        let mut year_holidays: Vec<holidays::Holiday> = Vec::new();
+        let mut days = Vec::new();
+        for offset in 0..7 {
+            let Some(day) = date.add_days(offset - days_since_start as i64) else {
+                continue;
+            };
+            if !year_holidays.iter().any(|h| h.date.year == day.year) {
+                year_holidays.extend(holidays::holidays_for_year(day.year));
+            }
+
+            let is_today = day == today;
+            let day_holidays = year_holidays
+                .iter()
+                .filter(|h| h.date == day && h.is_shown())
+                .cloned()
+                .collect();
+            days.push(CalendarDay::new(&day, is_today, day_holidays, week_start, language));
+        }
+
+        Self {
+            week_of_year: date.week_of_year(week_start),
+            week_start,
+            days,
+        }
+    }
+}
+
+/// Leap-year facts about an Ethiopian year, e.g. for "Pagumē will have 6 days in 2019".
+#[derive(Serialize, Deserialize)]
+pub struct PagumeInfo {
+    pub year: usize,
+    pub is_leap_year: bool,
+    pub pagume_length: usize,
+    /// The first Pagumē 6 from the start of `year`.
+    pub next_pagume_6: EthiopianDate,
+}
+
+impl PagumeInfo {
+    pub fn for_year(year: usize) -> Self {
+        Self {
+            year,
+            is_leap_year: EthiopianDate::is_leap_year(year),
+            pagume_length: EthiopianDate::pagume_length(year),
+            next_pagume_6: EthiopianDate::next_pagume_6(&EthiopianDate::new(year, 1, 1)),
+        }
+    }
+}
+
+/// At-a-glance summary of today: the date plus seasonal context.
+#[derive(Serialize, Deserialize)]
+pub struct TodaySummary {
+    pub date: EthiopianDate,
+    pub season: seasons::SeasonProgress,
+    pub daylight: Option<astronomy::Daylight>,
+}
+
+impl TodaySummary {
+    pub fn for_date(date: EthiopianDate) -> Self {
+        let daylight = date
+            .to_gregorian()
+            .map(|gregorian| astronomy::daylight(gregorian, astronomy::ADDIS_ABABA_LATITUDE));
+
+        Self {
+            season: seasons::season_progress(&date),
+            date,
+            daylight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_geez_number_table() {
+        let table = [
+            (1, "፩"),
+            (10, "፲"),
+            (70, "፸"),
+            (80, "፹"),
+            (99, "፺፱"),
+            (100, "፻"),
+            (101, "፻፩"),
+            (1_000, "፲፻"),
+            (10_000, "፼"),
+            (10_001, "፼፩"),
+            (100_000, "፲፼"),
+            (1_000_000, "፻፼"),
+            (100_010_000, "፼፼፩፼"),
+        ];
+        for (number, geez) in table {
+            assert_eq!(EthiopianDate::to_geez_number(number), geez, "{}", number);
+        }
+    }
+
+    #[test]
+    fn to_geez_number_of_zero_is_empty() {
+        assert_eq!(EthiopianDate::to_geez_number(0), "");
+    }
+
+    #[test]
+    fn from_geez_number_round_trips() {
+        for number in 1..=200_000 {
+            assert_eq!(EthiopianDate::from_geez_number(&EthiopianDate::to_geez_number(number)), Ok(number));
+        }
+        assert_eq!(EthiopianDate::from_geez_number("፼፼፩፼"), Ok(100_010_000));
+        assert_eq!(EthiopianDate::from_geez_number("፩፻"), Ok(100));
+    }
+
+    #[test]
+    fn from_geez_number_rejects_invalid_input() {
+        assert_eq!(EthiopianDate::from_geez_number(""), Err(GeezParseError::Empty));
+        assert_eq!(EthiopianDate::from_geez_number("  "), Err(GeezParseError::Empty));
+        assert_eq!(EthiopianDate::from_geez_number("abc"), Err(GeezParseError::InvalidCharacter('a')));
+        assert_eq!(EthiopianDate::from_geez_number("፲x"), Err(GeezParseError::InvalidCharacter('x')));
+        assert_eq!(EthiopianDate::from_geez_number("፩፲"), Err(GeezParseError::Malformed));
+        assert_eq!(EthiopianDate::from_geez_number("፩፩"), Err(GeezParseError::Malformed));
+        assert_eq!(EthiopianDate::from_geez_number("፲፲"), Err(GeezParseError::Malformed));
+        assert_eq!(EthiopianDate::from_geez_number("፻፻"), Err(GeezParseError::Malformed));
+        assert_eq!(EthiopianDate::from_geez_number("፼፩፼፼"), Err(GeezParseError::Malformed));
+    }
+
+    /// Anniversaries of a Pagumē 6, 2015 birth in 2016 to 2019: three common years and a leap year.
+    fn pagume_6_anniversaries(policy: Pagume6Policy) -> Vec<EthiopianDate> {
+        match policy {
+            Pagume6Policy::Pagume5 => vec![
+                EthiopianDate::new(2016, 13, 5),
+                EthiopianDate::new(2017, 13, 5),
+                EthiopianDate::new(2018, 13, 5),
+                EthiopianDate::new(2019, 13, 6),
+            ],
+            Pagume6Policy::Meskerem1 => vec![
+                EthiopianDate::new(2017, 1, 1),
+                EthiopianDate::new(2018, 1, 1),
+                EthiopianDate::new(2019, 1, 1),
+                EthiopianDate::new(2019, 13, 6),
+            ],
+            Pagume6Policy::LeapYearsOnly => vec![EthiopianDate::new(2019, 13, 6)],
+        }
+    }
+
+    const POLICIES: [Pagume6Policy; 3] = [Pagume6Policy::Pagume5, Pagume6Policy::Meskerem1, Pagume6Policy::LeapYearsOnly];
+
+    #[test]
+    fn pagume_6_cycle() {
+        assert!(EthiopianDate::is_leap_year(2015));
+        assert!(!EthiopianDate::is_leap_year(2016));
+        assert!(!EthiopianDate::is_leap_year(2017));
+        assert!(!EthiopianDate::is_leap_year(2018));
+        assert!(EthiopianDate::is_leap_year(2019));
+    }
+
+    #[test]
+    fn is_anniversary_across_four_years() {
+        let birth = EthiopianDate::new(2015, 13, 6);
+        let first = EthiopianDate::new(2016, 1, 1);
+        for policy in POLICIES {
+            let anniversaries: Vec<EthiopianDate> = (0..4 * 366)
+                .filter_map(|offset| first.add_days(offset))
+                .filter(|date| date.year <= 2019 && birth.is_anniversary(date, policy))
+                .collect();
+            assert_eq!(anniversaries, pagume_6_anniversaries(policy), "{:?}", policy);
+        }
+    }
+
+    #[test]
+    fn next_occurrence_across_four_years() {
+        for policy in POLICIES {
+            let mut from = EthiopianDate::new(2016, 1, 1);
+            let mut occurrences = Vec::new();
+            while let Some(next) = EthiopianDate::next_occurrence(13, 6, &from, policy).filter(|next| next.year <= 2019) {
+                from = next.add_days(1).unwrap();
+                occurrences.push(next);
+            }
+            assert_eq!(occurrences, pagume_6_anniversaries(policy), "{:?}", policy);
+        }
+    }
+}
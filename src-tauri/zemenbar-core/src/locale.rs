@@ -0,0 +1,134 @@
+//! Display languages and their month and weekday names.
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    Amharic,
+    English,
+    Tigrinya,
+    /// Afaan Oromoo, written in Qubee (Latin script).
+    Oromo,
+}
+
+const AMHARIC_MONTHS: [&str; 13] = [
+    "መስከረም", "ጥቅምት", "ኅዳር", "ታኅሣሥ", "ጥር", "የካቲት", "መጋቢት", "ሚያዝያ", "ግንቦት", "ሰኔ", "ሐምሌ", "ነሐሴ", "ጳጉሜ",
+];
+const ENGLISH_MONTHS: [&str; 13] = [
+    "Meskerem", "Tikimt", "Hidar", "Tahsas", "Tir", "Yekatit", "Megabit", "Miazia", "Ginbot", "Sene", "Hamle",
+    "Nehase", "Pagume",
+];
+const TIGRINYA_MONTHS: [&str; 13] = [
+    "መስከረም", "ጥቅምቲ", "ሕዳር", "ታሕሳስ", "ጥሪ", "ለካቲት", "መጋቢት", "ሚያዝያ", "ግንቦት", "ሰነ", "ሓምለ", "ነሓሰ", "ጳጉሜን",
+];
+const OROMO_MONTHS: [&str; 13] = [
+    "Fulbaana", "Onkololeessa", "Sadaasa", "Muddee", "Amajjii", "Guraandhala", "Bitootessa", "Eebila", "Caamsaa",
+    "Waxabajjii", "Adoolessa", "Hagayya", "Qaammee",
+];
+
+/// Weekday names starting from Sunday.
+const AMHARIC_WEEKDAYS: [&str; 7] = ["እሁድ", "ሰኞ", "ማክሰኞ", "ረቡዕ", "ሐሙስ", "ዓርብ", "ቅዳሜ"];
+const ENGLISH_WEEKDAYS: [&str; 7] = ["Sunday", "Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday"];
+const TIGRINYA_WEEKDAYS: [&str; 7] = ["ሰንበት", "ሰኑይ", "ሰሉስ", "ረቡዕ", "ሓሙስ", "ዓርቢ", "ቀዳም"];
+const OROMO_WEEKDAYS: [&str; 7] = ["Dilbata", "Wiixata", "Kibxata", "Roobii", "Kamisa", "Jimaata", "Sanbata"];
+
+impl Language {
+    pub const ALL: [Language; 4] = [Language::Amharic, Language::English, Language::Tigrinya, Language::Oromo];
+
+    /// The language's name for itself, for language pickers.
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::Amharic => "አማርኛ",
+            Language::English => "English",
+            Language::Tigrinya => "ትግርኛ",
+            Language::Oromo => "Afaan Oromoo",
+        }
+    }
+
+    /// Whether the language is written in Ge'ez script, and so uses ቀን/ዓ.ም. style suffixes.
+    pub fn uses_ethiopic_script(&self) -> bool {
+        matches!(self, Language::Amharic | Language::Tigrinya)
+    }
+
+    fn months(&self) -> &'static [&'static str; 13] {
+        match self {
+            Language::Amharic => &AMHARIC_MONTHS,
+            Language::English => &ENGLISH_MONTHS,
+            Language::Tigrinya => &TIGRINYA_MONTHS,
+            Language::Oromo => &OROMO_MONTHS,
+        }
+    }
+
+    fn weekdays(&self) -> &'static [&'static str; 7] {
+        match self {
+            Language::Amharic => &AMHARIC_WEEKDAYS,
+            Language::English => &ENGLISH_WEEKDAYS,
+            Language::Tigrinya => &TIGRINYA_WEEKDAYS,
+            Language::Oromo => &OROMO_WEEKDAYS,
+        }
+    }
+
+    /// Name of month 1-13, or `"Unknown"` out of range.
+    pub fn month_name(&self, month: usize) -> &'static str {
+        month
+            .checked_sub(1)
+            .and_then(|index| self.months().get(index))
+            .copied()
+            .unwrap_or("Unknown")
+    }
+
+    /// Name of a weekday (0 = Sunday), or `"Unknown"` out of range.
+    pub fn weekday_name(&self, weekday: usize) -> &'static str {
+        self.weekdays().get(weekday).copied().unwrap_or("Unknown")
+    }
+}
+
+/// Display preferences suggested by a system locale, used for first-run settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalePreferences {
+    pub language: Language,
+    pub use_geez_numbers: bool,
+    pub use_numeric_format: bool,
+}
+
+impl LocalePreferences {
+    /// Reads a BCP 47 or POSIX locale tag such as `am-ET`, `ti_ER` or `en_US.UTF-8`.
+    ///
+    /// Ge'ez-script locales of Ethiopia and Eritrea get Geez numerals. Locales in
+    /// none of the supported languages get English with numeric dates, which read
+    /// the same in any language.
+    pub fn from_locale_tag(tag: &str) -> Self {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        let mut parts = tag.split(['-', '_']);
+        let language_code = parts.next().unwrap_or_default().to_lowercase();
+        let in_ethiopia_or_eritrea = parts.any(|part| part.eq_ignore_ascii_case("ET") || part.eq_ignore_ascii_case("ER"));
+
+        let language = match language_code.as_str() {
+            "am" => Some(Language::Amharic),
+            "ti" => Some(Language::Tigrinya),
+            "om" => Some(Language::Oromo),
+            "en" => Some(Language::English),
+            _ => None,
+        };
+
+        match language {
+            Some(language) => Self {
+                language,
+                use_geez_numbers: language.uses_ethiopic_script() && in_ethiopia_or_eritrea,
+                use_numeric_format: false,
+            },
+            None => Self {
+                language: Language::English,
+                use_geez_numbers: false,
+                use_numeric_format: true,
+            },
+        }
+    }
+
+    /// Preferences for the operating system's current locale, if it can be read.
+    pub fn from_system() -> Option<Self> {
+        sys_locale::get_locale().map(|tag| Self::from_locale_tag(&tag))
+    }
+}
@@ -0,0 +1,76 @@
+//! Month name lookup used by the quick-jump field in the calendar header.
+
+use crate::EthiopianDate;
+
+/// Accepted spellings for each month, indexed by month number - 1.
+///
+/// Amharic spellings include the common homophone variants (ኅ/ህ/ሕ, ሐ/ሀ, ሣ/ሳ), followed
+/// by the Tigrinya names where they differ and the Afaan Oromo names.
+const MONTH_VARIANTS: [&[&str]; 13] = [
+    &["መስከረም", "meskerem", "meskeram", "maskaram", "meskerim", "fulbaana", "fuulbana"],
+    &["ጥቅምት", "tikimt", "tikemt", "tiqimt", "teqemt", "tekemt", "tikimit", "ጥቅምቲ", "tiqimti", "onkololeessa", "onkoloolessa"],
+    &["ኅዳር", "ህዳር", "ሕዳር", "hidar", "hedar", "hidaar", "khidar", "sadaasa"],
+    &["ታኅሣሥ", "ታህሳስ", "ታሕሳስ", "ታኅሳስ", "tahsas", "tahesas", "tahisas", "takhsas", "tahissas", "muddee"],
+    &["ጥር", "tir", "ter", "tirr", "ጥሪ", "tiri", "amajjii"],
+    &["የካቲት", "yekatit", "yakatit", "yekkatit", "ለካቲት", "lekatit", "guraandhala"],
+    &["መጋቢት", "megabit", "magabit", "megabbit", "bitootessa"],
+    &["ሚያዝያ", "ሚያዚያ", "miazia", "miyazia", "miyaziya", "miyazya", "miazya", "eebila", "ebla"],
+    &["ግንቦት", "ginbot", "genbot", "guenbot", "caamsaa"],
+    &["ሰኔ", "sene", "senie", "sane", "ሰነ", "waxabajjii"],
+    &["ሐምሌ", "ሀምሌ", "hamle", "hamlie", "hamile", "ሓምለ", "adoolessa"],
+    &["ነሐሴ", "ነሀሴ", "nehase", "nehasse", "nahase", "nehasie", "ነሓሰ", "hagayya"],
+    &["ጳጉሜ", "ጳጉሜን", "pagume", "pagumen", "paguemen", "puagume", "pagumie", "qaammee"],
+];
+
+/// Shortest prefix accepted for a partial month name.
+const MIN_PREFIX_CHARS: usize = 2;
+
+fn normalize(input: &str) -> String {
+    input
+        .trim()
+        .trim_end_matches(['.', '፡', '።'])
+        .to_lowercase()
+        .replace(['ē', 'é', 'è'], "e")
+        .replace(['\'', '’', '-', ' '], "")
+}
+
+/// Resolves a month name, abbreviation or number to a month index (1-13).
+///
+/// Accepts Amharic, Tigrinya, Afaan Oromo and English names, common transliterations, unambiguous
+/// prefixes ("mes", "ጥቅ"), Arabic digits and Geez numerals ("፪").
+pub fn resolve_month(input: &str) -> Option<usize> {
+    let query = normalize(input);
+    if query.is_empty() {
+        return None;
+    }
+
+    if let Ok(number) = query.parse::<usize>() {
+        return (1..=13).contains(&number).then_some(number);
+    }
+
+    if let Some(month) = (1..=13).find(|m| EthiopianDate::to_geez_number(*m) == query) {
+        return Some(month);
+    }
+
+    let exact = MONTH_VARIANTS
+        .iter()
+        .position(|variants| variants.iter().any(|v| *v == query));
+    if let Some(index) = exact {
+        return Some(index + 1);
+    }
+
+    if query.chars().count() < MIN_PREFIX_CHARS {
+        return None;
+    }
+
+    let mut matches = MONTH_VARIANTS
+        .iter()
+        .enumerate()
+        .filter(|(_, variants)| variants.iter().any(|v| v.starts_with(&query)))
+        .map(|(index, _)| index + 1);
+
+    match (matches.next(), matches.next()) {
+        (Some(month), None) => Some(month),
+        _ => None,
+    }
+}
@@ -0,0 +1,312 @@
+//! Optional data packs loaded at runtime from the `packs/` directory.
+//!
+//! Each pack lives in its own folder with a `pack.json` manifest next to its data file:
+//!
+//! ```text
+//! packs/
+//!   oromia-holidays/
+//!     pack.json
+//!     holidays.json
+//! ```
+//!
+//! `holidays` packs add holidays on fixed Ethiopian dates, `synaxarium` packs list
+//! Synaxarium days in the format of the bundled dataset (see `synaxarium`), and
+//! `localization` packs replace UI labels and holiday names of one display language
+//! (see `strings`).
+//!
+//! A pack is rejected when its `format_version` is not supported, when it needs a newer
+//! app than the running one, when the SHA-256 of its data file does not match the
+//! manifest, or when its data file is not signed by one of `TRUSTED_PACK_KEYS`. The
+//! `signature` is the hex Ed25519 signature of the data file, made for example with
+//! `openssl pkeyutl -sign -rawin -inkey key.pem -in holidays.json | xxd -p -c 64`.
+
+use crate::holidays::HolidayCategory;
+use crate::locale::Language;
+use crate::synaxarium::SynaxariumEntry;
+use ed25519_dalek::{Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Manifest format understood by this build.
+pub const SUPPORTED_FORMAT_VERSION: u32 = 1;
+
+const MANIFEST_FILE: &str = "pack.json";
+
+/// Ed25519 public keys whose signatures make a pack trusted.
+pub const TRUSTED_PACK_KEYS: [[u8; 32]; 1] = [[
+    0xac, 0x4e, 0x0e, 0x02, 0xe3, 0xf6, 0x61, 0x3a, 0x6b, 0xa7, 0x1c, 0x01, 0xe4, 0x93, 0x0f, 0x3d, 0x73, 0x29, 0x33,
+    0x5f, 0xaa, 0xf9, 0xd5, 0xf5, 0xa8, 0x99, 0x22, 0x0f, 0xeb, 0xda, 0x90, 0x11,
+]];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PackKind {
+    Holidays,
+    Synaxarium,
+    Localization,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackManifest {
+    pub id: String,
+    pub name: String,
+    pub version: String,
+    pub format_version: u32,
+    /// Oldest ZemenBar version able to use the pack, e.g. `"0.6.0"`.
+    #[serde(default)]
+    pub min_app_version: Option<String>,
+    pub kind: PackKind,
+    /// Data file name, relative to the pack folder.
+    pub data: String,
+    /// Lowercase hex SHA-256 of the data file.
+    pub sha256: String,
+    /// Hex Ed25519 signature of the data file by one of `TRUSTED_PACK_KEYS`.
+    pub signature: String,
+}
+
+/// A holiday contributed by a holidays pack, kept on a fixed Ethiopian date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackHoliday {
+    pub id: String,
+    pub name_amharic: String,
+    pub name_english: String,
+    pub month: usize,
+    pub day: usize,
+    #[serde(default)]
+    pub is_public: bool,
+    #[serde(default)]
+    pub category: HolidayCategory,
+}
+
+/// Labels and holiday names of a localization pack, replacing the built-in ones of `language`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackLocalization {
+    pub language: Language,
+    /// Label text by label key, e.g. `"today"`.
+    #[serde(default)]
+    pub labels: BTreeMap<String, String>,
+    /// Holiday name by holiday id, e.g. `"meskel"`.
+    #[serde(default)]
+    pub holidays: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadedPack {
+    pub manifest: PackManifest,
+    pub path: PathBuf,
+    pub data: serde_json::Value,
+}
+
+/// Pack status reported to the frontend, including packs that failed to load.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackInfo {
+    pub path: String,
+    pub id: Option<String>,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub kind: Option<PackKind>,
+    pub error: Option<String>,
+}
+
+static LOADED_PACKS: Mutex<Vec<LoadedPack>> = Mutex::new(Vec::new());
+static PACK_STATUS: Mutex<Vec<PackInfo>> = Mutex::new(Vec::new());
+
+fn parse_version(version: &str) -> Vec<u32> {
+    version
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| hex.get(i..i + 2).and_then(|pair| u8::from_str_radix(pair, 16).ok()))
+        .collect()
+}
+
+/// Checks that `signature_hex` is a signature of `bytes` by one of `TRUSTED_PACK_KEYS`.
+fn verify_signature(bytes: &[u8], signature_hex: &str) -> Result<(), String> {
+    let signature = decode_hex(signature_hex.trim())
+        .and_then(|signature| <[u8; 64]>::try_from(signature).ok())
+        .map(|signature| Signature::from_bytes(&signature))
+        .ok_or_else(|| "Pack signature is not a hex Ed25519 signature".to_string())?;
+    let trusted = TRUSTED_PACK_KEYS
+        .iter()
+        .filter_map(|key| VerifyingKey::from_bytes(key).ok())
+        .any(|key| key.verify_strict(bytes, &signature).is_ok());
+    if trusted {
+        Ok(())
+    } else {
+        Err("Pack is not signed by a trusted key".to_string())
+    }
+}
+
+fn load_pack(dir: &Path) -> Result<LoadedPack, String> {
+    let manifest_content = std::fs::read_to_string(dir.join(MANIFEST_FILE))
+        .map_err(|e| format!("Failed to read pack manifest: {}", e))?;
+    let manifest: PackManifest = serde_json::from_str(&manifest_content)
+        .map_err(|e| format!("Failed to parse pack manifest: {}", e))?;
+
+    if manifest.format_version != SUPPORTED_FORMAT_VERSION {
+        return Err(format!(
+            "Unsupported pack format {} (expected {})",
+            manifest.format_version, SUPPORTED_FORMAT_VERSION
+        ));
+    }
+    if let Some(min_version) = &manifest.min_app_version {
+        if parse_version(env!("CARGO_PKG_VERSION")) < parse_version(min_version) {
+            return Err(format!("Pack requires ZemenBar {} or newer", min_version));
+        }
+    }
+
+    if Path::new(&manifest.data).is_absolute() || manifest.data.contains("..") {
+        return Err("Pack data file must be inside the pack folder".to_string());
+    }
+    let data_path = dir.join(&manifest.data);
+    let bytes = std::fs::read(&data_path).map_err(|e| format!("Failed to read pack data: {}", e))?;
+    if sha256_hex(&bytes) != manifest.sha256.to_lowercase() {
+        return Err("Pack data checksum does not match its manifest".to_string());
+    }
+    verify_signature(&bytes, &manifest.signature)?;
+
+    let data: serde_json::Value = serde_json::from_slice(&bytes).map_err(|e| format!("Failed to parse pack data: {}", e))?;
+    match manifest.kind {
+        PackKind::Holidays => {
+            serde_json::from_value::<Vec<PackHoliday>>(data.clone())
+                .map_err(|e| format!("Invalid holidays pack: {}", e))?;
+        }
+        PackKind::Synaxarium => {
+            serde_json::from_value::<Vec<SynaxariumEntry>>(data.clone())
+                .map_err(|e| format!("Invalid Synaxarium pack: {}", e))?;
+        }
+        PackKind::Localization => {
+            serde_json::from_value::<PackLocalization>(data.clone())
+                .map_err(|e| format!("Invalid localization pack: {}", e))?;
+        }
+    }
+
+    Ok(LoadedPack {
+        manifest,
+        path: dir.to_path_buf(),
+        data,
+    })
+}
+
+/// Loads every pack folder under `packs_dir`, replacing previously loaded packs.
+///
+/// A missing directory simply means no packs are installed.
+pub fn load_packs(packs_dir: &Path) -> Vec<PackInfo> {
+    let mut loaded = Vec::new();
+    let mut status = Vec::new();
+
+    let mut dirs: Vec<PathBuf> = std::fs::read_dir(packs_dir)
+        .map(|entries| entries.flatten().map(|entry| entry.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    dirs.sort();
+
+    for dir in dirs {
+        match load_pack(&dir) {
+            Ok(pack) => {
+                status.push(PackInfo {
+                    path: dir.display().to_string(),
+                    id: Some(pack.manifest.id.clone()),
+                    name: Some(pack.manifest.name.clone()),
+                    version: Some(pack.manifest.version.clone()),
+                    kind: Some(pack.manifest.kind),
+                    error: None,
+                });
+                loaded.push(pack);
+            }
+            Err(e) => {
+                tracing::warn!("Skipping data pack {}: {}", dir.display(), e);
+                status.push(PackInfo {
+                    path: dir.display().to_string(),
+                    id: None,
+                    name: None,
+                    version: None,
+                    kind: None,
+                    error: Some(e),
+                });
+            }
+        }
+    }
+
+    if let Ok(mut packs) = LOADED_PACKS.lock() {
+        *packs = loaded;
+    }
+    if let Ok(mut current) = PACK_STATUS.lock() {
+        *current = status.clone();
+    }
+    status
+}
+
+/// Status of the packs found by the last `load_packs` call.
+pub fn pack_status() -> Vec<PackInfo> {
+    PACK_STATUS.lock().map(|status| status.clone()).unwrap_or_default()
+}
+
+/// Data of every loaded pack of the given kind, in folder order.
+pub fn pack_data(kind: PackKind) -> Vec<serde_json::Value> {
+    LOADED_PACKS
+        .lock()
+        .map(|packs| {
+            packs
+                .iter()
+                .filter(|pack| pack.manifest.kind == kind)
+                .map(|pack| pack.data.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Holidays contributed by all loaded holiday packs.
+pub fn pack_holidays() -> Vec<PackHoliday> {
+    pack_data(PackKind::Holidays)
+        .into_iter()
+        .filter_map(|data| serde_json::from_value::<Vec<PackHoliday>>(data).ok())
+        .flatten()
+        .collect()
+}
+
+/// Localizations from all loaded localization packs, in folder order.
+pub fn pack_localizations() -> Vec<PackLocalization> {
+    pack_data(PackKind::Localization)
+        .into_iter()
+        .filter_map(|data| serde_json::from_value::<PackLocalization>(data).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `[]` signed with the private key of `TRUSTED_PACK_KEYS[0]`.
+    const SIGNED_DATA: &[u8] = b"[]";
+    const SIGNATURE: &str = "a010d08a6d04603a06ac79252043e826314d9f3ee94ce4f16cde1386f3ba1b2c3cd1d62e537184470fe2505757b4356a5a7bd113305f6ec99e043deb4e7d6d06";
+
+    #[test]
+    fn accepts_a_trusted_signature() {
+        assert_eq!(verify_signature(SIGNED_DATA, SIGNATURE), Ok(()));
+        assert_eq!(verify_signature(SIGNED_DATA, &SIGNATURE.to_uppercase()), Ok(()));
+    }
+
+    #[test]
+    fn rejects_edited_data_and_malformed_signatures() {
+        assert!(verify_signature(b"[ ]", SIGNATURE).is_err());
+        assert!(verify_signature(SIGNED_DATA, &SIGNATURE[..126]).is_err());
+        assert!(verify_signature(SIGNED_DATA, "").is_err());
+        assert!(verify_signature(SIGNED_DATA, &SIGNATURE.replace('a', "g")).is_err());
+    }
+}
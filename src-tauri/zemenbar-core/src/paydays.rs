@@ -0,0 +1,195 @@
+//! Paydays and bill dates, stored in `paydays.json` in the app data directory.
+//!
+//! A payday repeats from its date like a recurring event (see `events`), and is then
+//! moved off weekends and public holidays: "every month on the 26th, or the previous
+//! working day" is a monthly payday on the 26th with `DayOffRule::PreviousWorkingDay`.
+//! Like events and birthdays, paydays are kept in memory once loaded and written back
+//! on every change.
+
+use crate::events::Recurrence;
+use crate::{business_days, EthiopianDate, EthiopianDateInput, Pagume6Policy};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Longest reminder lead time accepted, in days.
+pub const MAX_REMIND_DAYS_BEFORE: u32 = 30;
+
+/// Days a payday is looked for around a range, enough for a weekend next to a run of holidays.
+const MOVE_MARGIN_DAYS: i64 = 14;
+
+/// What happens to a payday that falls on a weekend or public holiday.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayOffRule {
+    /// Stays on the day off.
+    Keep,
+    #[default]
+    PreviousWorkingDay,
+    NextWorkingDay,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Payday {
+    pub id: String,
+    pub title: String,
+    /// First scheduled date, which the series repeats from.
+    #[serde(with = "crate::date_serde::compact")]
+    pub date: EthiopianDate,
+    pub recurrence: Recurrence,
+    #[serde(default)]
+    pub day_off_rule: DayOffRule,
+    /// Days ahead to notify; 0 notifies on the day itself.
+    #[serde(default)]
+    pub remind_days_before: u32,
+}
+
+/// Payday fields sent by the frontend when adding or updating a payday.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaydayInput {
+    pub title: String,
+    pub date: EthiopianDateInput,
+    pub recurrence: Recurrence,
+    #[serde(default)]
+    pub day_off_rule: DayOffRule,
+    #[serde(default)]
+    pub remind_days_before: u32,
+}
+
+impl PaydayInput {
+    fn into_payday(self, id: String) -> Result<Payday, String> {
+        let title = self.title.trim().to_string();
+        if title.is_empty() {
+            return Err("Payday title cannot be empty".to_string());
+        }
+        if self.remind_days_before > MAX_REMIND_DAYS_BEFORE {
+            return Err(format!(
+                "Reminders can be at most {} days ahead, got {} days",
+                MAX_REMIND_DAYS_BEFORE, self.remind_days_before
+            ));
+        }
+        Ok(Payday {
+            id,
+            title,
+            date: self.date.to_date()?,
+            recurrence: self.recurrence,
+            day_off_rule: self.day_off_rule,
+            remind_days_before: self.remind_days_before,
+        })
+    }
+}
+
+impl Payday {
+    /// `scheduled` moved off a day off according to the rule.
+    fn moved(&self, scheduled: &EthiopianDate) -> Option<EthiopianDate> {
+        match self.day_off_rule {
+            DayOffRule::Keep => Some(scheduled.clone()),
+            DayOffRule::PreviousWorkingDay => business_days::working_day_on_or_before(scheduled),
+            DayOffRule::NextWorkingDay => business_days::working_day_on_or_after(scheduled),
+        }
+    }
+
+    /// Days from `start` to `end` inclusive on which the payday falls once moved, in date order.
+    pub fn occurrences_between(
+        &self,
+        start: &EthiopianDate,
+        end: &EthiopianDate,
+        policy: Pagume6Policy,
+    ) -> Vec<EthiopianDate> {
+        let (Some(from), Some(to)) = (start.add_days(-MOVE_MARGIN_DAYS), end.add_days(MOVE_MARGIN_DAYS)) else {
+            return Vec::new();
+        };
+        let mut dates: Vec<EthiopianDate> = EthiopianDate::iter_days(from, to)
+            .filter(|date| *date >= self.date && self.recurrence.matches(&self.date, date, policy))
+            .filter_map(|scheduled| self.moved(&scheduled))
+            .filter(|date| date >= start && date <= end)
+            .collect();
+        dates.dedup();
+        dates
+    }
+}
+
+static PAYDAYS: Mutex<Vec<Payday>> = Mutex::new(Vec::new());
+
+fn new_payday_id() -> String {
+    let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    format!("pay-{:x}", nanos)
+}
+
+/// Reads the paydays store into memory. A missing file simply means no paydays yet.
+pub fn load_paydays(path: &Path) -> Result<(), String> {
+    let paydays = if path.exists() {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("Failed to read paydays file: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse paydays: {}", e))?
+    } else {
+        Vec::new()
+    };
+
+    let mut current = PAYDAYS.lock().map_err(|e| format!("Failed to lock paydays: {}", e))?;
+    *current = paydays;
+    Ok(())
+}
+
+/// Applies `change` to the in-memory paydays and persists the result.
+fn modify_paydays<T>(path: &Path, change: impl FnOnce(&mut Vec<Payday>) -> Result<T, String>) -> Result<T, String> {
+    let mut current = PAYDAYS.lock().map_err(|e| format!("Failed to lock paydays: {}", e))?;
+    let mut paydays = current.clone();
+    let result = change(&mut paydays)?;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| format!("Failed to create paydays directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&paydays).map_err(|e| format!("Failed to serialize paydays: {}", e))?;
+    std::fs::write(path, content).map_err(|e| format!("Failed to write paydays file: {}", e))?;
+
+    *current = paydays;
+    Ok(result)
+}
+
+pub fn list_paydays() -> Vec<Payday> {
+    PAYDAYS.lock().map(|paydays| paydays.clone()).unwrap_or_default()
+}
+
+pub fn add_payday(path: &Path, input: PaydayInput) -> Result<Payday, String> {
+    let payday = input.into_payday(new_payday_id())?;
+    modify_paydays(path, |paydays| {
+        paydays.push(payday.clone());
+        Ok(payday)
+    })
+}
+
+pub fn update_payday(path: &Path, id: &str, input: PaydayInput) -> Result<Payday, String> {
+    let payday = input.into_payday(id.to_string())?;
+    modify_paydays(path, |paydays| {
+        let existing = paydays
+            .iter_mut()
+            .find(|existing| existing.id == id)
+            .ok_or_else(|| format!("No payday with id {}", id))?;
+        *existing = payday.clone();
+        Ok(payday)
+    })
+}
+
+pub fn delete_payday(path: &Path, id: &str) -> Result<(), String> {
+    modify_paydays(path, |paydays| {
+        let count = paydays.len();
+        paydays.retain(|payday| payday.id != id);
+        if paydays.len() == count {
+            return Err(format!("No payday with id {}", id));
+        }
+        Ok(())
+    })
+}
+
+/// Paydays falling on `date` once moved off days off, each with `date` set to that day.
+pub fn paydays_on(date: &EthiopianDate) -> Vec<Payday> {
+    let policy = crate::pagume_6_policy();
+    list_paydays()
+        .into_iter()
+        .filter(|payday| !payday.occurrences_between(date, date, policy).is_empty())
+        .map(|payday| Payday {
+            date: date.clone(),
+            ..payday
+        })
+        .collect()
+}
@@ -0,0 +1,246 @@
+//! Free-text date lookup for the popup's quick search box.
+//!
+//! A query is tried, in order, as a relative day ("today", "in 40 days", "2 weeks ago",
+//! "in 10 working days"), a weekday ("next Friday", "last ሰኞ"), a Gregorian `YYYY-MM-DD`
+//! date, a written Ethiopian date ("Meskerem 1 2018", see `date_input`, or "next Meskerem 1")
+//! and a holiday name with an optional year ("Fasika 2017"). The first reading that works
+//! is the answer.
+
+use crate::business_days;
+use crate::holidays::{self, Holiday};
+use crate::locale::Language;
+use crate::{date_input, EthiopianDate};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Which reading of the query produced the date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryKind {
+    Relative,
+    Weekday,
+    Gregorian,
+    Ethiopian,
+    Holiday,
+}
+
+/// A query resolved to a single day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DateQuery {
+    pub kind: QueryKind,
+    pub date: EthiopianDate,
+    /// `YYYY-MM-DD`.
+    pub gregorian_date: String,
+    /// The holiday that was found, for `QueryKind::Holiday`.
+    pub holiday: Option<Holiday>,
+}
+
+/// Days, working days, weeks, Ethiopian months or years from `today`, e.g. "in 3 weeks"
+/// or "40 days ago".
+fn relative(words: &[&str], today: &EthiopianDate) -> Option<EthiopianDate> {
+    // "working days" and "business days" are read as one unit.
+    let mut words = words.to_vec();
+    let two_word_unit = |pair: &[&str]| matches!(pair[0], "working" | "business") && pair[1].starts_with("day");
+    if let Some(index) = words.windows(2).position(two_word_unit) {
+        words.splice(index..index + 2, ["working_days"]);
+    }
+    let (count, unit, sign) = match words.as_slice() {
+        ["today" | "ዛሬ"] => return Some(today.clone()),
+        ["tomorrow" | "ነገ"] => return today.add_days(1),
+        ["yesterday" | "ትናንት" | "ትላንት"] => return today.add_days(-1),
+        ["in", count, unit] | [count, unit, "from", "now"] | [count, unit, "later"] => (count, unit, 1),
+        [count, unit, "ago"] | [count, unit, "before"] => (count, unit, -1),
+        _ => return None,
+    };
+    let count = count.parse::<i64>().ok()? * sign;
+    match unit.trim_end_matches('s') {
+        "day" => today.add_days(count),
+        "working_day" => business_days::add_working_days(today, count),
+        "week" => today.add_days(count * 7),
+        "month" => today.add_months(count),
+        "year" => today.add_years(count),
+        _ => None,
+    }
+}
+
+/// Weekday 0-6 (0 = Sunday) named by `word` in any supported language.
+fn weekday_number(word: &str) -> Option<usize> {
+    (0..7).find(|weekday| {
+        Language::ALL.iter().any(|language| {
+            let name = language.weekday_name(*weekday).to_lowercase();
+            name == word || (word.len() >= 3 && word.is_ascii() && name.starts_with(word))
+        })
+    })
+}
+
+/// "Friday" and "this Friday" are today or the coming one; "next" and "last" skip today.
+fn weekday(words: &[&str], today: &EthiopianDate) -> Option<EthiopianDate> {
+    let (direction, name) = match words {
+        [name] | ["this", name] => ("this", *name),
+        [direction @ ("next" | "last"), name] => (*direction, *name),
+        _ => return None,
+    };
+    let target = weekday_number(name)? as i64;
+    let current = today.weekday() as i64;
+    let offset = match direction {
+        "next" => (target - current - 1).rem_euclid(7) + 1,
+        "last" => -((current - target - 1).rem_euclid(7) + 1),
+        _ => (target - current).rem_euclid(7),
+    };
+    today.add_days(offset)
+}
+
+/// "next Meskerem 1": the first day after `today` on that month and day.
+fn next_date(words: &[&str], today: &EthiopianDate) -> Option<EthiopianDate> {
+    let ["next", rest @ ..] = words else {
+        return None;
+    };
+    let parsed = date_input::normalize_date_input(&rest.join(" "));
+    if parsed.year.is_some() {
+        return None;
+    }
+    EthiopianDate::next_occurrence(parsed.month?, parsed.day?, &today.add_days(1)?, crate::pagume_6_policy())
+}
+
+/// A holiday named by the query, with a trailing Arabic or Geez year. Without a year the
+/// next occurrence from `today` is used.
+fn holiday(query: &str, today: &EthiopianDate) -> Option<Holiday> {
+    let (name, year) = match query.rsplit_once(' ') {
+        Some((name, year)) => match year.parse::<usize>().ok().or_else(|| EthiopianDate::from_geez_number(year).ok()) {
+            Some(year) => (name, Some(year)),
+            None => (query, None),
+        },
+        None => (query, None),
+    };
+    let best = |year: usize| holidays::find_holiday(name, year).into_iter().next().map(|found| found.holiday);
+
+    match year {
+        Some(year) => best(year),
+        None => best(today.year)
+            .filter(|holiday| holiday.date >= *today)
+            .or_else(|| best(today.year + 1)),
+    }
+}
+
+/// Resolves `text` to a day, reading relative phrases from `today`; `None` when no
+/// reading works.
+pub fn query_date(text: &str, today: &EthiopianDate) -> Option<DateQuery> {
+    let query = text.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+    if query.is_empty() {
+        return None;
+    }
+    let words: Vec<&str> = query.split(' ').collect();
+
+    let (kind, date, found) = if let Some(date) = relative(&words, today) {
+        (QueryKind::Relative, date, None)
+    } else if let Some(date) = weekday(&words, today) {
+        (QueryKind::Weekday, date, None)
+    } else if let Some(date) =
+        NaiveDate::parse_from_str(&query, "%Y-%m-%d").ok().and_then(EthiopianDate::from_naive_date)
+    {
+        (QueryKind::Gregorian, date, None)
+    } else if let Ok(date) = date_input::complete_partial_date(&query, today) {
+        (QueryKind::Ethiopian, date, None)
+    } else if let Some(date) = next_date(&words, today) {
+        (QueryKind::Ethiopian, date, None)
+    } else {
+        let holiday = holiday(&query, today)?;
+        (QueryKind::Holiday, holiday.date.clone(), Some(holiday))
+    };
+
+    Some(DateQuery {
+        kind,
+        gregorian_date: date.to_gregorian()?.format("%Y-%m-%d").to_string(),
+        date,
+        holiday: found,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn today() -> EthiopianDate {
+        EthiopianDate::new(2017, 4, 14)
+    }
+
+    fn query(text: &str) -> DateQuery {
+        query_date(text, &today()).unwrap_or_else(|| panic!("{} was not read", text))
+    }
+
+    #[test]
+    fn relative_days() {
+        let steps = [
+            ("today", 0),
+            ("Tomorrow", 1),
+            ("ትናንት", -1),
+            ("in 40 days", 40),
+            ("2 weeks ago", -14),
+            ("3 days from now", 3),
+        ];
+        for (text, days) in steps {
+            let found = query(text);
+            assert_eq!(found.kind, QueryKind::Relative, "{}", text);
+            assert_eq!(Some(found.date), today().add_days(days), "{}", text);
+        }
+        assert_eq!(query("in 2 months").date, EthiopianDate::new(2017, 6, 14));
+        assert_eq!(query("1 year ago").date, EthiopianDate::new(2016, 4, 14));
+    }
+
+    #[test]
+    fn weekdays() {
+        let weekday = today().weekday();
+        let this = query(&format!("this {}", Language::English.weekday_name(weekday)));
+        assert_eq!((this.kind, this.date), (QueryKind::Weekday, today()));
+
+        let next = query(&format!("next {}", Language::English.weekday_name(weekday)));
+        assert_eq!(Some(next.date), today().add_days(7));
+        let last = query(&format!("last {}", Language::Amharic.weekday_name(weekday)));
+        assert_eq!(Some(last.date), today().add_days(-7));
+
+        let tomorrow = (weekday + 1) % 7;
+        assert_eq!(Some(query(Language::English.weekday_name(tomorrow)).date), today().add_days(1));
+    }
+
+    #[test]
+    fn gregorian_dates() {
+        let found = query("2024-12-23");
+        assert_eq!((found.kind, found.date), (QueryKind::Gregorian, today()));
+        assert_eq!(found.gregorian_date, "2024-12-23");
+    }
+
+    #[test]
+    fn ethiopian_dates() {
+        let found = query("Meskerem 1 2018");
+        assert_eq!((found.kind, found.date), (QueryKind::Ethiopian, EthiopianDate::new(2018, 1, 1)));
+        assert_eq!(query("Tir 5").date, EthiopianDate::new(2017, 5, 5));
+    }
+
+    #[test]
+    fn next_ethiopian_date() {
+        let found = query("next meskerem 1");
+        assert_eq!((found.kind, found.date), (QueryKind::Ethiopian, EthiopianDate::new(2018, 1, 1)));
+        assert_eq!(query("next Tir 5").date, EthiopianDate::new(2017, 5, 5));
+        // "next" skips today, like "next Friday"
+        assert_eq!(query("next Tahsas 14").date, EthiopianDate::new(2018, 4, 14));
+        assert!(query_date("next Meskerem 1 2018", &today()).is_none());
+    }
+
+    #[test]
+    fn holidays() {
+        let found = query("Fasika 2017");
+        assert_eq!(found.kind, QueryKind::Holiday);
+        assert_eq!(found.holiday.map(|holiday| holiday.id).as_deref(), Some("fasika"));
+        assert_eq!(found.gregorian_date, "2025-04-20");
+
+        let next = query("fasika");
+        assert_eq!(next.date, found.date);
+    }
+
+    #[test]
+    fn unreadable_queries() {
+        assert!(query_date("", &today()).is_none());
+        assert!(query_date("   ", &today()).is_none());
+        assert!(query_date("in many days", &today()).is_none());
+    }
+}
@@ -0,0 +1,106 @@
+//! The four traditional Ethiopian seasons and progress through them.
+//!
+//! Each season starts on the 26th of its first month; Kiremt spans the new year
+//! and therefore includes Pagumē.
+
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Season {
+    Tsedey,
+    Bega,
+    Belg,
+    Kiremt,
+}
+
+impl Season {
+    /// Month in which the season starts (on the 26th).
+    fn start_month(&self) -> usize {
+        match self {
+            Season::Tsedey => 1,
+            Season::Bega => 4,
+            Season::Belg => 7,
+            Season::Kiremt => 10,
+        }
+    }
+
+    pub fn amharic_name(&self) -> &'static str {
+        match self {
+            Season::Tsedey => "ፀደይ",
+            Season::Bega => "በጋ",
+            Season::Belg => "በልግ",
+            Season::Kiremt => "ክረምት",
+        }
+    }
+
+    pub fn english_name(&self) -> &'static str {
+        match self {
+            Season::Tsedey => "Tsedey",
+            Season::Bega => "Bega",
+            Season::Belg => "Belg",
+            Season::Kiremt => "Kiremt",
+        }
+    }
+}
+
+const SEASON_START_DAY: usize = 26;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonProgress {
+    pub season: Season,
+    pub name_amharic: String,
+    pub name_english: String,
+    /// 1-based day within the season.
+    pub day_of_season: usize,
+    pub season_length: usize,
+    pub percent: f64,
+}
+
+fn year_length(year: usize) -> usize {
+    360 + EthiopianDate::pagume_length(year)
+}
+
+/// Returns the season a date falls in and how far through it the date is.
+pub fn season_progress(date: &EthiopianDate) -> SeasonProgress {
+    let today = date.day_of_year();
+    let start_of = |season: Season| EthiopianDate::new(date.year, season.start_month(), SEASON_START_DAY).day_of_year();
+
+    let (season, day_of_season, season_length) = if today < start_of(Season::Tsedey) {
+        // Kiremt that started in the previous year.
+        let started = year_length(date.year - 1) - start_of(Season::Kiremt) + 1;
+        (Season::Kiremt, started + today, started + start_of(Season::Tsedey) - 1)
+    } else if today >= start_of(Season::Kiremt) {
+        let started = year_length(date.year) - start_of(Season::Kiremt) + 1;
+        (
+            Season::Kiremt,
+            today - start_of(Season::Kiremt) + 1,
+            started + start_of(Season::Tsedey) - 1,
+        )
+    } else {
+        let season = [Season::Belg, Season::Bega, Season::Tsedey]
+            .into_iter()
+            .find(|season| today >= start_of(*season))
+            .unwrap_or(Season::Tsedey);
+        let next = match season {
+            Season::Tsedey => Season::Bega,
+            Season::Bega => Season::Belg,
+            _ => Season::Kiremt,
+        };
+        (
+            season,
+            today - start_of(season) + 1,
+            start_of(next) - start_of(season),
+        )
+    };
+
+    SeasonProgress {
+        season,
+        name_amharic: season.amharic_name().to_string(),
+        name_english: season.english_name().to_string(),
+        day_of_season,
+        season_length,
+        percent: day_of_season as f64 / season_length as f64 * 100.0,
+    }
+}
@@ -0,0 +1,168 @@
+//! Dates as spoken sentences, for VoiceOver labels and text to speech.
+//!
+//! Amharic and English spell every number out, e.g. "ዛሬ ማክሰኞ ታኅሣሥ አሥራ አራት ሁለት ሺህ
+//! አሥራ ሰባት ዓ.ም ነው". Tigrinya and Oromo keep the day and year as digits, which the
+//! screen reader reads in its own voice.
+
+use crate::locale::Language;
+use crate::EthiopianDate;
+
+const AMHARIC_ONES: [&str; 10] = ["", "አንድ", "ሁለት", "ሦስት", "አራት", "አምስት", "ስድስት", "ሰባት", "ስምንት", "ዘጠኝ"];
+const AMHARIC_TENS: [&str; 10] = ["", "አሥር", "ሃያ", "ሠላሳ", "አርባ", "ሃምሳ", "ስልሳ", "ሰባ", "ሰማንያ", "ዘጠና"];
+
+const ENGLISH_ONES: [&str; 20] = [
+    "", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten", "eleven", "twelve", "thirteen",
+    "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+const ENGLISH_TENS: [&str; 10] = ["", "ten", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+/// Amharic words for 1 to 999.
+fn amharic_below_thousand(number: usize, words: &mut Vec<&'static str>) {
+    let (hundreds, rest) = (number / 100, number % 100);
+    if hundreds > 0 {
+        words.extend([AMHARIC_ONES[hundreds], "መቶ"]);
+    }
+    match rest {
+        0 => {}
+        10 => words.push(AMHARIC_TENS[1]),
+        11..=19 => words.extend(["አሥራ", AMHARIC_ONES[rest - 10]]),
+        _ => {
+            if rest >= 20 {
+                words.push(AMHARIC_TENS[rest / 10]);
+            }
+            if rest % 10 > 0 {
+                words.push(AMHARIC_ONES[rest % 10]);
+            }
+        }
+    }
+}
+
+/// An Arabic number in Amharic words, e.g. 2017 as "ሁለት ሺህ አሥራ ሰባት".
+pub fn amharic_number(number: usize) -> String {
+    if number == 0 {
+        return "ዜሮ".to_string();
+    }
+    let mut words = Vec::new();
+    for (scale, name) in [(1_000_000_000, "ቢሊዮን"), (1_000_000, "ሚሊዮን"), (1_000, "ሺህ")] {
+        let group = number / scale % 1000;
+        if group > 0 {
+            amharic_below_thousand(group, &mut words);
+            words.push(name);
+        }
+    }
+    amharic_below_thousand(number % 1000, &mut words);
+    words.join(" ")
+}
+
+/// English words for 1 to 999.
+fn english_below_thousand(number: usize, words: &mut Vec<String>) {
+    let (hundreds, rest) = (number / 100, number % 100);
+    if hundreds > 0 {
+        words.push(format!("{} hundred", ENGLISH_ONES[hundreds]));
+    }
+    match rest {
+        0 => {}
+        1..=19 => words.push(ENGLISH_ONES[rest].to_string()),
+        _ if rest.is_multiple_of(10) => words.push(ENGLISH_TENS[rest / 10].to_string()),
+        _ => words.push(format!("{}-{}", ENGLISH_TENS[rest / 10], ENGLISH_ONES[rest % 10])),
+    }
+}
+
+/// An Arabic number in English words, e.g. 2017 as "two thousand seventeen".
+pub fn english_number(number: usize) -> String {
+    if number == 0 {
+        return "zero".to_string();
+    }
+    let mut words = Vec::new();
+    for (scale, name) in [(1_000_000_000, "billion"), (1_000_000, "million"), (1_000, "thousand")] {
+        let group = number / scale % 1000;
+        if group > 0 {
+            english_below_thousand(group, &mut words);
+            words.push(name.to_string());
+        }
+    }
+    english_below_thousand(number % 1000, &mut words);
+    words.join(" ")
+}
+
+/// An Arabic number as an English ordinal, e.g. 14 as "fourteenth" and 22 as "twenty-second".
+pub fn english_ordinal(number: usize) -> String {
+    let cardinal = english_number(number);
+    let (head, last) = match cardinal.rfind([' ', '-']) {
+        Some(index) => cardinal.split_at(index + 1),
+        None => ("", cardinal.as_str()),
+    };
+    let ordinal = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        _ if last.ends_with('y') => format!("{}ieth", &last[..last.len() - 1]),
+        _ => format!("{}th", last),
+    };
+    format!("{}{}", head, ordinal)
+}
+
+/// `date` as a sentence in `language`; today's date is phrased as "today is ...".
+pub fn spoken_date(date: &EthiopianDate, language: Language) -> String {
+    let is_today = *date == EthiopianDate::today();
+    let weekday = date.weekday_name(language);
+    let month = date.month_name(language);
+    match language {
+        Language::Amharic => {
+            let text = format!("{} {} {} {} ዓ.ም", weekday, month, amharic_number(date.day), amharic_number(date.year));
+            if is_today { format!("ዛሬ {} ነው", text) } else { text }
+        }
+        Language::English => {
+            let text = format!(
+                "{}, {} {}, {}, Ethiopian calendar",
+                weekday,
+                month,
+                english_ordinal(date.day),
+                english_number(date.year)
+            );
+            if is_today { format!("Today is {}", text) } else { text }
+        }
+        Language::Tigrinya => format!("{} {} {} {} ዓ.ም", weekday, month, date.day, date.year),
+        Language::Oromo => format!("{}, {} {}, {}", weekday, month, date.day, date.year),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numbers_in_words() {
+        let table = [
+            (11, "አሥራ አንድ", "eleven", "eleventh"),
+            (20, "ሃያ", "twenty", "twentieth"),
+            (22, "ሃያ ሁለት", "twenty-two", "twenty-second"),
+            (100, "አንድ መቶ", "one hundred", "one hundredth"),
+            (2017, "ሁለት ሺህ አሥራ ሰባት", "two thousand seventeen", "two thousand seventeenth"),
+        ];
+        for (number, amharic, english, ordinal) in table {
+            assert_eq!(amharic_number(number), amharic, "{}", number);
+            assert_eq!(english_number(number), english, "{}", number);
+            assert_eq!(english_ordinal(number), ordinal, "{}", number);
+        }
+    }
+
+    #[test]
+    fn dates_in_each_language() {
+        // Tahsas 14, 2017 was a Monday (December 23, 2024).
+        let date = EthiopianDate::new(2017, 4, 14);
+        let table = [
+            (Language::Amharic, "ሰኞ ታኅሣሥ አሥራ አራት ሁለት ሺህ አሥራ ሰባት ዓ.ም"),
+            (Language::English, "Monday, Tahsas fourteenth, two thousand seventeen, Ethiopian calendar"),
+            (Language::Tigrinya, "ሰኑይ ታሕሳስ 14 2017 ዓ.ም"),
+            (Language::Oromo, "Wiixata, Muddee 14, 2017"),
+        ];
+        for (language, expected) in table {
+            assert_eq!(spoken_date(&date, language), expected, "{:?}", language);
+        }
+    }
+}
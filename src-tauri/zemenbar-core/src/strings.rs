@@ -0,0 +1,174 @@
+//! UI labels in every display language.
+//!
+//! The frontend and the native tray menu both read their labels from here, so a
+//! translation only has to be added once. Month and weekday names live in `locale`.
+//! Installed localization packs replace labels and holiday names of their language.
+
+use crate::holidays;
+use crate::locale::Language;
+use crate::packs;
+use crate::EthiopianDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Labels by key: (key, [Amharic, English, Tigrinya, Oromo]).
+/// Labels with `{name}`, `{years}`, `{days}` and `{version}` placeholders are filled in by their users.
+const LABELS: [(&str, [&str; 4]); 47] = [
+    ("today", ["ዛሬ", "Today", "ሎሚ", "Har'a"]),
+    ("settings", ["ቅንብሮች", "Settings", "ቅጥዕታት", "Qindaa'ina"]),
+    ("copy", ["ቅዳ", "Copy", "ቅዳሕ", "Garagalchi"]),
+    ("copy_date", ["ቀኑን ቅዳ", "Copy Date", "ዕለት ቅዳሕ", "Guyyaa Garagalchi"]),
+    ("copy_to_clipboard", ["ቀኑን ወደ ቅንጥብ ሰሌዳ ቅዳ", "Copy date to clipboard", "ዕለት ናብ ክሊፕቦርድ ቅዳሕ", "Guyyaa gara kilippboordiitti garagalchi"]),
+    ("show_calendar", ["ቀን መቁጠሪያ አሳይ", "Show Calendar", "ዓውደ ኣዋርሕ ኣርኢ", "Kalaandarii Agarsiisi"]),
+    ("quit", ["ውጣ", "Quit", "ውጻእ", "Ba'i"]),
+    ("letter_date_stamp", ["የደብዳቤ ቀን ማህተም", "Letter Date Stamp", "ናይ ደብዳቤ ዕለት ማሕተም", "Chaappaa Guyyaa Xalayaa"]),
+    ("upcoming_holidays", ["የሚመጡ በዓላት", "Upcoming Holidays", "ዝመጽኡ በዓላት", "Ayyaanota Dhufan"]),
+    ("print", ["አትም…", "Print…", "ሕተም…", "Maxxansi…"]),
+    ("print_calendar", ["ቀን መቁጠሪያ ማተም", "Print Calendar", "ዓውደ ኣዋርሕ ምሕታም", "Kalaandarii Maxxansuu"]),
+    ("import_contacts_birthdays", ["የልደት ቀኖችን ከአድራሻዎች አስገባ", "Import Birthdays from Contacts", "መዓልትታት ልደት ካብ ኣድራሻታት ኣእቱ", "Guyyoota Dhalootaa Teessoo Irraa Galchi"]),
+    ("update_available", ["አዲስ ስሪት አለ፦ {version}", "Update Available: {version}", "ሓድሽ ስሪት ኣሎ፦ {version}", "Haaromsi Jira: {version}"]),
+    ("amete_alem_era", ["ዓመተ ዓለም", "Amete Alem era", "ዓመተ ዓለም", "Bara Amata Alam"]),
+    ("desktop_widget", ["የዴስክቶፕ መግብር", "Desktop widget", "ናይ ዴስክቶፕ መግብር", "Widget desktoppii"]),
+    ("week_starts_monday", ["ሰኞ", "Mon", "ሰኑ", "Wix"]),
+    ("week_starts_sunday", ["እሁድ", "Sun", "ሰን", "Dil"]),
+    ("text_format", ["ጽሑፍ", "Text", "ጽሑፍ", "Barreeffama"]),
+    ("numeric_format", ["ቀቀ/ወወ", "DD/MM", "ዕዕ/ወወ", "GG/JJ"]),
+    ("qen", ["ቀን", "ቀን", "ቀን", "ቀን"]),
+    ("clock_day", ["ቀን", "day", "መዓልቲ", "guyyaa"]),
+    ("clock_night", ["ሌሊት", "night", "ለይቲ", "halkan"]),
+    ("synaxarium", ["ስንክሳር", "Synaxarium", "ስንክሳር", "Sinkisaar"]),
+    ("ui_scale_compact", ["ትንሽ", "Compact", "ንእሽተይ", "Xiqqaa"]),
+    ("ui_scale_normal", ["መደበኛ", "Normal", "ንቡር", "Idilee"]),
+    ("ui_scale_large", ["ትልቅ", "Large", "ዓቢ", "Guddaa"]),
+    ("clock_in_tray", ["ሰዓት በምናሌ አሞሌው ላይ", "Clock in the menu bar", "ሰዓት ኣብ ሜኑ ባር", "Sa'aatii baarii menuu irratti"]),
+    ("birthday", ["የልደት ቀን", "Birthday", "መዓልቲ ልደት", "Guyyaa Dhalootaa"]),
+    ("anniversary", ["ዓመታዊ በዓል", "Anniversary", "ዓመታዊ በዓል", "Ayyaana Waggaa"]),
+    ("birthday_turns", ["{name} ዛሬ {years} ዓመት ሞላ", "{name} turns {years} today", "{name} ሎሚ {years} ዓመት መሊኡ", "{name} har'a waggaa {years} guute"]),
+    ("anniversary_years", ["{name}፡ ዛሬ {years} ዓመት", "{name}: {years} years today", "{name}፡ ሎሚ {years} ዓመት", "{name}: har'a waggaa {years}"]),
+    ("countdown_day_left", ["{days} ቀን ቀርቷል", "{days} day left", "{days} መዓልቲ ተሪፉ", "Guyyaa {days} hafe"]),
+    ("countdown_days_left", ["{days} ቀን ቀርቷል", "{days} days left", "{days} መዓልቲ ተሪፉ", "Guyyoota {days} hafe"]),
+    ("countdown_today", ["ዛሬ ነው", "Today", "ሎሚ እዩ", "Har'a"]),
+    ("upcoming", ["የሚመጡ በዓላት", "Upcoming", "ዝመጽኡ በዓላት", "Ayyaanota dhufan"]),
+    ("search_dates", ["ቀን ወይም በዓል ፈልግ", "Search a date or holiday", "ዕለት ወይ በዓል ድለ", "Guyyaa ykn ayyaana barbaadi"]),
+    ("category_national", ["ብሔራዊ", "National", "ሃገራዊ", "Biyyaalessaa"]),
+    ("category_orthodox", ["ኦርቶዶክስ", "Orthodox", "ኦርቶዶክስ", "Ortodoksii"]),
+    ("category_muslim", ["እስላማዊ", "Muslim", "እስላማዊ", "Musliimaa"]),
+    ("category_cultural", ["ባህላዊ", "Cultural", "ባህላዊ", "Aadaa"]),
+    ("category_academic", ["ትምህርታዊ", "Academic", "ትምህርታዊ", "Barnootaa"]),
+    ("academic_school_terms", ["የትምህርት ቤት መንፈቀ ዓመታት", "School terms", "ናይ ቤት ትምህርቲ መንፈቕ ዓመታት", "Semisteroota mana barumsaa"]),
+    ("academic_university_semesters", ["የዩኒቨርሲቲ ሴሚስተሮች", "University semesters", "ናይ ዩኒቨርሲቲ ሴሚስተራት", "Semisteroota yuunivarsiitii"]),
+    ("academic_national_exams", ["ብሔራዊ ፈተናዎች", "National exams", "ሃገራዊ ፈተናታት", "Qormaata biyyaalessaa"]),
+    ("system_calendar_events", ["የቀን መቁጠሪያ መተግበሪያ ክስተቶች", "Calendar app events", "ናይ ዓውደ ኣዋርሕ ኣፕ ፍጻመታት", "Taateewwan appii kalaandarii"]),
+    ("launch_at_login", ["ሲገቡ አስጀምር", "Launch at login", "ምስ ኣተኹም ጀምር", "Yeroo seentu jalqabi"]),
+    ("loading", ["ቀን መቁጠሪያ በመጫን ላይ...", "Loading calendar...", "ዓውደ ኣዋርሕ ይጽዕን ኣሎ...", "Kalaandariin fe'amaa jira..."]),
+];
+
+/// Tigrinya and Oromo names of the built-in holidays: (id, Tigrinya, Oromo).
+///
+/// Amharic and English names come with the holidays themselves.
+const HOLIDAY_NAMES: [(&str, &str, &str); 22] = [
+    ("enkutatash", "ሓድሽ ዓመት", "Bara Haaraa"),
+    ("meskel", "መስቀል", "Masqala"),
+    ("timket", "ጥምቀት", "Cuuphaa"),
+    ("adwa", "በዓል ዓወት ዓድዋ", "Ayyaana Injifannoo Adwaa"),
+    ("arbegnoch", "መዓልቲ ጀጋኑ", "Guyyaa Gootota"),
+    ("ginbot_20", "ግንቦት ፳", "Caamsaa 20"),
+    ("genna", "ልደት", "Qillee"),
+    ("labour_day", "መዓልቲ ሰራሕተኛታት", "Guyyaa Hojjettootaa"),
+    ("nineveh", "ጾመ ነነዌ", "Soomana Nanawee"),
+    ("abiy_tsom", "ዓቢይ ጾም", "Soomana Guddaa"),
+    ("debre_zeit", "ደብረ ዘይት", "Dabra Zeyit"),
+    ("hosanna", "ሆሳዕና", "Hosaa'inaa"),
+    ("siklet", "ስቕለት", "Siqlee"),
+    ("fasika", "ፋሲካ", "Fasikaa"),
+    ("rikbe_kahnat", "ርክበ ካህናት", "Rikba Kahinaat"),
+    ("erget", "ዕርገት", "Ergata"),
+    ("paraclete", "ጰራቅሊጦስ", "Pheraqliixos"),
+    ("tsome_hawaryat", "ጾመ ሓዋርያት", "Soomana Hawaariyaa"),
+    ("tsome_dihnet", "ጾመ ድሕነት", "Soomana Fayyinaa"),
+    ("eid_al_fitr", "ዒድ ኣል ፈጥር", "Iid Al Fitri"),
+    ("eid_al_adha", "ዒድ ኣል ኣድሓ (ዓረፋ)", "Iid Al Adhaa (Arafaa)"),
+    ("mawlid", "መውሊድ", "Mawliida"),
+];
+
+/// Every UI label in one language, as sent to the frontend.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiStrings {
+    pub language: Language,
+    pub labels: BTreeMap<String, String>,
+    /// Holiday names by holiday id, including those from installed packs.
+    pub holidays: BTreeMap<String, String>,
+}
+
+fn column(language: Language) -> usize {
+    match language {
+        Language::Amharic => 0,
+        Language::English => 1,
+        Language::Tigrinya => 2,
+        Language::Oromo => 3,
+    }
+}
+
+/// The text the first localization pack of `language` that has `key` in `field` gives it.
+fn pack_text(
+    language: Language,
+    key: &str,
+    field: fn(&packs::PackLocalization) -> &BTreeMap<String, String>,
+) -> Option<String> {
+    packs::pack_localizations()
+        .iter()
+        .filter(|pack| pack.language == language)
+        .find_map(|pack| field(pack).get(key).cloned())
+}
+
+/// The label for `key`, or the key itself when it is neither in a pack nor in the catalog.
+pub fn label(language: Language, key: &str) -> String {
+    pack_text(language, key, |pack| &pack.labels).unwrap_or_else(|| {
+        LABELS
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, names)| names[column(language)].to_string())
+            .unwrap_or_else(|| key.to_string())
+    })
+}
+
+/// A holiday's name in `language`.
+///
+/// Holidays without a Tigrinya or Oromo name, such as those from packs, fall back
+/// to their Amharic or English name respectively.
+pub fn holiday_name(holiday: &holidays::Holiday, language: Language) -> String {
+    if let Some(name) = pack_text(language, &holiday.id, |pack| &pack.holidays) {
+        return name;
+    }
+    let translated = HOLIDAY_NAMES.iter().find(|(id, _, _)| *id == holiday.id);
+    match (language, translated) {
+        (Language::Amharic, _) => holiday.name_amharic.clone(),
+        (Language::English, _) => holiday.name_english.clone(),
+        (Language::Tigrinya, Some((_, tigrinya, _))) => tigrinya.to_string(),
+        (Language::Tigrinya, None) => holiday.name_amharic.clone(),
+        (Language::Oromo, Some((_, _, oromo))) => oromo.to_string(),
+        (Language::Oromo, None) => holiday.name_english.clone(),
+    }
+}
+
+/// The whole catalog for one language.
+pub fn ui_strings(language: Language) -> UiStrings {
+    let mut labels: BTreeMap<String, String> = LABELS
+        .iter()
+        .map(|(key, names)| (key.to_string(), names[column(language)].to_string()))
+        .collect();
+    // The first pack with a label wins, so apply the packs in reverse folder order
+    for pack in packs::pack_localizations().into_iter().rev().filter(|pack| pack.language == language) {
+        labels.extend(pack.labels);
+    }
+    let holidays = holidays::holidays_for_year(EthiopianDate::today().year)
+        .iter()
+        .map(|holiday| (holiday.id.clone(), holiday_name(holiday, language)))
+        .collect();
+
+    UiStrings {
+        language,
+        labels,
+        holidays,
+    }
+}
@@ -0,0 +1,91 @@
+//! Daily commemorations of the Synaxarium (ስንክሳር).
+//!
+//! The bundled dataset, `data/synaxarium.json`, lists for each day of the year the
+//! principal annual feast when there is one, followed by the saint, angel or feast the
+//! Ethiopian Orthodox Church commemorates on that day of every month (ወርኃዊ በዓላት),
+//! e.g. Saint Michael on the 12th. Pagume has Saint Raphael on the 3rd only.
+//!
+//! The dataset is written in the format of Synaxarium data packs (see `packs`), and a
+//! day listed by an installed pack replaces the bundled one, so a pack can supply the
+//! full readings.
+
+use crate::packs::{self, PackKind};
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+static BUNDLED: LazyLock<Vec<SynaxariumEntry>> = LazyLock::new(|| {
+    serde_json::from_str(include_str!("../data/synaxarium.json")).expect("bundled Synaxarium data is valid")
+});
+
+/// A saint, angel or feast remembered on a day.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Commemoration {
+    pub name_amharic: String,
+    pub name_english: String,
+}
+
+/// The commemorations of an Ethiopian month and day, most important first.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SynaxariumEntry {
+    pub month: usize,
+    pub day: usize,
+    pub commemorations: Vec<Commemoration>,
+}
+
+/// Entries of every loaded Synaxarium pack, in folder order.
+fn pack_entries() -> Vec<SynaxariumEntry> {
+    packs::pack_data(PackKind::Synaxarium)
+        .into_iter()
+        .filter_map(|data| serde_json::from_value::<Vec<SynaxariumEntry>>(data).ok())
+        .flatten()
+        .collect()
+}
+
+/// The commemorations of Ethiopian `month`/`day`, from the first pack that lists the
+/// day or else the bundled dataset; `None` when neither has the day.
+pub fn entry(month: usize, day: usize) -> Option<SynaxariumEntry> {
+    pack_entries()
+        .into_iter()
+        .chain(BUNDLED.iter().cloned())
+        .find(|entry| entry.month == month && entry.day == day)
+}
+
+/// The entry of each day of an Ethiopian month that has one, in day order.
+pub fn entries_in_month(month: usize) -> Vec<SynaxariumEntry> {
+    let from_packs = pack_entries();
+    (1..=30)
+        .filter_map(|day| {
+            from_packs
+                .iter()
+                .chain(BUNDLED.iter())
+                .find(|entry| entry.month == month && entry.day == day)
+                .cloned()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundled_data_covers_every_day_but_pagume() {
+        for month in 1..=12 {
+            let days: Vec<usize> = entries_in_month(month).iter().map(|entry| entry.day).collect();
+            assert_eq!(days, (1..=30).collect::<Vec<_>>(), "month {}", month);
+        }
+        let pagume: Vec<usize> = entries_in_month(13).iter().map(|entry| entry.day).collect();
+        assert_eq!(pagume, vec![3]);
+        assert!(BUNDLED.iter().all(|entry| !entry.commemorations.is_empty()));
+    }
+
+    #[test]
+    fn annual_feast_comes_before_the_monthly_one() {
+        let timket = entry(5, 11).unwrap();
+        assert_eq!(timket.commemorations[0].name_english, "Timket, the Epiphany");
+        assert_eq!(timket.commemorations[1].name_amharic, "ፋኑኤል፣ ሐና ወኢያቄም");
+        assert_eq!(entry(3, 12).unwrap().commemorations.len(), 1);
+        assert_eq!(entry(13, 4), None);
+        assert_eq!(entry(14, 1), None);
+    }
+}
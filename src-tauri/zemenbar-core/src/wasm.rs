@@ -0,0 +1,81 @@
+//! JavaScript bindings for the date code, built with `--features wasm` as a cdylib (for
+//! example `cargo rustc -p zemenbar-core --release --target wasm32-unknown-unknown
+//! --features wasm --crate-type cdylib`, then `wasm-bindgen` on the output).
+//!
+//! Lets a web page, or the app's own frontend, convert, format and look up holidays
+//! with the same Rust code as the app instead of a JavaScript copy of it. Dates go
+//! in as year, month and day numbers; structured results come out as plain objects
+//! shaped like the Tauri commands' JSON. Errors are thrown as JavaScript `Error`s.
+
+use crate::{date_input, holidays, CalendarMonth, EthiopianDate, EthiopianDateInput};
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+fn to_js(value: &impl Serialize) -> Result<JsValue, JsError> {
+    serde_wasm_bindgen::to_value(value).map_err(|e| JsError::new(&format!("Failed to convert result: {}", e)))
+}
+
+fn ethiopian(year: usize, month: usize, day: usize) -> Result<EthiopianDate, JsError> {
+    EthiopianDateInput { year, month, day }.to_date().map_err(|e| JsError::new(&e))
+}
+
+/// Today's Ethiopian date in Addis Ababa.
+#[wasm_bindgen]
+pub fn today() -> Result<JsValue, JsError> {
+    to_js(&EthiopianDate::today())
+}
+
+/// The Ethiopian date of a Gregorian date, as `{ year, month, day, day_geez }`.
+#[wasm_bindgen(js_name = toEthiopian)]
+pub fn to_ethiopian(year: i32, month: u32, day: u32) -> Result<JsValue, JsError> {
+    let date = EthiopianDate::from_gregorian(year, month, day)
+        .ok_or_else(|| JsError::new(&format!("Invalid Gregorian date: {}-{}-{}", year, month, day)))?;
+    to_js(&date)
+}
+
+/// The Gregorian date of an Ethiopian date, as `YYYY-MM-DD`.
+#[wasm_bindgen(js_name = toGregorian)]
+pub fn to_gregorian(year: usize, month: usize, day: usize) -> Result<String, JsError> {
+    let date = ethiopian(year, month, day)?;
+    date.to_gregorian()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .ok_or_else(|| JsError::new(&format!("Failed to convert {} to Gregorian", date)))
+}
+
+/// Renders an Ethiopian date with a `%` token template; see `format` for the tokens.
+#[wasm_bindgen(js_name = formatDate)]
+pub fn format_date(year: usize, month: usize, day: usize, template: &str) -> Result<String, JsError> {
+    Ok(ethiopian(year, month, day)?.format(template))
+}
+
+/// Reads a written Ethiopian date such as "ጥር 11 2017" or "Tir 11, 2017".
+#[wasm_bindgen(js_name = parseDate)]
+pub fn parse_date(input: &str) -> Result<JsValue, JsError> {
+    let date = date_input::parse_date(input).map_err(|e| JsError::new(&e.to_string()))?;
+    to_js(&date)
+}
+
+/// A number in Geez numerals, e.g. 2017 as "፳፻፲፯".
+#[wasm_bindgen(js_name = toGeezNumber)]
+pub fn to_geez_number(number: usize) -> String {
+    EthiopianDate::to_geez_number(number)
+}
+
+/// Reads a Geez numeral back into a number.
+#[wasm_bindgen(js_name = fromGeezNumber)]
+pub fn from_geez_number(text: &str) -> Result<usize, JsError> {
+    EthiopianDate::from_geez_number(text).map_err(|e| JsError::new(&e.to_string()))
+}
+
+/// Every holiday and observance of an Ethiopian year, in date order.
+#[wasm_bindgen(js_name = holidaysForYear)]
+pub fn holidays_for_year(year: usize) -> Result<JsValue, JsError> {
+    to_js(&holidays::holidays_for_year(year))
+}
+
+/// The month grid, with weeks starting on `week_start` (0 = Sunday).
+#[wasm_bindgen(js_name = calendarMonth)]
+pub fn calendar_month(year: usize, month: usize, week_start: usize) -> Result<JsValue, JsError> {
+    ethiopian(year, month, 1)?;
+    to_js(&CalendarMonth::with_week_start(year, month, week_start))
+}